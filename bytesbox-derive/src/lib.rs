@@ -0,0 +1,105 @@
+//! Derive macro for the `ByteRecord` trait of `bytesbox`.
+//!
+//! Enable the `derive` feature of `bytesbox` and use the macro through
+//! `bytesbox::record::ByteRecord` rather than depending on this crate directly.
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, Data, DeriveInput, Fields};
+
+/// Implements `ByteRecord` and `RecordField` for a struct whose fields all implement
+/// `RecordField`.
+///
+/// Fields are encoded in declaration order, each at its fixed width, with no padding.
+#[proc_macro_derive(ByteRecord)]
+pub fn derive_byte_record(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "ByteRecord can only be derived for structs",
+            ))
+        }
+    };
+
+    let types: Vec<_> = fields.iter().map(|field| &field.ty).collect();
+    let accessors: Vec<TokenStream2> = fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| match &field.ident {
+            Some(ident) => quote!(#ident),
+            None => {
+                let index = syn::Index::from(index);
+                quote!(#index)
+            }
+        })
+        .collect();
+    let locals: Vec<_> = (0..types.len())
+        .map(|index| format_ident!("field_{}", index))
+        .collect();
+    let construct = match fields {
+        Fields::Named(_) => quote!(Self { #(#accessors: #locals),* }),
+        Fields::Unnamed(_) => quote!(Self(#(#locals),*)),
+        Fields::Unit => quote!(Self),
+    };
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::bytesbox::record::ByteRecord for #name #ty_generics #where_clause {
+            const SIZE: usize = 0 #(+ <#types as ::bytesbox::record::RecordField>::WIDTH)*;
+
+            fn to_bytes(&self) -> ::std::vec::Vec<u8> {
+                let mut out = ::std::vec::Vec::with_capacity(
+                    <Self as ::bytesbox::record::ByteRecord>::SIZE,
+                );
+                #(::bytesbox::record::RecordField::write_field(&self.#accessors, &mut out);)*
+                out
+            }
+
+            #[allow(unused_mut, unused_assignments, unused_variables)]
+            fn from_bytes(
+                bytes: &[u8],
+            ) -> ::std::result::Result<Self, ::bytesbox::error::RecordSizeMismatch> {
+                let expected = <Self as ::bytesbox::record::ByteRecord>::SIZE;
+                if bytes.len() != expected {
+                    return ::std::result::Result::Err(::bytesbox::error::RecordSizeMismatch {
+                        expected,
+                        found: bytes.len(),
+                    });
+                }
+                let mut rest = bytes;
+                #(
+                    let (field, tail) =
+                        rest.split_at(<#types as ::bytesbox::record::RecordField>::WIDTH);
+                    let #locals = <#types as ::bytesbox::record::RecordField>::read_field(field);
+                    rest = tail;
+                )*
+                ::std::result::Result::Ok(#construct)
+            }
+        }
+
+        impl #impl_generics ::bytesbox::record::RecordField for #name #ty_generics #where_clause {
+            const WIDTH: usize = <Self as ::bytesbox::record::ByteRecord>::SIZE;
+
+            fn write_field(&self, out: &mut ::std::vec::Vec<u8>) {
+                out.extend_from_slice(&::bytesbox::record::ByteRecord::to_bytes(self));
+            }
+
+            fn read_field(bytes: &[u8]) -> Self {
+                match <Self as ::bytesbox::record::ByteRecord>::from_bytes(bytes) {
+                    ::std::result::Result::Ok(record) => record,
+                    ::std::result::Result::Err(err) => ::std::panic!("{}", err),
+                }
+            }
+        }
+    })
+}
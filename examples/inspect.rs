@@ -0,0 +1,112 @@
+//! Examines a ByteBox snapshot file written by `ByteBox::write_snapshot`.
+//!
+//! cargo run --example inspect --features inspect -- <snapshot> <command>
+//!
+//! Commands:
+//!   stats            entry counts, byte totals and the tuning report
+//!   keys [prefix]    one key per line, optionally only those starting with `prefix`
+//!   json [prefix]    entries as a JSON array of {"key", "value"} objects
+//!   hex [prefix]     entries as `key<TAB>value` lines, hex encoded
+use bytesbox::snapshot::SnapshotReader;
+use bytesbox::ByteBox;
+use std::env;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Write};
+use std::process;
+
+const USAGE: &str =
+    "usage: inspect <snapshot> (stats | keys [prefix] | json [prefix] | hex [prefix])";
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.len() < 2 {
+        eprintln!("{}", USAGE);
+        process::exit(2);
+    }
+    if let Err(error) = run(&args[0], &args[1], args.get(2).map(String::as_bytes)) {
+        eprintln!("inspect: {}", error);
+        process::exit(1);
+    }
+}
+
+fn run(path: &str, command: &str, prefix: Option<&[u8]>) -> io::Result<()> {
+    let reader = SnapshotReader::new(BufReader::new(File::open(path)?))?;
+    let prefix = prefix.unwrap_or_default();
+    let entries = reader.filter(|entry| match entry {
+        Ok((key, _)) => key.starts_with(prefix),
+        Err(_) => true,
+    });
+    let mut out = BufWriter::new(io::stdout().lock());
+
+    match command {
+        "stats" => {
+            let mut byte_box = ByteBox::new();
+            for entry in entries {
+                let (key, value) = entry?;
+                byte_box.insert(&key, &value);
+            }
+            let stats = byte_box.stats();
+            writeln!(out, "entries: {}", stats.len)?;
+            writeln!(out, "key bytes: {}", stats.key_bytes)?;
+            writeln!(out, "value bytes: {}", stats.value_bytes)?;
+            writeln!(out, "{}", byte_box.analyze())?;
+        }
+        "keys" => {
+            for entry in entries {
+                let (key, _) = entry?;
+                writeln!(out, "{}", String::from_utf8_lossy(&key))?;
+            }
+        }
+        "json" => {
+            write!(out, "[")?;
+            for (index, entry) in entries.enumerate() {
+                let (key, value) = entry?;
+                if index > 0 {
+                    write!(out, ",")?;
+                }
+                write!(out, "\n  {{\"key\": ")?;
+                write_json(&mut out, &key)?;
+                write!(out, ", \"value\": ")?;
+                write_json(&mut out, &value)?;
+                write!(out, "}}")?;
+            }
+            writeln!(out, "\n]")?;
+        }
+        "hex" => {
+            for entry in entries {
+                let (key, value) = entry?;
+                writeln!(out, "{}\t{}", hex(&key), hex(&value))?;
+            }
+        }
+        _ => {
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        }
+    }
+    out.flush()
+}
+
+/// Writes UTF-8 bytes as a JSON string and anything else as `{"hex": "..."}`.
+fn write_json<W: Write>(out: &mut W, bytes: &[u8]) -> io::Result<()> {
+    let text = match std::str::from_utf8(bytes) {
+        Ok(text) => text,
+        Err(_) => return write!(out, "{{\"hex\": \"{}\"}}", hex(bytes)),
+    };
+    write!(out, "\"")?;
+    for c in text.chars() {
+        match c {
+            '"' => write!(out, "\\\"")?,
+            '\\' => write!(out, "\\\\")?,
+            '\n' => write!(out, "\\n")?,
+            '\r' => write!(out, "\\r")?,
+            '\t' => write!(out, "\\t")?,
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32)?,
+            c => write!(out, "{}", c)?,
+        }
+    }
+    write!(out, "\"")
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
@@ -0,0 +1,48 @@
+//! Times inserts and lookups on a reproducible Zipfian workload.
+//!
+//! cargo run --release --example workload --features workload
+use bytesbox::workload::{KeyDistribution, ValueSize, Workload};
+use bytesbox::ByteBox;
+use std::time::Instant;
+
+fn main() {
+    let workload = Workload {
+        key_space: 100_000,
+        keys: KeyDistribution::Zipf { exponent: 1.0 },
+        values: ValueSize::Uniform { min: 16, max: 256 },
+        seed: 42,
+    };
+    let operations = 1_000_000;
+
+    let pairs: Vec<_> = workload.pairs().take(operations).collect();
+    let mut byte_box = ByteBox::new();
+    let start = Instant::now();
+    for (key, value) in &pairs {
+        byte_box.insert(key, value);
+    }
+    let insert_time = start.elapsed();
+
+    let keys: Vec<_> = workload.keys().take(operations).collect();
+    let start = Instant::now();
+    let hits = keys
+        .iter()
+        .filter(|key| byte_box.get(key).is_some())
+        .count();
+    let get_time = start.elapsed();
+
+    println!(
+        "{} inserts in {:?} ({:.0} ns/op), {} distinct keys",
+        operations,
+        insert_time,
+        insert_time.as_nanos() as f64 / operations as f64,
+        byte_box.len()
+    );
+    println!(
+        "{} gets in {:?} ({:.0} ns/op), {} hits",
+        operations,
+        get_time,
+        get_time.as_nanos() as f64 / operations as f64,
+        hits
+    );
+    println!("{}", byte_box.analyze());
+}
@@ -0,0 +1,47 @@
+/*
+ * C declarations for the `ffi` feature of the bytesbox crate.
+ *
+ * Build the library with:
+ *   cargo rustc --release --features ffi --crate-type staticlib
+ * (or `--crate-type cdylib` for a shared library) and link against it.
+ */
+#ifndef BYTESBOX_H
+#define BYTESBOX_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct ByteBox ByteBox;
+typedef struct RawExport RawExport;
+
+ByteBox *bytebox_new(void);
+void bytebox_free(ByteBox *table);
+
+bool bytebox_insert(ByteBox *table, const uint8_t *key, size_t key_len,
+                    const uint8_t *value, size_t value_len);
+/* Returns NULL if the key is missing; the pointer is valid until the next mutation. */
+const uint8_t *bytebox_get(const ByteBox *table, const uint8_t *key,
+                           size_t key_len, size_t *value_len);
+bool bytebox_remove(ByteBox *table, const uint8_t *key, size_t key_len);
+size_t bytebox_len(const ByteBox *table);
+
+/*
+ * Flat export: `bytes` holds all keys and values back to back and `offsets` holds
+ * 2 * len + 1 boundaries. Entry i has its key at [offsets[2i], offsets[2i+1]) and its
+ * value at [offsets[2i+1], offsets[2i+2]).
+ */
+RawExport *bytebox_export(const ByteBox *table);
+const uint64_t *bytebox_raw_offsets(const RawExport *raw, size_t *len);
+const uint8_t *bytebox_raw_bytes(const RawExport *raw, size_t *len);
+void bytebox_raw_free(RawExport *raw);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* BYTESBOX_H */
@@ -0,0 +1,252 @@
+//! Tuning reports on how well a table's capacity and hashing fit its keys.
+use super::*;
+
+/// Share of all entries used to measure how concentrated the table is.
+const CONCENTRATION_TARGET: f64 = 0.75;
+
+/// Keys shorter than this cost less than the entry holding them.
+pub const SHORT_KEY_LEN: usize = 8;
+
+/// Keys at least this long dominate the cost of hashing and comparing on every lookup.
+pub const LONG_KEY_LEN: usize = 256;
+
+/// Share of short or long keys above which [`ByteBox::analyze`] flags the key lengths.
+const KEY_LENGTH_SHARE: f64 = 0.5;
+
+/// A concrete tuning action recommended by [`ByteBox::analyze`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Suggestion {
+    /// The table is at or above its load factor threshold and will resize on the next insert.
+    GrowCapacity { recommended: usize },
+    /// The table is allocated far beyond what its entries need.
+    ShrinkCapacity { recommended: usize },
+    /// Chains are long even though keys are spread evenly; a lower load factor would shorten them.
+    LowerLoadFactor { recommended: f32 },
+    /// Entries pile up in a small share of the cells, which points at a weak or attacked hash.
+    SeededHashing {
+        entries_share: f64,
+        buckets_share: f64,
+    },
+    /// Most keys are shorter than [`SHORT_KEY_LEN`], so per-entry overhead outweighs them.
+    GroupShortKeys { share: f64 },
+    /// Most keys are at least [`LONG_KEY_LEN`] long, so every lookup hashes and compares
+    /// them in full.
+    ShortenLongKeys { share: f64 },
+}
+
+impl Display for Suggestion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Suggestion::GrowCapacity { recommended } => write!(
+                f,
+                "load factor threshold reached — preallocate {} cells",
+                recommended
+            ),
+            Suggestion::ShrinkCapacity { recommended } => write!(
+                f,
+                "table is mostly empty — {} cells would be enough",
+                recommended
+            ),
+            Suggestion::LowerLoadFactor { recommended } => write!(
+                f,
+                "chains are long at the current load — consider a load factor of {:.2}",
+                recommended
+            ),
+            Suggestion::SeededHashing {
+                entries_share,
+                buckets_share,
+            } => write!(
+                f,
+                "{:.0}% of entries share {:.0}% of buckets — consider seeded hashing",
+                entries_share * 100.0,
+                buckets_share * 100.0
+            ),
+            Suggestion::GroupShortKeys { share } => write!(
+                f,
+                "{:.0}% of keys are shorter than {} bytes — consider grouping them into fewer entries with `packed::pack_values`",
+                share * 100.0,
+                SHORT_KEY_LEN
+            ),
+            Suggestion::ShortenLongKeys { share } => write!(
+                f,
+                "{:.0}% of keys are {} bytes or longer — consider `PrefixByteBox` or hashing the keys",
+                share * 100.0,
+                LONG_KEY_LEN
+            ),
+        }
+    }
+}
+
+/// A snapshot of the distribution of entries across the cells of a `ByteBox`,
+/// together with the tuning actions it suggests.
+///
+/// The fields are public so the report can be consumed programmatically, while the
+/// `Display` implementation renders it for humans.
+///
+/// This struct is created by the [`ByteBox::analyze`] method.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TuningReport {
+    /// Number of key-value pairs stored.
+    pub len: usize,
+    /// Number of allocated cells.
+    pub allocation: usize,
+    /// Current ratio of entries to cells.
+    pub load_factor: f32,
+    /// Ratio of entries to cells above which the table resizes.
+    pub load_factor_threshold: f32,
+    /// Number of cells holding at least one entry.
+    pub occupied_cells: usize,
+    /// Length of the longest collision chain.
+    pub longest_chain: usize,
+    /// Average chain length over the occupied cells.
+    pub mean_chain: f64,
+    /// Smallest share of cells holding 75% of the entries.
+    pub concentration: f64,
+    /// Capacity that fits the current entries below the load factor threshold.
+    pub recommended_capacity: usize,
+    /// Median key length, in bytes.
+    pub median_key_len: usize,
+    /// Share of keys shorter than [`SHORT_KEY_LEN`].
+    pub short_keys: f64,
+    /// Share of keys at least [`LONG_KEY_LEN`] long.
+    pub long_keys: f64,
+    /// Tuning actions derived from the figures above.
+    pub suggestions: Vec<Suggestion>,
+}
+
+impl TuningReport {
+    pub(crate) fn new(byte_box: &ByteBox) -> Self {
+        let mut chains: Vec<usize> = byte_box.chain_lengths().collect();
+        chains.sort_unstable_by(|a, b| b.cmp(a));
+
+        let len = byte_box.len;
+        let allocation = byte_box.alloc;
+        let threshold = byte_box.load_factor_threshold;
+        let load_factor = if allocation == 0 {
+            0.0
+        } else {
+            len as f32 / allocation as f32
+        };
+        let occupied_cells = chains.iter().filter(|&&depth| depth > 0).count();
+        let longest_chain = chains.first().copied().unwrap_or(0);
+        let mean_chain = if occupied_cells == 0 {
+            0.0
+        } else {
+            len as f64 / occupied_cells as f64
+        };
+
+        // Walk the densest cells first until they hold the target share of entries.
+        let target = (len as f64 * CONCENTRATION_TARGET).ceil() as usize;
+        let mut covered = 0;
+        let mut cells_needed = 0;
+        for depth in &chains {
+            if covered >= target {
+                break;
+            }
+            covered += depth;
+            cells_needed += 1;
+        }
+        let concentration = if allocation == 0 {
+            0.0
+        } else {
+            cells_needed as f64 / allocation as f64
+        };
+
+        let recommended_capacity = ((len as f64 / threshold as f64).ceil() as usize + 1)
+            .next_power_of_two()
+            .max(16);
+
+        let mut suggestions = Vec::new();
+        if load_factor >= threshold {
+            suggestions.push(Suggestion::GrowCapacity {
+                recommended: recommended_capacity,
+            });
+        } else if allocation > recommended_capacity * 4 {
+            suggestions.push(Suggestion::ShrinkCapacity {
+                recommended: recommended_capacity,
+            });
+        }
+
+        // With a perfect spread, the target share fits in `target` cells (or all of them).
+        let ideal = target.min(allocation) as f64 / allocation.max(1) as f64;
+        let skewed = len >= 16 && concentration < ideal * 0.3;
+        if skewed {
+            suggestions.push(Suggestion::SeededHashing {
+                entries_share: covered as f64 / len as f64,
+                buckets_share: concentration,
+            });
+        } else if longest_chain > 8 && threshold > 0.5 {
+            suggestions.push(Suggestion::LowerLoadFactor {
+                recommended: (threshold - 0.25).max(0.5),
+            });
+        }
+
+        let median_key_len = byte_box.key_size_percentiles(&[50.0])[0];
+        let (mut short, mut long) = (0, 0);
+        for (key, _) in byte_box.iter() {
+            short += usize::from(key.len() < SHORT_KEY_LEN);
+            long += usize::from(key.len() >= LONG_KEY_LEN);
+        }
+        let share = |count: usize| count as f64 / len.max(1) as f64;
+        let (short_keys, long_keys) = (share(short), share(long));
+        if len >= 16 && short_keys > KEY_LENGTH_SHARE {
+            suggestions.push(Suggestion::GroupShortKeys { share: short_keys });
+        } else if len >= 16 && long_keys > KEY_LENGTH_SHARE {
+            suggestions.push(Suggestion::ShortenLongKeys { share: long_keys });
+        }
+
+        TuningReport {
+            len,
+            allocation,
+            load_factor,
+            load_factor_threshold: threshold,
+            occupied_cells,
+            longest_chain,
+            mean_chain,
+            concentration,
+            recommended_capacity,
+            median_key_len,
+            short_keys,
+            long_keys,
+            suggestions,
+        }
+    }
+}
+
+impl Display for TuningReport {
+    /// Formats the report as a short, human-readable capacity planning summary.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "entries: {}, cells: {} ({} occupied), load factor: {:.2} (threshold {:.2})",
+            self.len,
+            self.allocation,
+            self.occupied_cells,
+            self.load_factor,
+            self.load_factor_threshold
+        )?;
+        writeln!(
+            f,
+            "longest chain: {}, mean chain: {:.2}, recommended capacity: {}",
+            self.longest_chain, self.mean_chain, self.recommended_capacity
+        )?;
+        writeln!(
+            f,
+            "median key: {} bytes, short keys: {:.0}%, long keys: {:.0}%",
+            self.median_key_len,
+            self.short_keys * 100.0,
+            self.long_keys * 100.0
+        )?;
+        if self.suggestions.is_empty() {
+            write!(f, "no tuning needed")
+        } else {
+            for (index, suggestion) in self.suggestions.iter().enumerate() {
+                if index > 0 {
+                    writeln!(f)?;
+                }
+                write!(f, "- {}", suggestion)?;
+            }
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,106 @@
+//! Operation step counters, enabled by the `ops-budget` feature.
+//!
+//! The counters measure the work behind the operations, such as chain entries compared
+//! per lookup or bytes copied per insert, rather than their latency. Unlike timings they
+//! are deterministic, so tests can assert exact or bounded budgets and catch an internal
+//! change that makes an operation do more work.
+use super::*;
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Step counts of a `ByteBox`, returned by [`ByteBox::op_counts`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OpCounts {
+    /// Lookups performed by [`ByteBox::get`] and the methods built on it.
+    pub lookups: u64,
+    /// Chain entries whose key was compared during those lookups.
+    pub probes: u64,
+    /// Key and value bytes copied into the table by inserts and updates.
+    pub bytes_copied: u64,
+    /// Entries moved to a new cell array by growth or rehashing.
+    pub entries_rehashed: u64,
+}
+
+impl OpCounts {
+    /// Returns the mean number of chain entries compared per lookup, or zero if nothing
+    /// was looked up.
+    pub fn probes_per_lookup(&self) -> f64 {
+        match self.lookups {
+            0 => 0.0,
+            lookups => self.probes as f64 / lookups as f64,
+        }
+    }
+}
+
+/// The counters embedded in a `ByteBox`. They are atomic so that `get`, which only
+/// borrows the table, can count without making `ByteBox` lose `Sync`.
+#[derive(Debug, Default)]
+pub(crate) struct OpCounters {
+    lookups: AtomicU64,
+    probes: AtomicU64,
+    bytes_copied: AtomicU64,
+    entries_rehashed: AtomicU64,
+}
+
+impl Clone for OpCounters {
+    fn clone(&self) -> Self {
+        let counts = self.counts();
+        OpCounters {
+            lookups: AtomicU64::new(counts.lookups),
+            probes: AtomicU64::new(counts.probes),
+            bytes_copied: AtomicU64::new(counts.bytes_copied),
+            entries_rehashed: AtomicU64::new(counts.entries_rehashed),
+        }
+    }
+}
+
+impl OpCounters {
+    pub(crate) fn record_lookup(&self, probes: u64) {
+        self.lookups.fetch_add(1, Ordering::Relaxed);
+        self.probes.fetch_add(probes, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_copy(&self, bytes: usize) {
+        self.bytes_copied.fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_rehash(&self, entries: usize) {
+        self.entries_rehashed
+            .fetch_add(entries as u64, Ordering::Relaxed);
+    }
+
+    fn counts(&self) -> OpCounts {
+        OpCounts {
+            lookups: self.lookups.load(Ordering::Relaxed),
+            probes: self.probes.load(Ordering::Relaxed),
+            bytes_copied: self.bytes_copied.load(Ordering::Relaxed),
+            entries_rehashed: self.entries_rehashed.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl ByteBox {
+    /// Returns the step counts of the operations performed so far.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut bytebox = ByteBox::new();
+    /// bytebox.insert(b"key", b"value");
+    /// bytebox.get(b"key");
+    ///
+    /// let counts = bytebox.op_counts();
+    /// assert_eq!(counts.bytes_copied, 8);
+    /// assert_eq!((counts.lookups, counts.probes), (1, 1));
+    /// ```
+    pub fn op_counts(&self) -> OpCounts {
+        self.op_counters.counts()
+    }
+
+    /// Resets every step counter to zero.
+    pub fn reset_op_counts(&mut self) {
+        self.op_counters = OpCounters::default();
+    }
+}
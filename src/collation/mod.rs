@@ -0,0 +1,122 @@
+//! Key orderings for sorted iteration.
+//!
+//! The comparators in this module are total orders: keys that collate equal (`"ID"` and
+//! `"id"`, or `"7"` and `"007"`) are tie-broken by their raw bytes, so sorting is
+//! deterministic and only identical keys compare equal.
+use super::*;
+
+use std::cmp::Ordering;
+
+/// Compares keys ignoring ASCII case.
+///
+/// # Examples
+///
+/// ```rust
+/// use bytesbox::collation::ascii_case_insensitive;
+/// use std::cmp::Ordering;
+///
+/// assert_eq!(ascii_case_insensitive(b"Accept", b"age"), Ordering::Less);
+/// assert_eq!(ascii_case_insensitive(b"ID", b"id"), Ordering::Less);
+/// ```
+pub fn ascii_case_insensitive(a: &[u8], b: &[u8]) -> Ordering {
+    a.iter()
+        .map(u8::to_ascii_lowercase)
+        .cmp(b.iter().map(u8::to_ascii_lowercase))
+        .then_with(|| a.cmp(b))
+}
+
+/// Compares keys so that runs of ASCII digits are ordered by their numeric value.
+///
+/// Everything outside digit runs compares byte by byte, so `"item2"` sorts before
+/// `"item10"`. Digit runs of any length are supported.
+///
+/// # Examples
+///
+/// ```rust
+/// use bytesbox::collation::natural;
+/// use std::cmp::Ordering;
+///
+/// assert_eq!(natural(b"item2", b"item10"), Ordering::Less);
+/// assert_eq!(natural(b"v1.10", b"v1.9"), Ordering::Greater);
+/// assert_eq!(natural(b"7", b"007"), Ordering::Greater);
+/// ```
+pub fn natural(a: &[u8], b: &[u8]) -> Ordering {
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if a[i].is_ascii_digit() && b[j].is_ascii_digit() {
+            let (run_a, next_i) = digit_run(a, i);
+            let (run_b, next_j) = digit_run(b, j);
+            // Without leading zeros, a longer run is a larger number.
+            let ordering = run_a.len().cmp(&run_b.len()).then_with(|| run_a.cmp(run_b));
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+            i = next_i;
+            j = next_j;
+        } else {
+            if a[i] != b[j] {
+                return a[i].cmp(&b[j]);
+            }
+            i += 1;
+            j += 1;
+        }
+    }
+    (a.len() - i).cmp(&(b.len() - j)).then_with(|| a.cmp(b))
+}
+
+/// Returns the digits of the run starting at `start` without leading zeros, and the index
+/// following the run.
+fn digit_run(bytes: &[u8], start: usize) -> (&[u8], usize) {
+    let end = bytes[start..]
+        .iter()
+        .position(|byte| !byte.is_ascii_digit())
+        .map_or(bytes.len(), |len| start + len);
+    let significant = bytes[start..end]
+        .iter()
+        .position(|&byte| byte != b'0')
+        .map_or(end, |zeros| start + zeros);
+    (&bytes[significant..end], end)
+}
+
+impl ByteBox {
+    /// Returns the entries ordered by key, byte by byte.
+    ///
+    /// This is [`ByteBox::iter_sorted_by`] with `<[u8]>::cmp`.
+    pub fn iter_sorted(&self) -> std::vec::IntoIter<(&[u8], &[u8])> {
+        self.iter_sorted_by(|a, b| a.cmp(b))
+    }
+
+    /// Returns the entries ordered by key according to `compare`.
+    ///
+    /// The entries are collected and sorted up front, which costs `O(n log n)` comparisons
+    /// and one pointer pair per entry. The iterator runs in both directions, so `rev()`
+    /// gives the descending order. The comparators of this module cover case-insensitive
+    /// and numeric-aware collations.
+    ///
+    /// # Arguments
+    ///
+    /// * `compare` - A total order over keys.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::collation::natural;
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut bytebox = ByteBox::new();
+    /// for key in [&b"node10"[..], b"node9", b"node1"] {
+    ///     bytebox.insert(key, b"up");
+    /// }
+    ///
+    /// let keys: Vec<&[u8]> = bytebox.iter_sorted_by(natural).map(|(key, _)| key).collect();
+    /// assert_eq!(keys, vec![&b"node1"[..], b"node9", b"node10"]);
+    /// ```
+    pub fn iter_sorted_by<F>(&self, mut compare: F) -> std::vec::IntoIter<(&[u8], &[u8])>
+    where
+        F: FnMut(&[u8], &[u8]) -> Ordering,
+    {
+        let mut entries: Vec<(&[u8], &[u8])> = self.iter().collect();
+        entries.sort_unstable_by(|a, b| compare(a.0, b.0));
+        entries.into_iter()
+    }
+}
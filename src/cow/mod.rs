@@ -0,0 +1,75 @@
+//! Copy-on-write clones of a table that share their entries.
+use super::*;
+
+impl ByteBox {
+    /// Returns a copy of the table that shares its entries with `self` until either side
+    /// changes them.
+    ///
+    /// Only the cell array is copied, one pointer per cell; the entries, with their keys
+    /// and values, are reference-counted and shared. A write through either table copies
+    /// the entries it changes on the way to them, usually a single entry, so a snapshot
+    /// of a large table costs memory in proportion to what changes while it is alive
+    /// rather than to the size of the table. A resize, `defragment` or `retain` relinks
+    /// or rebuilds every entry, so it copies all the entries still shared.
+    ///
+    /// The copy behaves exactly like one made by [`Clone::clone`], which copies every
+    /// entry up front. [`Stats::shared_bytes`] reports how much is still shared.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut live = ByteBox::new();
+    /// live.insert(b"key1", b"value1");
+    /// live.insert(b"key2", b"value2");
+    ///
+    /// let snapshot = live.clone_cheap();
+    /// assert_eq!(live.stats().shared_bytes, 20);
+    ///
+    /// live.insert(b"key1", b"changed");
+    /// assert_eq!(snapshot.get(b"key1"), Some(&b"value1"[..]));
+    /// assert_eq!(live.get(b"key1"), Some(&b"changed"[..]));
+    /// ```
+    pub fn clone_cheap(&self) -> ByteBox {
+        ByteBox {
+            cells: self.cells.clone(),
+            alloc: self.alloc,
+            len: self.len,
+            load_factor_threshold: self.load_factor_threshold,
+            generation: self.generation,
+            seed: self.seed,
+            checksum: self.checksum,
+            content_bytes: self.content_bytes,
+            pool: self.pool.clone(),
+            tombstones: self.tombstones.clone(),
+            decoder: self.decoder.clone(),
+            miss_handler: self.miss_handler.clone(),
+            migration_cursor: self.migration_cursor,
+            shrink: self.shrink,
+            watermarks: self.watermarks.clone(),
+            #[cfg(feature = "profiling")]
+            profiler: self.profiler.clone(),
+            #[cfg(feature = "ops-budget")]
+            op_counters: self.op_counters.clone(),
+        }
+    }
+
+    /// Sums the key and value bytes of the entries another table also refers to.
+    pub(crate) fn shared_bytes(&self) -> usize {
+        let mut shared_bytes = 0;
+        for cell in &self.cells {
+            // Entries after a shared one are reached through it, so they are shared too.
+            let mut shared = false;
+            let mut current = cell.as_ref();
+            while let Some(node) = current {
+                shared |= Arc::strong_count(node) > 1;
+                if shared {
+                    shared_bytes += node.key.len() + node.value.len();
+                }
+                current = node.next.as_ref();
+            }
+        }
+        shared_bytes
+    }
+}
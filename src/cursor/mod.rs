@@ -0,0 +1,157 @@
+//! Safe, in-place editing of a collision chain.
+use super::*;
+
+/// A position inside a collision chain: the link that points at the current entry.
+///
+/// Holding the link rather than the entry is what lets the cursor unlink the current
+/// entry without a pointer to the previous one, so chain edits need no `unsafe`.
+pub(crate) struct ChainCursor<'a, V = Vec<u8>> {
+    // Always `Some` between calls; the `Option` lets `move_next` move the borrow forward.
+    link: Option<&'a mut Link<V>>,
+    /// Gives mutable access to an entry: `Arc::make_mut` for tables that share entries,
+    /// [`Entry::unique`] for the others.
+    unshare: fn(&mut Arc<Entry<V>>) -> &mut Entry<V>,
+}
+
+impl<'a, V: Clone> ChainCursor<'a, V> {
+    /// Places a cursor on the first entry of the chain starting at `head`, copying
+    /// shared entries before they are changed.
+    pub(crate) fn new(head: &'a mut Link<V>) -> Self {
+        ChainCursor {
+            link: Some(head),
+            unshare: Arc::make_mut,
+        }
+    }
+}
+
+impl<'a, V> ChainCursor<'a, V> {
+    /// Places a cursor on the first entry of a chain whose entries are never shared.
+    pub(crate) fn unique(head: &'a mut Link<V>) -> Self {
+        ChainCursor {
+            link: Some(head),
+            unshare: Entry::unique,
+        }
+    }
+
+    /// Returns the current entry, or `None` past the end of the chain.
+    pub(crate) fn current(&self) -> Option<&Entry<V>> {
+        self.link.as_ref()?.as_deref()
+    }
+
+    /// Returns the current entry mutably, or `None` past the end of the chain.
+    pub(crate) fn current_mut(&mut self) -> Option<&mut Entry<V>> {
+        let unshare = self.unshare;
+        self.link.as_mut()?.as_mut().map(unshare)
+    }
+
+    /// Moves to the next entry, returning `false` if the cursor was already past the end.
+    pub(crate) fn move_next(&mut self) -> bool {
+        let link = match self.link.take() {
+            Some(link) => link,
+            None => return false,
+        };
+        if link.is_some() {
+            let unshare = self.unshare;
+            self.link = link.as_mut().map(|entry| &mut unshare(entry).next);
+            true
+        } else {
+            self.link = Some(link);
+            false
+        }
+    }
+
+    /// Unlinks the current entry and returns it; the cursor then points at its successor.
+    pub(crate) fn remove_current(&mut self) -> Option<Arc<Entry<V>>> {
+        let link = self.link.as_mut()?;
+        let mut removed = link.take()?;
+        **link = Entry::take_next(&mut removed);
+        Some(removed)
+    }
+
+    /// Advances to the first entry for which `pred` holds, without moving past it.
+    pub(crate) fn seek<P>(&mut self, mut pred: P) -> Option<&mut Entry<V>>
+    where
+        P: FnMut(&Entry<V>) -> bool,
+    {
+        while let Some(entry) = self.current() {
+            if pred(entry) {
+                return self.current_mut();
+            }
+            self.move_next();
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chain(keys: &[u8]) -> Link {
+        keys.iter().rev().fold(None, |next, &key| {
+            Some(Arc::new(Entry {
+                key: vec![key],
+                value: vec![key],
+                next,
+            }))
+        })
+    }
+
+    fn keys(mut link: &Link) -> Vec<u8> {
+        let mut keys = Vec::new();
+        while let Some(entry) = link {
+            keys.push(entry.key[0]);
+            link = &entry.next;
+        }
+        keys
+    }
+
+    #[test]
+    fn remove_head_middle_and_tail() {
+        let mut head = chain(&[1, 2, 3, 4]);
+        let mut cursor = ChainCursor::new(&mut head);
+        assert_eq!(cursor.remove_current().unwrap().key, vec![1]);
+        assert_eq!(cursor.current().unwrap().key, vec![2]);
+        assert!(cursor.move_next());
+        assert_eq!(cursor.remove_current().unwrap().key, vec![3]);
+        assert_eq!(cursor.remove_current().unwrap().key, vec![4]);
+        assert!(cursor.remove_current().is_none());
+        assert!(!cursor.move_next());
+        assert_eq!(keys(&head), vec![2]);
+    }
+
+    #[test]
+    fn seek_and_edit() {
+        let mut head = chain(&[1, 2, 3]);
+        let mut cursor = ChainCursor::new(&mut head);
+        cursor.seek(|entry| entry.key == [2]).unwrap().value = vec![9];
+        assert!(cursor.seek(|entry| entry.key == [1]).is_none());
+        assert!(cursor.current().is_none());
+        assert_eq!(head.as_ref().unwrap().next.as_ref().unwrap().value, vec![9]);
+
+        let mut empty: Link = None;
+        let mut cursor = ChainCursor::new(&mut empty);
+        assert!(cursor.current_mut().is_none());
+        assert!(cursor.remove_current().is_none());
+        assert!(!cursor.move_next());
+    }
+
+    #[test]
+    fn edits_copy_shared_entries() {
+        let mut head = chain(&[1, 2, 3]);
+        let shared = head.clone();
+        let mut cursor = ChainCursor::new(&mut head);
+        cursor.seek(|entry| entry.key == [2]).unwrap().value = vec![9];
+        assert_eq!(cursor.remove_current().unwrap().key, vec![2]);
+
+        assert_eq!(keys(&head), vec![1, 3]);
+        assert_eq!(keys(&shared), vec![1, 2, 3]);
+        let second = shared.as_ref().unwrap().next.as_ref().unwrap();
+        assert_eq!(second.value, vec![2]);
+        // The tail after the edit is still shared.
+        assert!(Arc::ptr_eq(
+            head.as_ref().unwrap().next.as_ref().unwrap(),
+            second.next.as_ref().unwrap()
+        ));
+    }
+}
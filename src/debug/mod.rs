@@ -0,0 +1,96 @@
+//! Bounded `Debug` output and configurable rendering of tables.
+use super::*;
+
+/// Number of entries shown by the `Debug` implementation of `ByteBox`.
+pub const DEBUG_SAMPLE: usize = 8;
+
+/// Layout of the visualization produced by
+/// [`ByteBox::render_table_with`](crate::ByteBox::render_table_with).
+///
+/// The default lists every cell in index order, like
+/// [`ByteBox::render_table`](crate::ByteBox::render_table).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RenderOptions {
+    /// List cells by decreasing number of entries instead of by index.
+    pub densest_first: bool,
+    /// Replace the empty cells with a single line counting them.
+    pub collapse_empty: bool,
+}
+
+/// Formats keys and values the same way as the `Display` implementation.
+struct Lossy<'a>(&'a [u8]);
+
+impl fmt::Debug for Lossy<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", String::from_utf8_lossy(self.0))
+    }
+}
+
+/// Entries of a table, limited to the first `limit`.
+struct Entries<'a> {
+    byte_box: &'a ByteBox,
+    limit: usize,
+}
+
+impl fmt::Debug for Entries<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(
+                self.byte_box
+                    .iter()
+                    .take(self.limit)
+                    .map(|(key, value)| (Lossy(key), Lossy(value))),
+            )
+            .finish()
+    }
+}
+
+impl fmt::Debug for ByteBox {
+    /// Formats a bounded summary of the table: its size, load and the first
+    /// [`DEBUG_SAMPLE`] entries. Use [`ByteBox::debug_full`] to dump every entry.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut summary = f.debug_struct("ByteBox");
+        summary
+            .field("len", &self.len)
+            .field("alloc", &self.alloc)
+            .field("load_factor", &(self.len as f32 / self.alloc.max(1) as f32))
+            .field(
+                "sample",
+                &Entries {
+                    byte_box: self,
+                    limit: DEBUG_SAMPLE,
+                },
+            );
+        if self.len > DEBUG_SAMPLE {
+            summary.finish_non_exhaustive()
+        } else {
+            summary.finish()
+        }
+    }
+}
+
+/// A `Debug` view of a `ByteBox` that lists every entry.
+///
+/// This struct is created by the [`ByteBox::debug_full`] method.
+pub struct DebugFull<'a> {
+    pub(crate) byte_box: &'a ByteBox,
+}
+
+impl fmt::Debug for DebugFull<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let byte_box = self.byte_box;
+        f.debug_struct("ByteBox")
+            .field("len", &byte_box.len)
+            .field("alloc", &byte_box.alloc)
+            .field("load_factor_threshold", &byte_box.load_factor_threshold)
+            .field("generation", &byte_box.generation)
+            .field(
+                "entries",
+                &Entries {
+                    byte_box,
+                    limit: usize::MAX,
+                },
+            )
+            .finish()
+    }
+}
@@ -0,0 +1,87 @@
+//! Read-side decoding of stored values.
+use super::*;
+
+use std::error::Error;
+use std::sync::Arc;
+
+/// A read decoder: turns a stored value into the bytes callers see, writing them to the
+/// output buffer, which is empty when the decoder is called.
+pub type ReadDecoder =
+    Arc<dyn Fn(&[u8], &mut Vec<u8>) -> Result<(), Box<dyn Error + Send + Sync>> + Send + Sync>;
+
+impl ByteBox {
+    /// Registers the decoder applied by [`ByteBox::get_decoded`], replacing any previous one.
+    ///
+    /// Stored values are left as they are; only reads through `get_decoded` are decoded.
+    /// This lets a table loaded from legacy snapshots, whose values still carry an old
+    /// envelope, be consumed as if it held the plain values. Clones of the table share the
+    /// decoder.
+    ///
+    /// # Arguments
+    ///
+    /// * `decoder` - Receives a stored value and the output buffer, and reports values it
+    ///   cannot decode as an error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut legacy = ByteBox::new();
+    /// legacy.insert(b"user:1", b"v1:ada");
+    ///
+    /// legacy.set_read_decoder(|stored, out| {
+    ///     let plain = stored.strip_prefix(b"v1:").ok_or("missing v1 envelope")?;
+    ///     out.extend_from_slice(plain);
+    ///     Ok(())
+    /// });
+    ///
+    /// let mut buf = Vec::new();
+    /// assert_eq!(legacy.get_decoded(b"user:1", &mut buf).unwrap(), Some(3));
+    /// assert_eq!(buf, b"ada");
+    /// assert_eq!(legacy.get(b"user:1"), Some(&b"v1:ada"[..]));
+    /// ```
+    pub fn set_read_decoder<F>(&mut self, decoder: F)
+    where
+        F: Fn(&[u8], &mut Vec<u8>) -> Result<(), Box<dyn Error + Send + Sync>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.decoder = Some(Arc::new(decoder));
+    }
+
+    /// Removes the read decoder; [`ByteBox::get_decoded`] then copies values unchanged.
+    pub fn clear_read_decoder(&mut self) {
+        self.decoder = None;
+    }
+
+    /// Replaces the contents of `buf` with the decoded value of `key`.
+    ///
+    /// Without a registered decoder this behaves like [`ByteBox::get_to_vec`].
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(usize))` containing the length of the decoded value now held by `buf`.
+    /// * `Ok(None)` if the key does not exist; `buf` is left untouched.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`DecodeError`] if the decoder rejects the value; `buf` then holds
+    /// whatever the decoder wrote before failing.
+    pub fn get_decoded(&self, key: &[u8], buf: &mut Vec<u8>) -> Result<Option<usize>, DecodeError> {
+        let value = match self.get(key) {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+        buf.clear();
+        match &self.decoder {
+            Some(decoder) => decoder(value, buf).map_err(|source| DecodeError {
+                key: key.to_vec(),
+                source,
+            })?,
+            None => buf.extend_from_slice(value),
+        }
+        Ok(Some(buf.len()))
+    }
+}
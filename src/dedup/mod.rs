@@ -0,0 +1,218 @@
+//! Content-addressable storage sharing identical values between keys.
+use super::*;
+
+use crate::map::ByteMap;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// A content-addressable variant of `ByteBox` where identical values are stored only once.
+///
+/// Every distinct value lives in a single shared buffer, and a key's entry holds a
+/// pointer to it inline, with no allocation of its own. A table mapping millions of keys
+/// to a few distinct values, such as file names to MIME types, therefore pays for its
+/// keys, one pointer per key and every distinct value once. Lookups stay transparent:
+/// [`DedupByteBox::get`] returns the shared bytes exactly like [`ByteBox::get`] does.
+///
+/// # Examples
+///
+/// ```rust
+/// use bytesbox::dedup::DedupByteBox;
+///
+/// let mut table = DedupByteBox::new();
+/// table.insert(b"index.html", b"text/html");
+/// table.insert(b"about.html", b"text/html");
+/// table.insert(b"logo.png", b"image/png");
+///
+/// assert_eq!(table.get(b"about.html"), Some(&b"text/html"[..]));
+/// assert_eq!(table.len(), 3);
+/// assert_eq!(table.distinct_values(), 2);
+/// ```
+#[derive(Debug, Default)]
+pub struct DedupByteBox {
+    keys: ByteMap<Arc<[u8]>>,
+    /// Every distinct value. Each holds one reference besides those of its keys, so a
+    /// value's strong count is one more than the number of keys sharing it.
+    values: HashSet<Arc<[u8]>>,
+}
+
+impl Clone for DedupByteBox {
+    /// Copies the table into fresh shared buffers; sharing them with `self` would break
+    /// the reference counts both tables rely on.
+    fn clone(&self) -> Self {
+        let mut copy = Self::new();
+        for (key, value) in self.keys.iter() {
+            copy.insert(key, value);
+        }
+        copy
+    }
+}
+
+impl DedupByteBox {
+    /// Creates a new, empty `DedupByteBox`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of keys stored in the `DedupByteBox`.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Returns `true` if the `DedupByteBox` contains no keys.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Returns the number of distinct values currently stored.
+    pub fn distinct_values(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns how many keys currently share the given value.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::dedup::DedupByteBox;
+    ///
+    /// let mut table = DedupByteBox::new();
+    /// table.insert(b"a", b"same");
+    /// table.insert(b"b", b"same");
+    /// assert_eq!(table.ref_count(b"same"), 2);
+    /// assert_eq!(table.ref_count(b"other"), 0);
+    /// ```
+    pub fn ref_count(&self, value: &[u8]) -> usize {
+        self.values
+            .get(value)
+            .map_or(0, |shared| Arc::strong_count(shared) - 1)
+    }
+
+    /// Inserts a key-value pair, sharing the value with other keys when it is already stored.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - A byte slice representing the key.
+    /// * `value` - A byte slice representing the value.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if a new key was inserted.
+    /// * `false` if an existing key was updated.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::dedup::DedupByteBox;
+    ///
+    /// let mut table = DedupByteBox::new();
+    /// assert!(table.insert(b"key", b"value1"));
+    /// assert!(!table.insert(b"key", b"value2"));
+    /// assert_eq!(table.get(b"key"), Some(&b"value2"[..]));
+    /// assert_eq!(table.distinct_values(), 1);
+    /// ```
+    pub fn insert(&mut self, key: &[u8], value: &[u8]) -> bool {
+        let shared = match self.values.get(value) {
+            Some(shared) => Arc::clone(shared),
+            None => {
+                let shared: Arc<[u8]> = Arc::from(value);
+                self.values.insert(Arc::clone(&shared));
+                shared
+            }
+        };
+        match self.keys.insert(key, shared) {
+            Some(old) => {
+                self.release(old);
+                false
+            }
+            None => true,
+        }
+    }
+
+    /// Retrieves the shared value associated with the given key.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::dedup::DedupByteBox;
+    ///
+    /// let mut table = DedupByteBox::new();
+    /// table.insert(b"key", b"value");
+    /// assert_eq!(table.get(b"key"), Some(&b"value"[..]));
+    /// assert_eq!(table.get(b"nonexistent"), None);
+    /// ```
+    pub fn get(&self, key: &[u8]) -> Option<&[u8]> {
+        self.keys.get(key).map(|shared| &shared[..])
+    }
+
+    /// Removes a key, releasing its value once no other key refers to it.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Vec<u8>)` containing a copy of the value if the key existed.
+    /// * `None` if the key was not found.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::dedup::DedupByteBox;
+    ///
+    /// let mut table = DedupByteBox::new();
+    /// table.insert(b"key", b"value");
+    /// assert_eq!(table.remove(b"key"), Some(b"value".to_vec()));
+    /// assert_eq!(table.distinct_values(), 0);
+    /// ```
+    pub fn remove(&mut self, key: &[u8]) -> Option<Vec<u8>> {
+        let shared = self.keys.remove(key)?;
+        let value = shared.to_vec();
+        self.release(shared);
+        Some(value)
+    }
+
+    /// Returns memory and occupancy figures of the key table.
+    ///
+    /// `value_bytes` counts every key's value as seen by callers, while
+    /// `stored_value_bytes` counts each distinct value once.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::dedup::DedupByteBox;
+    ///
+    /// let mut table = DedupByteBox::new();
+    /// table.insert(b"index.html", b"text/html");
+    /// table.insert(b"about.html", b"text/html");
+    ///
+    /// let stats = table.stats();
+    /// assert_eq!(stats.value_bytes, 18);
+    /// assert_eq!(stats.stored_value_bytes, 9);
+    /// assert_eq!(stats.value_savings(), 9);
+    /// ```
+    pub fn stats(&self) -> Stats {
+        let mut stats = Stats {
+            len: self.keys.len(),
+            allocation: self.keys.allocation(),
+            ..Stats::default()
+        };
+        for depth in self.keys.chain_lengths() {
+            if depth > 0 {
+                stats.occupied_cells += 1;
+            }
+            stats.longest_chain = stats.longest_chain.max(depth);
+        }
+        for (key, value) in self.keys.iter() {
+            stats.key_bytes += key.len();
+            stats.value_bytes += value.len();
+        }
+        stats.stored_key_bytes = stats.key_bytes;
+        stats.stored_value_bytes = self.values.iter().map(|shared| shared.len()).sum();
+        stats
+    }
+
+    /// Drops a key's reference to `shared`, forgetting the value when it was the last one.
+    fn release(&mut self, shared: Arc<[u8]>) {
+        // The key's reference and the one held by `values`.
+        if Arc::strong_count(&shared) == 2 {
+            self.values.remove(&shared[..]);
+        }
+    }
+}
@@ -0,0 +1,107 @@
+//! Rebuilding a table's storage after heavy churn.
+use super::*;
+
+use std::mem::size_of;
+
+/// What a call to [`ByteBox::defragment`] did.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DefragmentReport {
+    /// Heap bytes released: spare key and value capacity, pooled entries and unused cells.
+    ///
+    /// This is the size of the allocations given back to the allocator, which may keep
+    /// some of them for reuse instead of returning them to the operating system.
+    pub reclaimed_bytes: usize,
+    /// The new allocation if the cell array was shrunk.
+    pub shrunk_to: Option<usize>,
+}
+
+impl ByteBox {
+    /// Rebuilds the table's storage so that it holds nothing beyond its current entries.
+    ///
+    /// Every entry is copied into a fresh allocation of exactly its size, in cell order,
+    /// and the old allocations are released. This drops the spare capacity left behind
+    /// when values shrink, and lets a long-running process hand back memory scattered by
+    /// churn. The pool of recycled entries is emptied, and the cell array is shrunk to the
+    /// smallest allocation keeping the load below its threshold (at least 16 cells); it is
+    /// never grown. The contents are unchanged and entries sharing a cell keep their
+    /// relative order, so the iteration order only changes when the cell array shrinks.
+    ///
+    /// Defragmenting touches every entry and allocates once per entry, so it belongs in a
+    /// quiet period rather than on a request path; [`ByteBox::maintain`] offers a bounded
+    /// shrink for periodic ticks. Tombstones of soft removals are kept; they are dropped
+    /// by [`ByteBox::compact`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut bytebox = ByteBox::new();
+    /// for i in 0..1_000u32 {
+    ///     bytebox.insert(&i.to_le_bytes(), &[0u8; 256]);
+    /// }
+    /// for i in 0..1_000u32 {
+    ///     if i % 10 == 0 {
+    ///         bytebox.insert(&i.to_le_bytes(), b"small");
+    ///     } else {
+    ///         bytebox.remove(&i.to_le_bytes());
+    ///     }
+    /// }
+    ///
+    /// let report = bytebox.defragment();
+    /// assert_eq!(report.shrunk_to, Some(256));
+    /// assert!(report.reclaimed_bytes >= 100 * 251);
+    /// assert_eq!(bytebox.get(&10u32.to_le_bytes()), Some(&b"small"[..]));
+    /// ```
+    pub fn defragment(&mut self) -> DefragmentReport {
+        let mut report = DefragmentReport::default();
+
+        let pool = self.pool.stats();
+        report.reclaimed_bytes += pool.pooled_bytes + pool.pooled * size_of::<Entry>();
+        self.pool.trim();
+
+        if self.alloc == 0 {
+            return report;
+        }
+        let new_cap = self.fitted_allocation().min(self.alloc);
+        if new_cap < self.alloc {
+            report.shrunk_to = Some(new_cap);
+            report.reclaimed_bytes += (self.alloc - new_cap) * size_of::<Link>();
+        }
+
+        let mut new_cells: Vec<Link> = vec![None; new_cap];
+        for cell in std::mem::take(&mut self.cells) {
+            let mut current = cell;
+            while let Some(mut entry) = current {
+                current = Entry::take_next(&mut entry);
+                report.reclaimed_bytes += entry.key.capacity() - entry.key.len();
+                report.reclaimed_bytes += entry.value.capacity() - entry.value.len();
+                let idx = Self::seeded_hash(&entry.key, self.seed, new_cap);
+                new_cells[idx] = Some(Arc::new(Entry {
+                    key: entry.key.as_slice().to_vec(),
+                    value: entry.value.as_slice().to_vec(),
+                    next: new_cells[idx].take(),
+                }));
+            }
+        }
+        // Entries were prepended; reversing every chain restores the order they were
+        // visited in, so entries sharing a cell keep their relative order.
+        for cell in new_cells.iter_mut() {
+            let mut reversed = cell.take();
+            while let Some(mut entry) = reversed {
+                let relinked = Entry::unique(&mut entry);
+                reversed = relinked.next.take();
+                relinked.next = cell.take();
+                *cell = Some(entry);
+            }
+        }
+
+        self.cells = new_cells;
+        self.alloc = new_cap;
+        self.shrink = None;
+        self.migration_cursor = 0;
+        self.generation = self.generation.wrapping_add(1);
+        self.debug_check_invariants();
+        report
+    }
+}
@@ -0,0 +1,278 @@
+//! Loading and writing tables as delimited `key<delimiter>value` text, such as TSV or CSV.
+use super::*;
+
+use std::io::{self, BufRead, Write};
+
+/// The layout of a delimited file.
+///
+/// Fields are raw bytes: no encoding is assumed. Records end with `\n`, optionally
+/// preceded by `\r`, and blank lines are skipped.
+///
+/// Without a quote byte, the key ends at the first delimiter and the value is the rest of
+/// the line, so values may contain the delimiter. With a quote byte, a field starting with
+/// it runs to the matching closing quote and may contain the delimiter, line breaks and
+/// doubled quotes standing for one quote, as in CSV.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DelimitedFormat {
+    /// Byte separating the key from the value.
+    pub delimiter: u8,
+    /// Byte enclosing fields that need quoting, if quoting is enabled.
+    pub quote: Option<u8>,
+}
+
+impl DelimitedFormat {
+    /// Tab-separated, unquoted.
+    pub const TSV: DelimitedFormat = DelimitedFormat {
+        delimiter: b'\t',
+        quote: None,
+    };
+    /// Comma-separated, with `"` quoting.
+    pub const CSV: DelimitedFormat = DelimitedFormat {
+        delimiter: b',',
+        quote: Some(b'"'),
+    };
+}
+
+impl From<u8> for DelimitedFormat {
+    /// An unquoted format using `delimiter`.
+    fn from(delimiter: u8) -> Self {
+        DelimitedFormat {
+            delimiter,
+            quote: None,
+        }
+    }
+}
+
+fn malformed(line: usize, message: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("line {}: {}", line, message),
+    )
+}
+
+/// Outcome of parsing one quoted field.
+enum Quoted {
+    /// The field and the position right after its closing quote.
+    Field(Vec<u8>, usize),
+    /// The closing quote is not in the buffer yet.
+    Incomplete,
+}
+
+fn parse_quoted(record: &[u8], start: usize, quote: u8) -> Quoted {
+    let mut field = Vec::new();
+    let mut pos = start + 1;
+    while pos < record.len() {
+        if record[pos] == quote {
+            if record.get(pos + 1) == Some(&quote) {
+                field.push(quote);
+                pos += 2;
+                continue;
+            }
+            return Quoted::Field(field, pos + 1);
+        }
+        field.push(record[pos]);
+        pos += 1;
+    }
+    Quoted::Incomplete
+}
+
+/// Splits a complete record into key and value, or returns `Ok(None)` if a quoted field
+/// continues on the next line.
+fn parse_record(
+    record: &[u8],
+    format: DelimitedFormat,
+    line: usize,
+) -> io::Result<Option<(Vec<u8>, Vec<u8>)>> {
+    let quote = match format.quote {
+        Some(quote) => quote,
+        None => {
+            let split = record
+                .iter()
+                .position(|&byte| byte == format.delimiter)
+                .ok_or_else(|| malformed(line, "missing delimiter"))?;
+            return Ok(Some((
+                record[..split].to_vec(),
+                record[split + 1..].to_vec(),
+            )));
+        }
+    };
+
+    let (key, after_key) = if record.first() == Some(&quote) {
+        match parse_quoted(record, 0, quote) {
+            Quoted::Field(key, end) => (key, end),
+            Quoted::Incomplete => return Ok(None),
+        }
+    } else {
+        let end = record
+            .iter()
+            .position(|&byte| byte == format.delimiter)
+            .ok_or_else(|| malformed(line, "missing delimiter"))?;
+        (record[..end].to_vec(), end)
+    };
+    if record.get(after_key) != Some(&format.delimiter) {
+        return Err(malformed(line, "expected delimiter after key"));
+    }
+
+    let start = after_key + 1;
+    if record.get(start) != Some(&quote) {
+        return Ok(Some((key, record[start..].to_vec())));
+    }
+    match parse_quoted(record, start, quote) {
+        Quoted::Field(value, end) if end == record.len() => Ok(Some((key, value))),
+        Quoted::Field(..) => Err(malformed(line, "unexpected bytes after closing quote")),
+        Quoted::Incomplete => Ok(None),
+    }
+}
+
+fn write_field<W: Write>(writer: &mut W, field: &[u8], format: DelimitedFormat) -> io::Result<()> {
+    let special = |byte: &u8| *byte == format.delimiter || *byte == b'\n' || *byte == b'\r';
+    match format.quote {
+        Some(quote) if field.iter().any(|byte| special(byte) || *byte == quote) => {
+            writer.write_all(&[quote])?;
+            for chunk in field.split_inclusive(|&byte| byte == quote) {
+                writer.write_all(chunk)?;
+                if chunk.last() == Some(&quote) {
+                    writer.write_all(&[quote])?;
+                }
+            }
+            writer.write_all(&[quote])
+        }
+        _ => writer.write_all(field),
+    }
+}
+
+impl ByteBox {
+    /// Builds a table from `key<delimiter>value` records.
+    ///
+    /// When a key appears more than once, the last record wins.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - The delimited data; wrap files in a `BufReader`.
+    /// * `format` - A [`DelimitedFormat`], or just the delimiter byte for unquoted data.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidData`, naming the line, for a record without delimiter or with a
+    /// malformed quoted field, `UnexpectedEof` for an unterminated quoted field, or the
+    /// underlying I/O error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::delimited::DelimitedFormat;
+    /// use bytesbox::ByteBox;
+    ///
+    /// let tsv = ByteBox::from_delimited(&b"id\t42\nname\tada\tlovelace\n"[..], b'\t').unwrap();
+    /// assert_eq!(tsv.get(b"name"), Some(&b"ada\tlovelace"[..]));
+    ///
+    /// let csv = &b"\"a,b\",\"say \"\"hi\"\"\"\r\nplain,value\r\n"[..];
+    /// let table = ByteBox::from_delimited(csv, DelimitedFormat::CSV).unwrap();
+    /// assert_eq!(table.get(b"a,b"), Some(&b"say \"hi\""[..]));
+    /// assert_eq!(table.get(b"plain"), Some(&b"value"[..]));
+    /// ```
+    pub fn from_delimited<R, F>(mut reader: R, format: F) -> io::Result<ByteBox>
+    where
+        R: BufRead,
+        F: Into<DelimitedFormat>,
+    {
+        let format = format.into();
+        let mut byte_box = ByteBox::new();
+        let mut record = Vec::new();
+        let mut line = 0;
+        let mut record_line = 1;
+
+        loop {
+            let start = record.len();
+            let read = reader.read_until(b'\n', &mut record)?;
+            line += 1;
+            if read == 0 {
+                if record.is_empty() {
+                    return Ok(byte_box);
+                }
+                return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    format!("line {}: unterminated quoted field", record_line),
+                ));
+            }
+            if start == 0 {
+                record_line = line;
+            }
+
+            // Strip the line break, but keep it inside a record that spans lines.
+            let mut end = record.len();
+            if record[end - 1] == b'\n' {
+                end -= 1;
+                if end > 0 && record[end - 1] == b'\r' {
+                    end -= 1;
+                }
+            }
+            if end == 0 {
+                record.clear();
+                continue;
+            }
+            match parse_record(&record[..end], format, record_line)? {
+                Some((key, value)) => {
+                    byte_box.insert(&key, &value);
+                    record.clear();
+                }
+                None if record.last() != Some(&b'\n') => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        format!("line {}: unterminated quoted field", record_line),
+                    ));
+                }
+                None => {}
+            }
+        }
+    }
+
+    /// Writes every entry as a `key<delimiter>value` record, in iteration order.
+    ///
+    /// With a quote byte, fields containing the delimiter, the quote or a line break are
+    /// quoted, so [`ByteBox::from_delimited`] reads the output back unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidInput` if the format is unquoted and a key contains the delimiter
+    /// or a key or value contains a line break, since the record could not be read back.
+    /// Otherwise returns the underlying I/O error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::delimited::DelimitedFormat;
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut bytebox = ByteBox::new();
+    /// bytebox.insert(b"greeting", b"hello, world");
+    ///
+    /// let mut csv = Vec::new();
+    /// bytebox.write_delimited(&mut csv, DelimitedFormat::CSV).unwrap();
+    /// assert_eq!(csv, b"greeting,\"hello, world\"\n");
+    /// ```
+    pub fn write_delimited<W, F>(&self, mut writer: W, format: F) -> io::Result<()>
+    where
+        W: Write,
+        F: Into<DelimitedFormat>,
+    {
+        let format = format.into();
+        for (key, value) in self.iter() {
+            if format.quote.is_none() {
+                let breaks =
+                    |field: &[u8]| field.iter().any(|&byte| byte == b'\n' || byte == b'\r');
+                if key.contains(&format.delimiter) || breaks(key) || breaks(value) {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "entry cannot be written without quoting",
+                    ));
+                }
+            }
+            write_field(&mut writer, key, format)?;
+            writer.write_all(&[format.delimiter])?;
+            write_field(&mut writer, value, format)?;
+            writer.write_all(b"\n")?;
+        }
+        writer.flush()
+    }
+}
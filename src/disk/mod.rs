@@ -0,0 +1,222 @@
+//! A sorted, block-indexed on-disk table format and a reader that looks keys up without
+//! loading the whole file.
+use super::*;
+
+use crate::snapshot::{read_chunk, read_u64, write_chunk};
+use std::fs::File;
+use std::io::{self, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+/// Bytes a disk table starts and ends with; the trailing digits are the format version.
+pub const DISK_MAGIC: &[u8; 8] = b"BBDISK01";
+
+/// Size, in bytes, after which [`ByteBox::write_disk_table`] starts a new data block.
+pub const DISK_BLOCK_SIZE: usize = 4096;
+
+/// Length of the fixed footer: index offset, block count, entry count and magic.
+const FOOTER_LEN: u64 = 32;
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+/// One index record: the first key of a data block and where the block lives.
+#[derive(Debug, Clone)]
+struct BlockHandle {
+    first_key: Vec<u8>,
+    offset: u64,
+    len: u64,
+}
+
+impl ByteBox {
+    /// Writes the table sorted by key in the block-indexed format read by [`DiskByteBox`].
+    ///
+    /// The file is [`DISK_MAGIC`], then data blocks of about [`DISK_BLOCK_SIZE`] bytes
+    /// holding length-prefixed (`u64` LE) keys and values in key order, then an index with
+    /// the first key, offset and length of every block, then a footer with the index
+    /// offset, the block count, the entry count and [`DISK_MAGIC`] again.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - Where the table is written; wrap files in a `BufWriter`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::disk::DiskByteBox;
+    /// use bytesbox::ByteBox;
+    /// use std::io::Cursor;
+    ///
+    /// let mut bytebox = ByteBox::new();
+    /// bytebox.insert(b"user:1", b"ada");
+    /// bytebox.insert(b"user:2", b"grace");
+    ///
+    /// let mut file = Vec::new();
+    /// bytebox.write_disk_table(&mut file).unwrap();
+    ///
+    /// let mut disk = DiskByteBox::from_reader(Cursor::new(file)).unwrap();
+    /// assert_eq!(disk.len(), 2);
+    /// assert_eq!(disk.get(b"user:2").unwrap(), Some(b"grace".to_vec()));
+    /// assert_eq!(disk.get(b"user:3").unwrap(), None);
+    /// ```
+    pub fn write_disk_table<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let mut entries: Vec<(&[u8], &[u8])> = self.iter().collect();
+        entries.sort_unstable_by_key(|&(key, _)| key);
+
+        writer.write_all(DISK_MAGIC)?;
+        let mut offset = DISK_MAGIC.len() as u64;
+        let mut index = Vec::new();
+        let mut block = Vec::new();
+        let mut first_key: &[u8] = &[];
+        for (key, value) in entries {
+            if block.is_empty() {
+                first_key = key;
+            }
+            write_chunk(&mut block, key)?;
+            write_chunk(&mut block, value)?;
+            if block.len() >= DISK_BLOCK_SIZE {
+                writer.write_all(&block)?;
+                index.push((first_key, offset, block.len() as u64));
+                offset += block.len() as u64;
+                block.clear();
+            }
+        }
+        if !block.is_empty() {
+            writer.write_all(&block)?;
+            index.push((first_key, offset, block.len() as u64));
+            offset += block.len() as u64;
+        }
+
+        for &(first_key, block_offset, len) in &index {
+            write_chunk(&mut writer, first_key)?;
+            writer.write_all(&block_offset.to_le_bytes())?;
+            writer.write_all(&len.to_le_bytes())?;
+        }
+        writer.write_all(&offset.to_le_bytes())?;
+        writer.write_all(&(index.len() as u64).to_le_bytes())?;
+        writer.write_all(&(self.len as u64).to_le_bytes())?;
+        writer.write_all(DISK_MAGIC)?;
+        writer.flush()
+    }
+}
+
+/// A read-only table backed by a file written by [`ByteBox::write_disk_table`].
+///
+/// Opening reads only the block index. A lookup binary-searches the index for the one
+/// block that may hold the key, then reads and scans that block, so memory use depends
+/// on the number of blocks rather than on the size of the data.
+#[derive(Debug)]
+pub struct DiskByteBox<R> {
+    reader: R,
+    index: Vec<BlockHandle>,
+    len: u64,
+}
+
+impl DiskByteBox<BufReader<File>> {
+    /// Opens the disk table stored at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidData` if the file is not a disk table, or the underlying I/O error.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::from_reader(BufReader::new(File::open(path)?))
+    }
+}
+
+impl<R: Read + Seek> DiskByteBox<R> {
+    /// Reads the footer and block index of a disk table.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidData` if the magic bytes or the footer do not match, or the
+    /// underlying I/O error.
+    pub fn from_reader(mut reader: R) -> io::Result<Self> {
+        let mut magic = [0u8; 8];
+        reader.seek(SeekFrom::Start(0))?;
+        reader.read_exact(&mut magic)?;
+        if &magic != DISK_MAGIC {
+            return Err(invalid("not a ByteBox disk table"));
+        }
+
+        let file_len = reader.seek(SeekFrom::End(0))?;
+        if file_len < DISK_MAGIC.len() as u64 + FOOTER_LEN {
+            return Err(invalid("disk table footer is truncated"));
+        }
+        reader.seek(SeekFrom::Start(file_len - FOOTER_LEN))?;
+        let index_offset = read_u64(&mut reader)?;
+        let block_count = read_u64(&mut reader)?;
+        let len = read_u64(&mut reader)?;
+        reader.read_exact(&mut magic)?;
+        if &magic != DISK_MAGIC || index_offset > file_len - FOOTER_LEN {
+            return Err(invalid("disk table footer is corrupt"));
+        }
+
+        reader.seek(SeekFrom::Start(index_offset))?;
+        let mut index = Vec::new();
+        for _ in 0..block_count {
+            let first_key = read_chunk(&mut reader)?;
+            let offset = read_u64(&mut reader)?;
+            let len = read_u64(&mut reader)?;
+            if offset.saturating_add(len) > index_offset {
+                return Err(invalid("disk table index points past the data blocks"));
+            }
+            index.push(BlockHandle {
+                first_key,
+                offset,
+                len,
+            });
+        }
+
+        Ok(DiskByteBox { reader, index, len })
+    }
+
+    /// Returns the number of entries in the table.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Returns `true` if the table holds no entries.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of data blocks, which is also the number of index records kept
+    /// in memory.
+    pub fn block_count(&self) -> usize {
+        self.index.len()
+    }
+
+    /// Looks `key` up, reading at most one data block.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(Vec<u8>))` holding the value if the key exists.
+    /// * `Ok(None)` if it does not.
+    ///
+    /// # Errors
+    ///
+    /// Returns `UnexpectedEof` or `InvalidData` for a corrupt block, or the underlying I/O
+    /// error.
+    pub fn get(&mut self, key: &[u8]) -> io::Result<Option<Vec<u8>>> {
+        let block = match self
+            .index
+            .partition_point(|handle| handle.first_key.as_slice() <= key)
+        {
+            0 => return Ok(None),
+            after => &self.index[after - 1],
+        };
+
+        self.reader.seek(SeekFrom::Start(block.offset))?;
+        let mut data = (&mut self.reader).take(block.len);
+        while data.limit() > 0 {
+            let entry_key = read_chunk(&mut data)?;
+            let value = read_chunk(&mut data)?;
+            match entry_key.as_slice().cmp(key) {
+                std::cmp::Ordering::Less => {}
+                std::cmp::Ordering::Equal => return Ok(Some(value)),
+                std::cmp::Ordering::Greater => return Ok(None),
+            }
+        }
+        Ok(None)
+    }
+}
@@ -0,0 +1,370 @@
+//! Error types returned by `ByteBox` operations.
+use std::error::Error;
+use std::fmt::{self, Display};
+
+/// Returned when an operation requires a key that is not present in the `ByteBox`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingKey {
+    /// The key that was not found.
+    pub key: Vec<u8>,
+}
+
+impl Display for MissingKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "key {:?} not found", String::from_utf8_lossy(&self.key))
+    }
+}
+
+impl Error for MissingKey {}
+
+/// Returned by [`ByteBox::try_get_range`](crate::ByteBox::try_get_range) when a sub-slice
+/// of a value cannot be produced.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RangeError {
+    /// The key is not present in the table.
+    MissingKey,
+    /// The requested range does not fit in the value.
+    OutOfBounds {
+        /// Start of the requested range.
+        start: usize,
+        /// End (exclusive) of the requested range.
+        end: usize,
+        /// Length of the stored value.
+        len: usize,
+    },
+}
+
+impl Display for RangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RangeError::MissingKey => write!(f, "key not found"),
+            RangeError::OutOfBounds { start, end, len } => write!(
+                f,
+                "range {}..{} out of bounds for value of length {}",
+                start, end, len
+            ),
+        }
+    }
+}
+
+impl Error for RangeError {}
+
+/// Returned by [`ByteBox::try_prealloc`](crate::ByteBox::try_prealloc) when the requested
+/// capacity cannot or should not be allocated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PreallocError {
+    /// A table needs at least one cell.
+    ///
+    /// No longer returned: a capacity of zero creates a table that allocates on first
+    /// insert. Kept so that existing matches keep compiling.
+    ZeroCapacity,
+    /// The requested number of cells is above the configured maximum.
+    ExceedsLimit {
+        /// Number of cells requested.
+        requested: usize,
+        /// Maximum number of cells allowed.
+        limit: usize,
+    },
+    /// The cell array does not fit in the memory currently available.
+    OutOfMemory {
+        /// Size of the cell array, in bytes.
+        requested_bytes: usize,
+    },
+}
+
+impl Display for PreallocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PreallocError::ZeroCapacity => write!(f, "capacity must be at least one cell"),
+            PreallocError::ExceedsLimit { requested, limit } => write!(
+                f,
+                "requested {} cells, above the limit of {}",
+                requested, limit
+            ),
+            PreallocError::OutOfMemory { requested_bytes } => write!(
+                f,
+                "cannot allocate {} bytes for the cell array",
+                requested_bytes
+            ),
+        }
+    }
+}
+
+impl Error for PreallocError {}
+
+/// Returned by [`ByteBox::from_header_block`](crate::ByteBox::from_header_block) when a
+/// header block is malformed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeaderBlockError {
+    /// The block ends in the middle of the field starting at `offset`.
+    Truncated {
+        /// Position of the incomplete length prefix or field.
+        offset: usize,
+    },
+    /// The record at `offset` refers to a static name that does not exist.
+    UnknownIndex {
+        /// The unknown static table index.
+        index: u8,
+        /// Position of the record.
+        offset: usize,
+    },
+}
+
+impl Display for HeaderBlockError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HeaderBlockError::Truncated { offset } => {
+                write!(f, "header block truncated at offset {}", offset)
+            }
+            HeaderBlockError::UnknownIndex { index, offset } => write!(
+                f,
+                "unknown static name index {} at offset {}",
+                index, offset
+            ),
+        }
+    }
+}
+
+impl Error for HeaderBlockError {}
+
+/// Returned by [`ByteBox::insert_with_policy`](crate::ByteBox::insert_with_policy) under
+/// [`DuplicatePolicy::Error`](crate::policy::DuplicatePolicy::Error) when the key already exists.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateKey {
+    /// The key that was already present.
+    pub key: Vec<u8>,
+}
+
+impl Display for DuplicateKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "key {:?} already exists",
+            String::from_utf8_lossy(&self.key)
+        )
+    }
+}
+
+impl Error for DuplicateKey {}
+
+/// Returned by the checked arithmetic methods of `ByteBox`, such as
+/// [`ByteBox::checked_add`](crate::ByteBox::checked_add).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NumericError {
+    /// The key is not present in the table.
+    MissingKey,
+    /// The stored value does not have the width of the requested integer type.
+    WidthMismatch {
+        /// Width of the integer type, in bytes.
+        expected: usize,
+        /// Length of the stored value.
+        found: usize,
+    },
+    /// The result does not fit in the integer type.
+    Overflow,
+}
+
+impl Display for NumericError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NumericError::MissingKey => write!(f, "key not found"),
+            NumericError::WidthMismatch { expected, found } => write!(
+                f,
+                "stored value is {} bytes long, expected {}",
+                found, expected
+            ),
+            NumericError::Overflow => write!(f, "arithmetic overflow"),
+        }
+    }
+}
+
+impl Error for NumericError {}
+
+/// Returned by [`ByteBox::get_decoded`](crate::ByteBox::get_decoded) when the registered
+/// read decoder rejects a stored value.
+#[derive(Debug)]
+pub struct DecodeError {
+    /// The key whose value could not be decoded.
+    pub key: Vec<u8>,
+    /// The error reported by the decoder.
+    pub source: Box<dyn Error + Send + Sync>,
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "cannot decode value of key {:?}: {}",
+            String::from_utf8_lossy(&self.key),
+            self.source
+        )
+    }
+}
+
+impl Error for DecodeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&*self.source)
+    }
+}
+
+/// Returned by [`ByteRecord::from_bytes`](crate::record::ByteRecord::from_bytes) when a
+/// value does not have the size of the record type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecordSizeMismatch {
+    /// Size of the record type, in bytes.
+    pub expected: usize,
+    /// Length of the value.
+    pub found: usize,
+}
+
+impl Display for RecordSizeMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "value is {} bytes long, expected a {} byte record",
+            self.found, self.expected
+        )
+    }
+}
+
+impl Error for RecordSizeMismatch {}
+
+/// Returned by [`unpack_values`](crate::packed::unpack_values) when a value is not a valid
+/// packed value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackedValueError {
+    /// The length prefix or the field starting at `offset` runs past the end of the value.
+    Truncated {
+        /// Position of the field's length prefix.
+        offset: usize,
+    },
+    /// The length prefix at `offset` does not fit in 64 bits.
+    LengthOverflow {
+        /// Position of the length prefix.
+        offset: usize,
+    },
+}
+
+impl Display for PackedValueError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PackedValueError::Truncated { offset } => {
+                write!(
+                    f,
+                    "packed value truncated in the field at offset {}",
+                    offset
+                )
+            }
+            PackedValueError::LengthOverflow { offset } => {
+                write!(f, "length prefix at offset {} overflows 64 bits", offset)
+            }
+        }
+    }
+}
+
+impl Error for PackedValueError {}
+
+/// Returned by [`Utf8KeyBox`](crate::utf8::Utf8KeyBox) when a key is not valid UTF-8.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidUtf8Key {
+    /// The rejected key.
+    pub key: Vec<u8>,
+    /// Length of the longest valid UTF-8 prefix of the key.
+    pub valid_up_to: usize,
+}
+
+impl Display for InvalidUtf8Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "key {:?} is not valid UTF-8 (invalid byte at offset {})",
+            String::from_utf8_lossy(&self.key),
+            self.valid_up_to
+        )
+    }
+}
+
+impl Error for InvalidUtf8Key {}
+
+/// Returned by [`ByteBox::ingest_frame`](crate::ByteBox::ingest_frame) when a frame is
+/// malformed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameError {
+    /// The prefix or field starting at `offset` runs past the end of the frame.
+    Truncated {
+        /// Position of the prefix or field.
+        offset: usize,
+    },
+    /// The frame continues after its last pair.
+    TrailingBytes {
+        /// Position of the first unexpected byte.
+        offset: usize,
+    },
+}
+
+impl Display for FrameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FrameError::Truncated { offset } => {
+                write!(f, "frame truncated in the field at offset {}", offset)
+            }
+            FrameError::TrailingBytes { offset } => {
+                write!(
+                    f,
+                    "unexpected bytes after the last pair at offset {}",
+                    offset
+                )
+            }
+        }
+    }
+}
+
+impl Error for FrameError {}
+
+/// Returned when an [`EntryHandle`](crate::handle::EntryHandle) is used after its table
+/// was structurally modified.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StaleHandle {
+    /// Generation of the table when the handle was created.
+    pub expected: u64,
+    /// Generation of the table when the handle was used.
+    pub found: u64,
+}
+
+impl Display for StaleHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "entry handle is stale (generation {} -> {})",
+            self.expected, self.found
+        )
+    }
+}
+
+impl Error for StaleHandle {}
+
+/// Returned when a [`StrBox`](crate::strbox::StrBox) is built from a table holding a key
+/// or value that is not valid UTF-8.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidUtf8Entry {
+    /// The key of the rejected entry.
+    pub key: Vec<u8>,
+    /// `true` if the value is invalid, `false` if the key is.
+    pub in_value: bool,
+    /// Length of the longest valid UTF-8 prefix of the invalid key or value.
+    pub valid_up_to: usize,
+}
+
+impl Display for InvalidUtf8Entry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} of key {:?} is not valid UTF-8 (invalid byte at offset {})",
+            if self.in_value { "value" } else { "key" },
+            String::from_utf8_lossy(&self.key),
+            self.valid_up_to
+        )
+    }
+}
+
+impl Error for InvalidUtf8Entry {}
@@ -0,0 +1,150 @@
+//! C bindings for `ByteBox`, enabled by the `ffi` feature.
+//!
+//! The matching declarations live in `include/bytesbox.h`. Tables and exports are handed
+//! to C as opaque pointers that must be released with [`bytebox_free`] and
+//! [`bytebox_raw_free`] respectively.
+use super::*;
+
+use raw::RawExport;
+use std::ptr;
+use std::slice;
+
+/// Builds a byte slice from a C pointer and length, accepting a null pointer for `len == 0`.
+unsafe fn bytes_from<'a>(data: *const u8, len: usize) -> &'a [u8] {
+    if len == 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(data, len)
+    }
+}
+
+/// Allocates a new, empty `ByteBox`.
+#[no_mangle]
+pub extern "C" fn bytebox_new() -> *mut ByteBox {
+    Box::into_raw(Box::new(ByteBox::new()))
+}
+
+/// Releases a table created by [`bytebox_new`].
+///
+/// # Safety
+///
+/// `table` must be null or a pointer returned by [`bytebox_new`] that was not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn bytebox_free(table: *mut ByteBox) {
+    if !table.is_null() {
+        drop(Box::from_raw(table));
+    }
+}
+
+/// Inserts a key-value pair, returning `true` if the key was new.
+///
+/// # Safety
+///
+/// `table` must be a live table and `key`/`value` must point to `key_len`/`value_len`
+/// readable bytes (or be null when the length is zero).
+#[no_mangle]
+pub unsafe extern "C" fn bytebox_insert(
+    table: *mut ByteBox,
+    key: *const u8,
+    key_len: usize,
+    value: *const u8,
+    value_len: usize,
+) -> bool {
+    (*table).insert(bytes_from(key, key_len), bytes_from(value, value_len))
+}
+
+/// Looks up a key, writing the value length to `value_len` and returning a pointer to it.
+///
+/// Returns null when the key is missing. The returned pointer stays valid until the
+/// table is mutated or freed.
+///
+/// # Safety
+///
+/// `table` must be a live table, `key` must point to `key_len` readable bytes (or be null
+/// when the length is zero) and `value_len` must be null or writable.
+#[no_mangle]
+pub unsafe extern "C" fn bytebox_get(
+    table: *const ByteBox,
+    key: *const u8,
+    key_len: usize,
+    value_len: *mut usize,
+) -> *const u8 {
+    match (*table).get(bytes_from(key, key_len)) {
+        Some(value) => {
+            if !value_len.is_null() {
+                *value_len = value.len();
+            }
+            value.as_ptr()
+        }
+        None => ptr::null(),
+    }
+}
+
+/// Removes a key, returning `true` if it was present.
+///
+/// # Safety
+///
+/// `table` must be a live table and `key` must point to `key_len` readable bytes (or be
+/// null when the length is zero).
+#[no_mangle]
+pub unsafe extern "C" fn bytebox_remove(
+    table: *mut ByteBox,
+    key: *const u8,
+    key_len: usize,
+) -> bool {
+    (*table).remove(bytes_from(key, key_len)).is_some()
+}
+
+/// Returns the number of key-value pairs stored in the table.
+///
+/// # Safety
+///
+/// `table` must be a live table.
+#[no_mangle]
+pub unsafe extern "C" fn bytebox_len(table: *const ByteBox) -> usize {
+    (*table).len()
+}
+
+/// Exports the table into the flat layout described by [`RawExport`].
+///
+/// # Safety
+///
+/// `table` must be a live table. The result must be released with [`bytebox_raw_free`].
+#[no_mangle]
+pub unsafe extern "C" fn bytebox_export(table: *const ByteBox) -> *mut RawExport {
+    Box::into_raw(Box::new((*table).export_raw()))
+}
+
+/// Returns the offsets array of an export, writing its element count to `len`.
+///
+/// # Safety
+///
+/// `raw` must be a live export and `len` must be writable.
+#[no_mangle]
+pub unsafe extern "C" fn bytebox_raw_offsets(raw: *const RawExport, len: *mut usize) -> *const u64 {
+    *len = (*raw).offsets.len();
+    (*raw).offsets.as_ptr()
+}
+
+/// Returns the bytes array of an export, writing its length to `len`.
+///
+/// # Safety
+///
+/// `raw` must be a live export and `len` must be writable.
+#[no_mangle]
+pub unsafe extern "C" fn bytebox_raw_bytes(raw: *const RawExport, len: *mut usize) -> *const u8 {
+    *len = (*raw).bytes.len();
+    (*raw).bytes.as_ptr()
+}
+
+/// Releases an export created by [`bytebox_export`].
+///
+/// # Safety
+///
+/// `raw` must be null or a pointer returned by [`bytebox_export`] that was not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn bytebox_raw_free(raw: *mut RawExport) {
+    if !raw.is_null() {
+        drop(Box::from_raw(raw));
+    }
+}
@@ -0,0 +1,149 @@
+//! Key-value pairs carried in length-prefixed wire frames.
+//!
+//! A frame is a big-endian `u32` pair count followed by that many pairs, each written as
+//! a big-endian `u32` key length, the key, a big-endian `u32` value length and the value.
+//! There is no padding and nothing follows the last pair:
+//!
+//! ```text
+//! count:u32 | key_len:u32 key | value_len:u32 value | ... (count pairs)
+//! ```
+use super::*;
+
+/// Size of the count and length prefixes, in bytes.
+const PREFIX_LEN: usize = 4;
+
+/// Encodes `pairs` as a frame readable by [`ByteBox::ingest_frame`].
+///
+/// # Panics
+///
+/// Panics if there are more than `u32::MAX` pairs or a key or value is longer than
+/// `u32::MAX` bytes.
+///
+/// # Examples
+///
+/// ```rust
+/// use bytesbox::frame::write_frame;
+///
+/// let frame = write_frame(&[(b"id", b"7")]);
+/// assert_eq!(frame, b"\0\0\0\x01\0\0\0\x02id\0\0\0\x017");
+/// ```
+pub fn write_frame(pairs: &[(&[u8], &[u8])]) -> Vec<u8> {
+    let len = PREFIX_LEN
+        + pairs
+            .iter()
+            .map(|(key, value)| 2 * PREFIX_LEN + key.len() + value.len())
+            .sum::<usize>();
+    let mut out = Vec::with_capacity(len);
+    out.extend_from_slice(&prefix(pairs.len()));
+    for (key, value) in pairs {
+        out.extend_from_slice(&prefix(key.len()));
+        out.extend_from_slice(key);
+        out.extend_from_slice(&prefix(value.len()));
+        out.extend_from_slice(value);
+    }
+    out
+}
+
+fn prefix(len: usize) -> [u8; PREFIX_LEN] {
+    u32::try_from(len)
+        .expect("frame field longer than u32::MAX")
+        .to_be_bytes()
+}
+
+/// Reads the frame pair by pair without copying.
+struct FrameReader<'a> {
+    frame: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> FrameReader<'a> {
+    fn read_prefix(&mut self) -> Result<usize, FrameError> {
+        let bytes = self.read(PREFIX_LEN)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize)
+    }
+
+    fn read_field(&mut self) -> Result<&'a [u8], FrameError> {
+        let start = self.offset;
+        let len = self.read_prefix()?;
+        self.read(len)
+            .map_err(|_| FrameError::Truncated { offset: start })
+    }
+
+    fn read(&mut self, len: usize) -> Result<&'a [u8], FrameError> {
+        let bytes = self
+            .frame
+            .get(self.offset..)
+            .and_then(|rest| rest.get(..len))
+            .ok_or(FrameError::Truncated {
+                offset: self.offset,
+            })?;
+        self.offset += len;
+        Ok(bytes)
+    }
+}
+
+impl ByteBox {
+    /// Inserts every pair of a wire frame; see the [module documentation](crate::frame)
+    /// for the layout.
+    ///
+    /// The pairs are inserted straight from `frame`, without collecting them first. The
+    /// frame is checked in full before the first insert, so a malformed frame leaves the
+    /// table unchanged. A key repeated within the frame ends up with its last value.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - One complete frame.
+    ///
+    /// # Returns
+    ///
+    /// The number of pairs in the frame.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`FrameError`] if a prefix or field runs past the end of `frame`, or if
+    /// bytes follow the last pair.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::frame::write_frame;
+    /// use bytesbox::ByteBox;
+    ///
+    /// let frame = write_frame(&[(b"user:1", b"alice"), (b"user:2", b"bob")]);
+    ///
+    /// let mut bytebox = ByteBox::new();
+    /// assert_eq!(bytebox.ingest_frame(&frame), Ok(2));
+    /// assert_eq!(bytebox.get(b"user:2"), Some(&b"bob"[..]));
+    ///
+    /// assert!(bytebox.ingest_frame(&frame[..frame.len() - 1]).is_err());
+    /// ```
+    pub fn ingest_frame(&mut self, frame: &[u8]) -> Result<usize, FrameError> {
+        let count = Self::check_frame(frame)?;
+        let mut reader = FrameReader {
+            frame,
+            offset: PREFIX_LEN,
+        };
+        for _ in 0..count {
+            let key = reader.read_field()?;
+            let value = reader.read_field()?;
+            self.insert(key, value);
+        }
+        Ok(count)
+    }
+
+    /// Walks the whole frame and returns its pair count.
+    fn check_frame(frame: &[u8]) -> Result<usize, FrameError> {
+        let mut reader = FrameReader { frame, offset: 0 };
+        let count = reader.read_prefix()?;
+        for _ in 0..count {
+            reader.read_field()?;
+            reader.read_field()?;
+        }
+        if reader.offset < frame.len() {
+            return Err(FrameError::TrailingBytes {
+                offset: reader.offset,
+            });
+        }
+        Ok(count)
+    }
+}
@@ -0,0 +1,170 @@
+//! An immutable, read-optimized form of a `ByteBox`.
+use super::*;
+
+/// Location of one entry inside the buffer of a [`FrozenByteBox`].
+#[derive(Debug, Clone, Copy)]
+struct FrozenEntry {
+    hash: u64,
+    offset: usize,
+    key_len: usize,
+    value_len: usize,
+}
+
+/// A read-only `ByteBox` laid out for lookups.
+///
+/// All keys and values are packed into a single buffer, and an open-addressed index of
+/// at most half occupancy maps hashes to entries. A lookup hashes the key once, probes a
+/// few adjacent slots, and compares the stored hash before touching the key bytes, so
+/// there are no chains to follow and no per-entry allocations to chase.
+///
+/// This struct is created by the [`ByteBox::freeze`] method; [`FrozenByteBox::thaw`]
+/// turns it back into a mutable table.
+///
+/// # Examples
+///
+/// ```rust
+/// use bytesbox::ByteBox;
+///
+/// let mut bytebox = ByteBox::new();
+/// bytebox.insert(b"route:/", b"index");
+/// bytebox.insert(b"route:/about", b"about");
+///
+/// let frozen = bytebox.freeze();
+/// assert_eq!(frozen.get(b"route:/about"), Some(&b"about"[..]));
+/// assert_eq!(frozen.get(b"route:/missing"), None);
+///
+/// let mut bytebox = frozen.thaw();
+/// bytebox.insert(b"route:/contact", b"contact");
+/// assert_eq!(bytebox.len(), 3);
+/// ```
+#[derive(Debug, Clone)]
+pub struct FrozenByteBox {
+    data: Vec<u8>,
+    entries: Vec<FrozenEntry>,
+    /// `entry index + 1` per slot, zero meaning empty; the length is a power of two.
+    slots: Vec<usize>,
+    seed: u64,
+    alloc: usize,
+}
+
+impl FrozenByteBox {
+    /// Returns the number of key-value pairs stored.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if no key-value pair is stored.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Retrieves the value associated with the given key.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(&[u8])` containing the value if the key exists.
+    /// * `None` if the key does not exist.
+    pub fn get(&self, key: &[u8]) -> Option<&[u8]> {
+        let hash = ByteBox::full_hash(key, self.seed);
+        let mask = self.slots.len() - 1;
+        let mut slot = hash as usize & mask;
+        loop {
+            let index = self.slots[slot].checked_sub(1)?;
+            let entry = &self.entries[index];
+            if entry.hash == hash && self.key(entry) == key {
+                return Some(self.value(entry));
+            }
+            slot = (slot + 1) & mask;
+        }
+    }
+
+    /// Returns `true` if the table contains the given key.
+    pub fn contains_key(&self, key: &[u8]) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Returns an iterator over every key with its value, in the iteration order of the
+    /// table that was frozen.
+    pub fn iter(&self) -> impl Iterator<Item = (&[u8], &[u8])> + '_ {
+        self.entries
+            .iter()
+            .map(move |entry| (self.key(entry), self.value(entry)))
+    }
+
+    /// Converts the table back into a mutable `ByteBox`.
+    ///
+    /// The new table has the capacity and hash seed of the one that was frozen. Settings
+    /// attached to the original table, such as its load factor, handlers and watermarks,
+    /// are not carried over.
+    pub fn thaw(self) -> ByteBox {
+        let mut byte_box = ByteBox::prealloc(self.alloc);
+        byte_box.rehash_with_seed(self.seed);
+        for (key, value) in self.iter() {
+            byte_box.insert(key, value);
+        }
+        byte_box
+    }
+
+    fn key(&self, entry: &FrozenEntry) -> &[u8] {
+        &self.data[entry.offset..entry.offset + entry.key_len]
+    }
+
+    fn value(&self, entry: &FrozenEntry) -> &[u8] {
+        let start = entry.offset + entry.key_len;
+        &self.data[start..start + entry.value_len]
+    }
+}
+
+impl ByteBox {
+    /// Converts the table into a [`FrozenByteBox`] for a read-only phase.
+    ///
+    /// Freezing copies every entry once into a flat buffer and builds the lookup index,
+    /// which costs `O(n)`; afterwards lookups skip the chain walk and pointer chasing of
+    /// the mutable table. Use [`FrozenByteBox::thaw`] to make the table writable again.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut bytebox = ByteBox::new();
+    /// for i in 0..100u32 {
+    ///     bytebox.insert(&i.to_le_bytes(), b"ready");
+    /// }
+    ///
+    /// let frozen = bytebox.freeze();
+    /// assert_eq!(frozen.len(), 100);
+    /// assert!((0..100u32).all(|i| frozen.contains_key(&i.to_le_bytes())));
+    /// ```
+    pub fn freeze(self) -> FrozenByteBox {
+        let mut data = Vec::with_capacity(self.content_bytes);
+        let mut entries = Vec::with_capacity(self.len);
+        let mut slots = vec![0; (self.len * 2).next_power_of_two()];
+        let mask = slots.len() - 1;
+
+        for (key, value) in self.iter() {
+            let hash = Self::full_hash(key, self.seed);
+            let mut slot = hash as usize & mask;
+            while slots[slot] != 0 {
+                slot = (slot + 1) & mask;
+            }
+            slots[slot] = entries.len() + 1;
+            entries.push(FrozenEntry {
+                hash,
+                offset: data.len(),
+                key_len: key.len(),
+                value_len: value.len(),
+            });
+            data.extend_from_slice(key);
+            data.extend_from_slice(value);
+        }
+
+        FrozenByteBox {
+            data,
+            entries,
+            slots,
+            seed: self.seed,
+            alloc: self.alloc,
+        }
+    }
+}
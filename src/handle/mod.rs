@@ -0,0 +1,151 @@
+//! Handles for revisiting an entry without hashing its key again.
+use super::*;
+
+/// The position of an entry in a `ByteBox`, valid until the table is structurally
+/// modified.
+///
+/// A handle records the cell and chain position of an entry together with the table's
+/// generation. Inserting a new key, removing an entry, rehashing or clearing the table
+/// bumps the generation and invalidates every handle; updating values, including through
+/// [`ByteBox::write`], does not. A handle is only meaningful for the table that produced
+/// it: used with another table it is either rejected or refers to an unrelated entry.
+///
+/// Handles are created by [`ByteBox::get_handle`] and [`ByteBox::insert_handle`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EntryHandle {
+    cell: usize,
+    depth: usize,
+    generation: u64,
+}
+
+impl ByteBox {
+    /// Returns a handle to the entry holding `key`.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(EntryHandle)` if the key exists.
+    /// * `None` if the key does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut bytebox = ByteBox::new();
+    /// bytebox.insert(b"conn:42", b"idle");
+    ///
+    /// let handle = bytebox.get_handle(b"conn:42").unwrap();
+    /// bytebox.write(handle, b"busy").unwrap();
+    /// assert_eq!(bytebox.read(handle), Ok(&b"busy"[..]));
+    /// assert!(bytebox.get_handle(b"conn:7").is_none());
+    /// ```
+    pub fn get_handle(&self, key: &[u8]) -> Option<EntryHandle> {
+        if self.alloc == 0 {
+            return None;
+        }
+        let cell = self.cell_of(key);
+        let mut current = self.cells[cell].as_deref();
+        let mut depth = 0;
+        while let Some(entry) = current {
+            if entry.key == key {
+                return Some(EntryHandle {
+                    cell,
+                    depth,
+                    generation: self.generation,
+                });
+            }
+            current = entry.next.as_deref();
+            depth += 1;
+        }
+        None
+    }
+
+    /// Inserts a key-value pair and returns a handle to its entry.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - A byte slice representing the key.
+    /// * `value` - A byte slice representing the value.
+    pub fn insert_handle(&mut self, key: &[u8], value: &[u8]) -> EntryHandle {
+        self.insert(key, value);
+        self.get_handle(key).expect("the key was just inserted")
+    }
+
+    /// Returns the value of the entry behind `handle`.
+    ///
+    /// The key is not hashed or compared: the lookup goes straight to the recorded cell
+    /// and steps down the chain to the recorded position.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StaleHandle`] if the table was structurally modified since the handle
+    /// was created.
+    pub fn read(&self, handle: EntryHandle) -> Result<&[u8], StaleHandle> {
+        self.check_handle(handle)?;
+        self.handle_entry(handle)
+            .map(|entry| entry.value.as_slice())
+            .ok_or(StaleHandle {
+                expected: handle.generation,
+                found: self.generation,
+            })
+    }
+
+    /// Replaces the value of the entry behind `handle`.
+    ///
+    /// Writing a value is not a structural change, so `handle` and every other handle
+    /// stay valid.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StaleHandle`] if the table was structurally modified since the handle
+    /// was created; the table is left unchanged.
+    pub fn write(&mut self, handle: EntryHandle, value: &[u8]) -> Result<(), StaleHandle> {
+        self.check_handle(handle)?;
+        let stale = StaleHandle {
+            expected: handle.generation,
+            found: self.generation,
+        };
+        let entry = self.handle_entry_mut(handle).ok_or(stale)?;
+
+        let before = entry_hash(&entry.key, &entry.value);
+        let before_len = entry.value.len();
+        // A fresh allocation releases the old buffer's capacity, as `insert` does.
+        entry.value = value.to_vec();
+        let after = entry_hash(&entry.key, &entry.value);
+        self.checksum = self.checksum.wrapping_sub(before).wrapping_add(after);
+        self.content_bytes = self.content_bytes - before_len + value.len();
+        self.after_mutation();
+        Ok(())
+    }
+
+    fn check_handle(&self, handle: EntryHandle) -> Result<(), StaleHandle> {
+        if handle.generation == self.generation {
+            Ok(())
+        } else {
+            Err(StaleHandle {
+                expected: handle.generation,
+                found: self.generation,
+            })
+        }
+    }
+
+    fn handle_entry(&self, handle: EntryHandle) -> Option<&Entry> {
+        let mut entry = self.cells.get(handle.cell)?.as_deref()?;
+        for _ in 0..handle.depth {
+            entry = entry.next.as_deref()?;
+        }
+        Some(entry)
+    }
+
+    fn handle_entry_mut(&mut self, handle: EntryHandle) -> Option<&mut Entry> {
+        let mut entry = self
+            .cells
+            .get_mut(handle.cell)?
+            .as_mut()
+            .map(Arc::make_mut)?;
+        for _ in 0..handle.depth {
+            entry = entry.next.as_mut().map(Arc::make_mut)?;
+        }
+        Some(entry)
+    }
+}
@@ -0,0 +1,237 @@
+//! Alternative hash functions and a helper to compare them on real keys.
+//!
+//! `ByteBox` hashes keys with the standard library's `DefaultHasher`. Faster or keyed
+//! functions are available behind features:
+//!
+//! * `fxhash` - FxHash, via `rustc-hash`.
+//! * `ahash` - AHash, randomly keyed.
+//! * `siphash` - SipHash-1-3 with random keys, via `siphasher`.
+//! * `xxhash` - XXH3, via `xxhash-rust`.
+//!
+//! [`ByteBox::evaluate_hashers`] runs every enabled function over a sample of keys and
+//! reports how evenly and how fast each one spreads them.
+use super::*;
+
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher;
+use std::time::{Duration, Instant};
+
+/// A hash function that can be evaluated by [`ByteBox::evaluate_hashers`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HasherChoice {
+    /// The standard library's `DefaultHasher`, used by `ByteBox`.
+    Default,
+    /// FxHash, fast but unkeyed.
+    #[cfg(feature = "fxhash")]
+    Fx,
+    /// AHash with random keys.
+    #[cfg(feature = "ahash")]
+    AHash,
+    /// SipHash-1-3 with random keys.
+    #[cfg(feature = "siphash")]
+    Sip13,
+    /// XXH3, 64-bit output.
+    #[cfg(feature = "xxhash")]
+    Xxh3,
+}
+
+impl HasherChoice {
+    /// Returns every hash function enabled in this build, `Default` first.
+    pub fn available() -> Vec<HasherChoice> {
+        vec![
+            HasherChoice::Default,
+            #[cfg(feature = "fxhash")]
+            HasherChoice::Fx,
+            #[cfg(feature = "ahash")]
+            HasherChoice::AHash,
+            #[cfg(feature = "siphash")]
+            HasherChoice::Sip13,
+            #[cfg(feature = "xxhash")]
+            HasherChoice::Xxh3,
+        ]
+    }
+
+    /// Returns a short, human-readable name of the hash function.
+    pub fn name(&self) -> &'static str {
+        match self {
+            HasherChoice::Default => "DefaultHasher",
+            #[cfg(feature = "fxhash")]
+            HasherChoice::Fx => "FxHash",
+            #[cfg(feature = "ahash")]
+            HasherChoice::AHash => "AHash",
+            #[cfg(feature = "siphash")]
+            HasherChoice::Sip13 => "SipHash-1-3",
+            #[cfg(feature = "xxhash")]
+            HasherChoice::Xxh3 => "XXH3",
+        }
+    }
+
+    /// Hashes every key with this function and measures the outcome over `allocation` cells.
+    fn evaluate<K: AsRef<[u8]>>(&self, keys: &[K], allocation: usize) -> HasherEvaluation {
+        match self {
+            HasherChoice::Default => measure(*self, keys, allocation, |key| {
+                let mut hasher = DefaultHasher::new();
+                key.hash(&mut hasher);
+                hasher.finish()
+            }),
+            #[cfg(feature = "fxhash")]
+            HasherChoice::Fx => measure(*self, keys, allocation, |key| {
+                let mut hasher = rustc_hash::FxHasher::default();
+                key.hash(&mut hasher);
+                hasher.finish()
+            }),
+            #[cfg(feature = "ahash")]
+            HasherChoice::AHash => {
+                let state = ahash::RandomState::new();
+                measure(*self, keys, allocation, |key| {
+                    let mut hasher = state.build_hasher();
+                    key.hash(&mut hasher);
+                    hasher.finish()
+                })
+            }
+            #[cfg(feature = "siphash")]
+            HasherChoice::Sip13 => {
+                let (k0, k1) = (random_u64(), random_u64());
+                measure(*self, keys, allocation, |key| {
+                    let mut hasher = siphasher::sip::SipHasher13::new_with_keys(k0, k1);
+                    key.hash(&mut hasher);
+                    hasher.finish()
+                })
+            }
+            #[cfg(feature = "xxhash")]
+            HasherChoice::Xxh3 => measure(*self, keys, allocation, xxhash_rust::xxh3::xxh3_64),
+        }
+    }
+}
+
+impl Display for HasherChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// How one hash function spread a sample of keys.
+///
+/// This struct is created by the [`ByteBox::evaluate_hashers`] method.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HasherEvaluation {
+    /// The evaluated hash function.
+    pub hasher: HasherChoice,
+    /// Number of keys hashed.
+    pub keys: usize,
+    /// Number of cells the keys were distributed over.
+    pub allocation: usize,
+    /// Number of cells that received at least one key.
+    pub occupied_cells: usize,
+    /// Largest number of keys that landed in the same cell.
+    pub longest_chain: usize,
+    /// Chi-squared statistic of the cell counts divided by its expected value; close to
+    /// 1.0 for a uniform spread, larger when keys cluster.
+    pub chi_squared_ratio: f64,
+    /// Time spent hashing the whole sample.
+    pub elapsed: Duration,
+}
+
+impl HasherEvaluation {
+    /// Returns the average time spent hashing one key, in nanoseconds.
+    pub fn nanos_per_key(&self) -> f64 {
+        self.elapsed.as_nanos() as f64 / self.keys.max(1) as f64
+    }
+}
+
+impl Display for HasherEvaluation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {:.1} ns/key, chi² ratio {:.2}, longest chain {}, {} of {} cells occupied",
+            self.hasher,
+            self.nanos_per_key(),
+            self.chi_squared_ratio,
+            self.longest_chain,
+            self.occupied_cells,
+            self.allocation
+        )
+    }
+}
+
+fn measure<K, F>(hasher: HasherChoice, keys: &[K], allocation: usize, hash: F) -> HasherEvaluation
+where
+    K: AsRef<[u8]>,
+    F: Fn(&[u8]) -> u64,
+{
+    let start = Instant::now();
+    let hashes: Vec<u64> = keys.iter().map(|key| hash(key.as_ref())).collect();
+    let elapsed = start.elapsed();
+
+    let mut counts = vec![0usize; allocation];
+    for hash in hashes {
+        counts[(hash as usize) % allocation] += 1;
+    }
+
+    let expected = keys.len() as f64 / allocation as f64;
+    let chi_squared: f64 = if expected == 0.0 {
+        0.0
+    } else {
+        counts
+            .iter()
+            .map(|&count| (count as f64 - expected).powi(2) / expected)
+            .sum()
+    };
+    let degrees = (allocation - 1).max(1) as f64;
+
+    HasherEvaluation {
+        hasher,
+        keys: keys.len(),
+        allocation,
+        occupied_cells: counts.iter().filter(|&&count| count > 0).count(),
+        longest_chain: counts.iter().copied().max().unwrap_or(0),
+        chi_squared_ratio: chi_squared / degrees,
+        elapsed,
+    }
+}
+
+/// Draws a random `u64` from the standard library's per-process random keys.
+#[cfg_attr(not(feature = "siphash"), allow(dead_code))]
+fn random_u64() -> u64 {
+    RandomState::new().build_hasher().finish()
+}
+
+impl ByteBox {
+    /// Compares every enabled hash function on a sample of the caller's keys.
+    ///
+    /// The keys are spread over as many cells as a `ByteBox` holding them would allocate,
+    /// and each function is timed over the whole sample. Use the results to pick the
+    /// function best suited to real key shapes.
+    ///
+    /// # Arguments
+    ///
+    /// * `sample_keys` - Representative keys; duplicates are hashed as given.
+    ///
+    /// # Returns
+    ///
+    /// One [`HasherEvaluation`] per function listed by [`HasherChoice::available`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    /// use bytesbox::hashers::HasherChoice;
+    ///
+    /// let keys: Vec<String> = (0..1000).map(|i| format!("user:{}", i)).collect();
+    /// let results = ByteBox::evaluate_hashers(&keys);
+    ///
+    /// assert_eq!(results[0].hasher, HasherChoice::Default);
+    /// for result in &results {
+    ///     println!("{}", result);
+    /// }
+    /// ```
+    pub fn evaluate_hashers<K: AsRef<[u8]>>(sample_keys: &[K]) -> Vec<HasherEvaluation> {
+        let allocation = ((sample_keys.len() as f64 / 0.75).ceil() as usize + 1)
+            .next_power_of_two()
+            .max(16);
+        HasherChoice::available()
+            .iter()
+            .map(|choice| choice.evaluate(sample_keys, allocation))
+            .collect()
+    }
+}
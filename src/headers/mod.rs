@@ -0,0 +1,184 @@
+//! Compact header block encoding with an optional table of common header names.
+use super::*;
+
+/// Header names that can be replaced by their index in a header block, taken from the
+/// HPACK static table (RFC 7541, Appendix A) with duplicates removed.
+///
+/// A record tagged `i` (with `1 <= i <= STATIC_NAMES.len()`) uses `STATIC_NAMES[i - 1]`
+/// as its name.
+pub const STATIC_NAMES: [&[u8]; 52] = [
+    b":authority",
+    b":method",
+    b":path",
+    b":scheme",
+    b":status",
+    b"accept-charset",
+    b"accept-encoding",
+    b"accept-language",
+    b"accept-ranges",
+    b"accept",
+    b"access-control-allow-origin",
+    b"age",
+    b"allow",
+    b"authorization",
+    b"cache-control",
+    b"content-disposition",
+    b"content-encoding",
+    b"content-language",
+    b"content-length",
+    b"content-location",
+    b"content-range",
+    b"content-type",
+    b"cookie",
+    b"date",
+    b"etag",
+    b"expect",
+    b"expires",
+    b"from",
+    b"host",
+    b"if-match",
+    b"if-modified-since",
+    b"if-none-match",
+    b"if-range",
+    b"if-unmodified-since",
+    b"last-modified",
+    b"link",
+    b"location",
+    b"max-forwards",
+    b"proxy-authenticate",
+    b"proxy-authorization",
+    b"range",
+    b"referer",
+    b"refresh",
+    b"retry-after",
+    b"server",
+    b"set-cookie",
+    b"strict-transport-security",
+    b"transfer-encoding",
+    b"user-agent",
+    b"vary",
+    b"via",
+    b"www-authenticate",
+];
+
+/// How header names are written by [`ByteBox::to_header_block`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeaderEncoding {
+    /// Every name is written in full.
+    Literal,
+    /// Names found in [`STATIC_NAMES`] are written as a one-byte index.
+    StaticTable,
+}
+
+fn read_u32(block: &[u8], offset: &mut usize) -> Result<usize, HeaderBlockError> {
+    let bytes = block
+        .get(*offset..*offset + 4)
+        .ok_or(HeaderBlockError::Truncated { offset: *offset })?;
+    *offset += 4;
+    Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize)
+}
+
+fn read_field<'a>(block: &'a [u8], offset: &mut usize) -> Result<&'a [u8], HeaderBlockError> {
+    let start = *offset;
+    let len = read_u32(block, offset)?;
+    let field = block
+        .get(*offset..offset.saturating_add(len))
+        .ok_or(HeaderBlockError::Truncated { offset: start })?;
+    *offset += len;
+    Ok(field)
+}
+
+fn write_field(block: &mut Vec<u8>, field: &[u8]) {
+    block.extend_from_slice(&(field.len() as u32).to_be_bytes());
+    block.extend_from_slice(field);
+}
+
+impl ByteBox {
+    /// Encodes every entry as a record of a header block.
+    ///
+    /// Each record starts with a tag byte. Tag `0` is followed by the name; a tag between
+    /// 1 and 52 stands for the name at that position of [`STATIC_NAMES`]. The value comes
+    /// next. Names and values are prefixed by their length as a big-endian `u32`.
+    /// Records appear in iteration order.
+    ///
+    /// # Arguments
+    ///
+    /// * `encoding` - Whether common names are replaced by their static table index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a key or value is longer than `u32::MAX` bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::headers::HeaderEncoding;
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut headers = ByteBox::new();
+    /// headers.insert(b"content-type", b"text/html");
+    ///
+    /// let block = headers.to_header_block(HeaderEncoding::StaticTable);
+    /// assert_eq!(block, b"\x16\0\0\0\x09text/html");
+    ///
+    /// let decoded = ByteBox::from_header_block(&block).unwrap();
+    /// assert_eq!(decoded.get(b"content-type"), Some(&b"text/html"[..]));
+    /// ```
+    pub fn to_header_block(&self, encoding: HeaderEncoding) -> Vec<u8> {
+        let mut block = Vec::new();
+        for (name, value) in self.iter() {
+            assert!(
+                name.len() <= u32::MAX as usize && value.len() <= u32::MAX as usize,
+                "header field longer than u32::MAX bytes"
+            );
+            let index = match encoding {
+                HeaderEncoding::Literal => None,
+                HeaderEncoding::StaticTable => STATIC_NAMES.iter().position(|&known| known == name),
+            };
+            match index {
+                Some(index) => block.push(index as u8 + 1),
+                None => {
+                    block.push(0);
+                    write_field(&mut block, name);
+                }
+            }
+            write_field(&mut block, value);
+        }
+        block
+    }
+
+    /// Decodes a header block written by [`ByteBox::to_header_block`].
+    ///
+    /// Both encodings are accepted. When a name appears more than once, the last record wins.
+    ///
+    /// # Arguments
+    ///
+    /// * `block` - The encoded records.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ByteBox)` holding one entry per distinct name.
+    /// * `Err(HeaderBlockError)` if a record is truncated or uses an unknown index.
+    pub fn from_header_block(block: &[u8]) -> Result<ByteBox, HeaderBlockError> {
+        let mut headers = ByteBox::new();
+        let mut offset = 0;
+        while offset < block.len() {
+            let tag = block[offset];
+            let record = offset;
+            offset += 1;
+            let name =
+                match tag {
+                    0 => read_field(block, &mut offset)?,
+                    index => *STATIC_NAMES.get(index as usize - 1).ok_or(
+                        HeaderBlockError::UnknownIndex {
+                            index,
+                            offset: record,
+                        },
+                    )?,
+                };
+            let value = read_field(block, &mut offset)?;
+            headers.insert(name, value);
+        }
+        Ok(headers)
+    }
+}
@@ -0,0 +1,74 @@
+//! Internal consistency checks enabled by the `strict-debug` feature.
+//!
+//! With the feature on, every mutation re-validates the whole table in debug builds, which
+//! costs `O(n)` per call but pins internal corruption to the call that caused it. Release
+//! builds compile the checks out even with the feature on.
+use super::*;
+
+impl ByteBox {
+    /// Panics if an internal invariant of the table does not hold.
+    ///
+    /// Does nothing unless the `strict-debug` feature is enabled in a build with debug
+    /// assertions.
+    #[inline]
+    pub(crate) fn debug_check_invariants(&self) {
+        #[cfg(all(feature = "strict-debug", debug_assertions))]
+        self.check_invariants();
+    }
+
+    /// Walks every chain, checking cell placement, chain termination and the counters
+    /// maintained by the mutations.
+    #[cfg(all(feature = "strict-debug", debug_assertions))]
+    fn check_invariants(&self) {
+        assert_eq!(
+            self.cells.len(),
+            self.alloc,
+            "strict-debug: cell array does not match the allocation"
+        );
+
+        let mut len = 0;
+        let mut checksum = 0u64;
+        let mut content_bytes = 0;
+        for (index, cell) in self.cells.iter().enumerate() {
+            let mut chain: Vec<&[u8]> = Vec::new();
+            let mut current = cell.as_deref();
+            while let Some(entry) = current {
+                // A chain longer than the table can only be a cycle or a lost count.
+                assert!(
+                    len < self.len,
+                    "strict-debug: more entries reachable than the recorded length {}",
+                    self.len
+                );
+                assert_eq!(
+                    self.cell_of(&entry.key),
+                    index,
+                    "strict-debug: key {:?} stored in the wrong cell",
+                    String::from_utf8_lossy(&entry.key)
+                );
+                assert!(
+                    !chain.contains(&&entry.key[..]),
+                    "strict-debug: key {:?} stored twice",
+                    String::from_utf8_lossy(&entry.key)
+                );
+                chain.push(&entry.key);
+                len += 1;
+                checksum = checksum.wrapping_add(entry_hash(&entry.key, &entry.value));
+                content_bytes += entry.key.len() + entry.value.len();
+                current = entry.next.as_deref();
+            }
+        }
+
+        assert_eq!(
+            len, self.len,
+            "strict-debug: reachable entries do not match the recorded length"
+        );
+        assert_eq!(
+            checksum, self.checksum,
+            "strict-debug: content hash out of sync"
+        );
+        assert_eq!(
+            content_bytes, self.content_bytes,
+            "strict-debug: content byte count out of sync"
+        );
+    }
+}
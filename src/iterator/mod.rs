@@ -2,6 +2,13 @@ use super::*;
 /// An iterator over the key-value pairs of a `ByteBox`.
 ///
 /// This struct is created by the [`ByteBox::iter`] method.
+///
+/// The iterator borrows the table, so the borrow checker rules out structural mutation
+/// while it is alive; there is no runtime check to pay for on each step.
+///
+/// Cloning the iterator is cheap and resumes from the same position, so a scan can be
+/// replayed without walking the leading empty cells again.
+#[derive(Clone)]
 pub struct ByteBoxIterator<'a> {
     pub(crate) byte_box: &'a ByteBox,
     pub(crate) index: usize,
@@ -50,3 +57,173 @@ impl<'a> Iterator for ByteBoxIterator<'a> {
         None
     }
 }
+
+/// A lazy iterator that removes and yields the entries of a `ByteBox` matching a predicate.
+///
+/// This struct is created by the [`ByteBox::extract_if`] method. Entries are only
+/// examined as the iterator advances: dropping it early leaves every entry that was
+/// not visited yet untouched in the table.
+pub struct ExtractIf<'a, F>
+where
+    F: FnMut(&[u8], &[u8]) -> bool,
+{
+    pub(crate) byte_box: &'a mut ByteBox,
+    pub(crate) index: usize,
+    pub(crate) pending: Link,
+    /// The visited entries of that chain that stay, most recently visited first.
+    pub(crate) kept: Link,
+    pub(crate) pred: F,
+}
+
+impl<'a, F> ExtractIf<'a, F>
+where
+    F: FnMut(&[u8], &[u8]) -> bool,
+{
+    /// Puts the kept and the unvisited entries of the current chain back into their cell,
+    /// in their original order.
+    fn restore_chain(&mut self) {
+        let mut chain = self.pending.take();
+        while let Some(mut entry) = self.kept.take() {
+            let relinked = Arc::make_mut(&mut entry);
+            self.kept = relinked.next.take();
+            relinked.next = chain;
+            chain = Some(entry);
+        }
+        if chain.is_some() {
+            self.byte_box.cells[self.index - 1] = chain;
+        }
+    }
+}
+
+impl<'a, F> Iterator for ExtractIf<'a, F>
+where
+    F: FnMut(&[u8], &[u8]) -> bool,
+{
+    type Item = (Vec<u8>, Vec<u8>);
+
+    /// Advances to the next matching entry, removes it from the table and returns it.
+    ///
+    /// # Returns
+    ///
+    /// * `Some((Vec<u8>, Vec<u8>))` containing the owned key and value of the removed entry.
+    /// * `None` once every cell has been visited.
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            // `pending` holds the not yet visited part of the chain of cell `index - 1`.
+            while let Some(mut entry) = self.pending.take() {
+                self.pending = Entry::take_next(&mut entry);
+                if (self.pred)(&entry.key, &entry.value) {
+                    self.byte_box.len -= 1;
+                    self.byte_box.checksum = self
+                        .byte_box
+                        .checksum
+                        .wrapping_sub(entry_hash(&entry.key, &entry.value));
+                    self.byte_box.content_bytes -= entry.key.len() + entry.value.len();
+                    self.byte_box.generation = self.byte_box.generation.wrapping_add(1);
+                    self.byte_box.check_watermarks();
+                    let entry = Entry::into_owned(entry);
+                    return Some((entry.key, entry.value));
+                }
+                Arc::make_mut(&mut entry).next = self.kept.take();
+                self.kept = Some(entry);
+            }
+            self.restore_chain();
+
+            if self.index >= self.byte_box.cells.len() {
+                return None;
+            }
+            self.pending = self.byte_box.cells[self.index].take();
+            self.index += 1;
+        }
+    }
+}
+
+impl<'a, F> Drop for ExtractIf<'a, F>
+where
+    F: FnMut(&[u8], &[u8]) -> bool,
+{
+    /// Puts the current chain back into its cell.
+    fn drop(&mut self) {
+        self.restore_chain();
+        // Not checked per removal: entries of the current chain are out of the table.
+        self.byte_box.debug_check_invariants();
+    }
+}
+
+/// An iterator over batches of key-value pairs of a `ByteBox`.
+///
+/// This struct is created by the [`ByteBox::iter_chunks`] method.
+pub struct Chunks<'a> {
+    pub(crate) inner: ByteBoxIterator<'a>,
+    pub(crate) size: usize,
+}
+
+impl<'a> Iterator for Chunks<'a> {
+    type Item = Vec<(&'a [u8], &'a [u8])>;
+
+    /// Returns the next batch, or `None` once every entry has been yielded.
+    fn next(&mut self) -> Option<Self::Item> {
+        let chunk: Vec<_> = self.inner.by_ref().take(self.size).collect();
+        if chunk.is_empty() {
+            None
+        } else {
+            Some(chunk)
+        }
+    }
+}
+
+/// An iterator over the key-value pairs of a `ByteBox` driven by a snapshot of the
+/// occupied cell indices.
+///
+/// This struct is created by the [`ByteBox::iter_stable`] method. Since the visiting
+/// order is fixed up front, it can also be walked from the back with `rev()`.
+#[derive(Clone)]
+pub struct StableIter<'a> {
+    pub(crate) byte_box: &'a ByteBox,
+    pub(crate) cells: std::vec::IntoIter<usize>,
+    pub(crate) entry: Option<&'a Entry>,
+    /// The not yet yielded part of the chain taken from the back, in chain order.
+    pub(crate) back: VecDeque<&'a Entry>,
+}
+
+impl<'a> Iterator for StableIter<'a> {
+    type Item = (&'a [u8], &'a [u8]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.entry.is_none() {
+            match self.cells.next() {
+                Some(index) => self.entry = self.byte_box.cells[index].as_deref(),
+                // Only the chain taken by the back end is left.
+                None => {
+                    let entry = self.back.pop_front()?;
+                    return Some((&entry.key[..], &entry.value[..]));
+                }
+            }
+        }
+        let entry = self.entry?;
+        self.entry = entry.next.as_deref();
+        Some((&entry.key[..], &entry.value[..]))
+    }
+}
+
+impl<'a> DoubleEndedIterator for StableIter<'a> {
+    /// Returns the last key-value pair not yielded yet.
+    ///
+    /// Chains are singly linked, so the chain of the last remaining cell is buffered
+    /// before its entries are yielded in reverse.
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.back.is_empty() {
+            let mut current = match self.cells.next_back() {
+                Some(index) => self.byte_box.cells[index].as_deref(),
+                // Only the chain taken by the front end is left.
+                None => self.entry.take(),
+            };
+            while let Some(entry) = current {
+                self.back.push_back(entry);
+                current = entry.next.as_deref();
+            }
+        }
+        let entry = self.back.pop_back()?;
+        Some((&entry.key[..], &entry.value[..]))
+    }
+}
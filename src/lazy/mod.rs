@@ -0,0 +1,129 @@
+//! A `ByteBox` wrapper whose values can be rendered on first read.
+use super::*;
+
+use std::collections::HashMap;
+
+type Thunk = Box<dyn FnOnce() -> Vec<u8>>;
+
+/// A table whose values may be given as closures that run only when the value is read.
+///
+/// [`LazyByteBox::insert_lazy`] stores a closure instead of bytes. The first
+/// [`LazyByteBox::get`] for that key runs it and caches the result in the inner `ByteBox`,
+/// so later reads are plain lookups. Values that are never read are never rendered.
+///
+/// # Examples
+///
+/// ```rust
+/// use bytesbox::lazy::LazyByteBox;
+///
+/// let mut config = LazyByteBox::new();
+/// config.insert(b"name", b"server-1");
+/// config.insert_lazy(b"banner", || b"rendered on demand".to_vec());
+///
+/// assert!(!config.is_materialized(b"banner"));
+/// assert_eq!(config.get(b"banner"), Some(&b"rendered on demand"[..]));
+/// assert!(config.is_materialized(b"banner"));
+/// ```
+#[derive(Default)]
+pub struct LazyByteBox {
+    table: ByteBox,
+    pending: HashMap<Vec<u8>, Thunk>,
+}
+
+impl fmt::Debug for LazyByteBox {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LazyByteBox")
+            .field("materialized", &self.table.len())
+            .field("pending", &self.pending.len())
+            .finish()
+    }
+}
+
+impl LazyByteBox {
+    /// Creates a new, empty `LazyByteBox`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of keys stored, rendered or not.
+    pub fn len(&self) -> usize {
+        self.table.len() + self.pending.len()
+    }
+
+    /// Returns `true` if no key is stored.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Inserts a key with a ready value, discarding any closure pending for the key.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if the key was not present.
+    /// * `false` if an existing value or pending closure was replaced.
+    pub fn insert(&mut self, key: &[u8], value: &[u8]) -> bool {
+        let was_pending = self.pending.remove(key).is_some();
+        self.table.insert(key, value) && !was_pending
+    }
+
+    /// Inserts a key whose value is produced by `render` on the first read.
+    ///
+    /// Any value already stored for the key is dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key to insert.
+    /// * `render` - Builds the value; called at most once.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if the key was not present.
+    /// * `false` if an existing value or pending closure was replaced.
+    pub fn insert_lazy<F>(&mut self, key: &[u8], render: F) -> bool
+    where
+        F: FnOnce() -> Vec<u8> + 'static,
+    {
+        let was_stored = self.table.remove(key).is_some();
+        let was_pending = self
+            .pending
+            .insert(key.to_vec(), Box::new(render))
+            .is_some();
+        !was_stored && !was_pending
+    }
+
+    /// Returns the value of `key`, rendering and caching it first if it is still pending.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(&[u8])` containing the value if the key exists.
+    /// * `None` if the key does not exist.
+    pub fn get(&mut self, key: &[u8]) -> Option<&[u8]> {
+        if let Some(render) = self.pending.remove(key) {
+            self.table.insert(key, &render());
+        }
+        self.table.get(key)
+    }
+
+    /// Returns `true` if `key` holds a value that has already been rendered or was inserted
+    /// ready; `false` if it is pending or missing.
+    pub fn is_materialized(&self, key: &[u8]) -> bool {
+        self.table.contains_key(key)
+    }
+
+    /// Removes `key` without rendering a pending value.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the key was present.
+    pub fn remove(&mut self, key: &[u8]) -> bool {
+        self.pending.remove(key).is_some() || self.table.remove(key).is_some()
+    }
+
+    /// Renders every pending value and returns the underlying table.
+    pub fn into_byte_box(mut self) -> ByteBox {
+        for (key, render) in self.pending.drain() {
+            self.table.insert(&key, &render());
+        }
+        self.table
+    }
+}
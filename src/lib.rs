@@ -37,27 +37,238 @@
 //!
 //! ## Safety Considerations
 //!
-//!The `remove` method uses `unsafe` code to manipulate pointers for efficient removal of entries. Care has been taken to ensure this is safe, but users should be aware of the risks associated with `unsafe` blocks.
+//! Chain edits such as `remove`, `retain` and `remove_prefix` go through an internal cursor that unlinks entries in place without `unsafe` code. The optional `ffi` bindings are the only place where callers must uphold safety contracts.
+pub mod analysis;
+#[cfg(feature = "ops-budget")]
+pub mod budget;
+pub mod collation;
+pub mod cow;
+mod cursor;
+pub mod debug;
+pub mod decode;
+pub mod dedup;
+pub mod defragment;
+pub mod delimited;
+pub mod disk;
+pub mod error;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod frame;
+pub mod frozen;
+pub mod handle;
+pub mod hashers;
+pub mod headers;
+mod invariants;
 pub mod iterator;
+pub mod lazy;
+pub mod maintenance;
+pub mod map;
+pub mod migrate;
+pub mod miss;
+pub mod numeric;
+pub mod outcome;
+pub mod packed;
+#[cfg(feature = "rayon")]
+pub mod parallel;
+pub mod pattern;
+pub mod policy;
+pub mod pool;
+pub mod prefault;
+pub mod prefix;
 pub mod primitives;
+#[cfg(feature = "profiling")]
+pub mod profiling;
+#[cfg(feature = "python")]
+pub mod python;
+pub mod raw;
+pub mod record;
+pub mod router;
+pub mod scoped;
+pub mod search;
+pub mod segmented;
+#[cfg(feature = "serde")]
+pub mod serialization;
+pub mod sharded;
+#[cfg(feature = "bytes")]
+pub mod shared;
+mod sip;
+pub mod snapshot;
+pub mod static_table;
+pub mod stats;
+pub mod strbox;
+pub mod tagged;
+#[cfg(feature = "testdata")]
+pub mod testdata;
+pub mod tombstone;
+pub mod tree;
+pub mod utf8;
+pub mod view;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+pub mod watermark;
+#[cfg(feature = "workload")]
+pub mod workload;
 
+use analysis::*;
+use cursor::*;
+use debug::*;
+use decode::*;
+use error::*;
 use iterator::*;
+use maintenance::*;
+use miss::*;
+use outcome::*;
+use policy::*;
+use pool::*;
 use primitives::*;
+use raw::*;
+use sip::*;
+use stats::*;
+use tombstone::*;
+use watermark::*;
 
 #[cfg(feature = "color")]
 use bytescolor::ByteColor;
 
 use std::collections::hash_map::DefaultHasher;
+use std::collections::VecDeque;
 use std::fmt::{self, Display};
 use std::hash::{Hash, Hasher};
+use std::ops::{Bound, RangeBounds};
+use std::sync::Arc;
+use std::time::Instant;
 
-/// Represents a key-value pair within the `ByteBox` hash table.
+/// Largest capacity accepted by [`ByteBox::try_prealloc`], 2^28 cells (2 GiB of cells on
+/// 64-bit targets).
+pub const DEFAULT_MAX_PREALLOC: usize = 1 << 28;
+
+/// Number of cells allocated by the first insert into a table created without cells.
+const INITIAL_ALLOCATION: usize = 16;
+
+/// Returns the memory the system reports as available, in bytes, when it is known.
+fn available_memory() -> Option<usize> {
+    #[cfg(target_os = "linux")]
+    {
+        let meminfo = std::fs::read_to_string("/proc/meminfo").ok()?;
+        let line = meminfo
+            .lines()
+            .find(|line| line.starts_with("MemAvailable:"))?;
+        let kib: usize = line.split_whitespace().nth(1)?.parse().ok()?;
+        Some(kib.saturating_mul(1024))
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        None
+    }
+}
+
+/// Length of the value chunks hashed separately by [`entry_hash`].
+const ENTRY_HASH_CHUNK: usize = 64;
+
+/// Hashes one entry for the content hash; independent of the table's seed and layout.
+///
+/// The hash is the wrapping sum of a head and one term per value chunk, all SipHash-1-3
+/// with lengths as little-endian `u64`s. The head is keyed with zeros and covers the key
+/// length, the key and the value length. Chunk `i` holds the value bytes from
+/// `i * ENTRY_HASH_CHUNK` on, at most `ENTRY_HASH_CHUNK` of them, and is keyed with the
+/// zero-keyed hash of the key length and key, and with `i`. Hashing chunks separately
+/// lets an append rehash only the value's last chunk; see [`entry_hash_append_delta`].
+fn entry_hash(key: &[u8], value: &[u8]) -> u64 {
+    let mut hasher = SipHasher13::new_with_keys(0, 0);
+    hasher.write_len(key.len());
+    hasher.write(key);
+    let chunk_key = hasher.finish();
+    hasher.write_len(value.len());
+    hasher
+        .finish()
+        .wrapping_add(chunk_hashes(chunk_key, value, 0))
+}
+
+/// Returns how much [`entry_hash`] changes when bytes are appended to a value that was
+/// `old_len` bytes long and is now `value`.
+///
+/// Only the key, the old value's last chunk and the appended bytes are hashed.
+fn entry_hash_append_delta(key: &[u8], value: &[u8], old_len: usize) -> u64 {
+    let mut hasher = SipHasher13::new_with_keys(0, 0);
+    hasher.write_len(key.len());
+    hasher.write(key);
+    let chunk_key = hasher.finish();
+    let mut old_head = hasher.clone();
+    old_head.write_len(old_len);
+    hasher.write_len(value.len());
+
+    // Chunks before the one `old_len` falls in are unchanged.
+    let first = old_len / ENTRY_HASH_CHUNK;
+    let before = old_head
+        .finish()
+        .wrapping_add(chunk_hashes(chunk_key, &value[..old_len], first));
+    let after = hasher
+        .finish()
+        .wrapping_add(chunk_hashes(chunk_key, value, first));
+    after.wrapping_sub(before)
+}
+
+/// Sums the hashes of the chunks of `value` from chunk `first` on.
+fn chunk_hashes(chunk_key: u64, value: &[u8], first: usize) -> u64 {
+    value[first * ENTRY_HASH_CHUNK..]
+        .chunks(ENTRY_HASH_CHUNK)
+        .zip(first..)
+        .fold(0, |sum, (chunk, index)| {
+            let mut hasher = SipHasher13::new_with_keys(chunk_key, index as u64);
+            hasher.write(chunk);
+            sum.wrapping_add(hasher.finish())
+        })
+}
+
+/// Represents a key-value pair within the `ByteBox` hash table, or a [`map::ByteMap`] when the
+/// value is not a byte vector.
 /// Each `Entry` may point to the next entry in case of hash collisions.
 #[derive(Debug, Clone)]
-struct Entry {
+struct Entry<V = Vec<u8>> {
     key: Vec<u8>,
-    value: Vec<u8>,
-    next: Option<Box<Entry>>,
+    value: V,
+    next: Link<V>,
+}
+
+/// A link of a collision chain.
+///
+/// Entries are reference-counted so that [`ByteBox::clone_cheap`] can share them between
+/// tables. Every write reaches its entry through `Arc::make_mut`, which copies the entry
+/// first if another table still refers to it.
+type Link<V = Vec<u8>> = Option<Arc<Entry<V>>>;
+
+impl<V> Entry<V> {
+    /// Returns the entry of a table that never shares its entries.
+    fn unique(node: &mut Arc<Self>) -> &mut Self {
+        Arc::get_mut(node).expect("entry shared by a table that never shares entries")
+    }
+
+    /// Unlinks `node` from its successor and returns the successor. A shared entry keeps
+    /// its link, which the other table still relies on.
+    fn take_next(node: &mut Arc<Self>) -> Link<V> {
+        match Arc::get_mut(node) {
+            Some(entry) => entry.next.take(),
+            None => node.next.clone(),
+        }
+    }
+}
+
+impl<V: Clone> Entry<V> {
+    /// Takes an unlinked entry out of its `Arc`, copying it if it is still shared.
+    fn into_owned(node: Arc<Self>) -> Self {
+        Arc::try_unwrap(node).unwrap_or_else(|shared| Entry {
+            key: shared.key.clone(),
+            value: shared.value.clone(),
+            next: None,
+        })
+    }
+
+    /// Gives the chain starting at `link` entries of its own, copying every shared one.
+    fn unshare_chain(mut link: &mut Link<V>) {
+        while let Some(node) = link {
+            link = &mut Arc::make_mut(node).next;
+        }
+    }
 }
 
 /// A hash table implementation that stores key-value pairs as byte vectors.
@@ -75,18 +286,43 @@ struct Entry {
 /// assert_eq!(bytebox.get(b"key1"), Some(&b"value1"[..]));
 /// assert_eq!(bytebox.len(), 2);
 /// ```
-#[derive(Clone, Debug)]
 pub struct ByteBox {
-    cells: Vec<Option<Box<Entry>>>,
+    cells: Vec<Link>,
     alloc: usize,
     len: usize,
     load_factor_threshold: f32,
+    generation: u64,
+    seed: u64,
+    checksum: u64,
+    content_bytes: usize,
+    pool: EntryPool,
+    tombstones: TombstoneLog,
+    decoder: Option<ReadDecoder>,
+    miss_handler: Option<MissHandler>,
+    migration_cursor: usize,
+    shrink: Option<Shrink>,
+    watermarks: Vec<Watermark>,
+    #[cfg(feature = "profiling")]
+    profiler: profiling::Profiler,
+    #[cfg(feature = "ops-budget")]
+    op_counters: budget::OpCounters,
+}
+
+impl Clone for ByteBox {
+    /// Returns a deep copy owning every entry; [`ByteBox::clone_cheap`] shares them instead.
+    fn clone(&self) -> Self {
+        let mut copy = self.clone_cheap();
+        copy.cells.iter_mut().for_each(Entry::unshare_chain);
+        copy
+    }
 }
 
 impl Display for ByteBox {
     /// Formats the `ByteBox` for display purposes.
     ///
-    /// This implementation displays the contents in a readable key-value format.
+    /// Entries are shown in a readable key-value format, sorted by key so that equal tables
+    /// always print the same way. A precision (`{:.N}`) truncates every value to its first
+    /// `N` bytes, followed by an ellipsis and the full length of the value.
     ///
     /// # Examples
     ///
@@ -94,36 +330,53 @@ impl Display for ByteBox {
     /// use bytesbox::ByteBox;
     ///
     /// let mut bytebox = ByteBox::new();
-    /// bytebox.insert(b"key", b"value");
-    /// println!("{}", bytebox);
+    /// bytebox.insert(b"b", b"short");
+    /// bytebox.insert(b"a", b"a rather long value");
+    /// assert_eq!(
+    ///     bytebox.to_string(),
+    ///     r#"{"a": "a rather long value", "b": "short"}"#
+    /// );
+    /// assert_eq!(
+    ///     format!("{:.6}", bytebox),
+    ///     r#"{"a": "a rath"… (19 bytes), "b": "short"}"#
+    /// );
     /// ```
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{{")?;
+        let mut entries: Vec<(&[u8], &[u8])> = self.iter().collect();
+        entries.sort_unstable_by_key(|&(key, _)| key);
 
-        let mut first = true;
-        for (_, cell) in self.cells.iter().enumerate() {
-            let mut current = cell.as_ref();
-            while let Some(entry) = current {
-                if !first {
-                    write!(f, ", ")?;
-                }
-                write!(
+        write!(f, "{{")?;
+        for (index, (key, value)) in entries.into_iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{:?}: ", String::from_utf8_lossy(key))?;
+            match f.precision() {
+                Some(limit) if value.len() > limit => write!(
                     f,
-                    "{:?}: {:?}",
-                    String::from_utf8_lossy(&entry.key),
-                    String::from_utf8_lossy(&entry.value)
-                )?;
-                current = entry.next.as_ref();
+                    "{:?}… ({} bytes)",
+                    String::from_utf8_lossy(&value[..limit]),
+                    value.len()
+                )?,
+                _ => write!(f, "{:?}", String::from_utf8_lossy(value))?,
             }
-            first = false;
         }
-
         write!(f, "}}")
     }
 }
 
+impl Default for ByteBox {
+    /// Creates an empty `ByteBox` that allocates on first insert, same as [`ByteBox::new`].
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ByteBox {
-    /// Creates a new `ByteBox` with a default initial capacity of 16 cells.
+    /// Creates a new, empty `ByteBox` without allocating.
+    ///
+    /// The cell array is allocated, with 16 cells, by the first insert, so tables that are
+    /// never populated cost no heap memory.
     ///
     /// # Examples
     ///
@@ -132,13 +385,17 @@ impl ByteBox {
     ///
     /// let bytebox = ByteBox::new();
     /// assert_eq!(bytebox.len(), 0);
+    /// assert_eq!(bytebox.allocation(), 0);
     /// ```
     pub fn new() -> Self {
-        Self::prealloc(16)
+        Self::prealloc(0)
     }
 
     /// Creates a new `ByteBox` with a specified initial capacity.
     ///
+    /// A size of zero is allowed and behaves like [`ByteBox::new`]: nothing is allocated
+    /// until the first insert.
+    ///
     /// # Arguments
     ///
     /// * `size` - The initial number of cells to allocate.
@@ -157,7 +414,266 @@ impl ByteBox {
             alloc: size,
             len: 0,
             load_factor_threshold: 0.75,
+            generation: 0,
+            seed: 0,
+            checksum: 0,
+            content_bytes: 0,
+            pool: EntryPool::new(),
+            tombstones: TombstoneLog::default(),
+            decoder: None,
+            miss_handler: None,
+            migration_cursor: 0,
+            shrink: None,
+            watermarks: Vec::new(),
+            #[cfg(feature = "profiling")]
+            profiler: profiling::Profiler::default(),
+            #[cfg(feature = "ops-budget")]
+            op_counters: budget::OpCounters::default(),
+        }
+    }
+
+    /// Creates a new `ByteBox` with a specified initial capacity, failing instead of
+    /// aborting when the capacity is unreasonable.
+    ///
+    /// Unlike [`ByteBox::prealloc`], this rejects a capacity above
+    /// [`DEFAULT_MAX_PREALLOC`] cells, checks the cell array against the memory the
+    /// system reports as available (on Linux), and reports allocation failure as an
+    /// error. Use it when the capacity comes from untrusted configuration.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - The initial number of cells to allocate.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    /// use bytesbox::error::PreallocError;
+    ///
+    /// assert_eq!(ByteBox::try_prealloc(32).unwrap().allocation(), 32);
+    /// assert!(matches!(
+    ///     ByteBox::try_prealloc(usize::MAX),
+    ///     Err(PreallocError::ExceedsLimit { .. })
+    /// ));
+    /// ```
+    pub fn try_prealloc(size: usize) -> Result<Self, PreallocError> {
+        Self::try_prealloc_within(size, DEFAULT_MAX_PREALLOC)
+    }
+
+    /// Same as [`ByteBox::try_prealloc`], with a caller-chosen maximum number of cells.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - The initial number of cells to allocate.
+    /// * `max_cells` - The largest capacity accepted.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    /// use bytesbox::error::PreallocError;
+    ///
+    /// assert_eq!(
+    ///     ByteBox::try_prealloc_within(4096, 1024).unwrap_err(),
+    ///     PreallocError::ExceedsLimit { requested: 4096, limit: 1024 }
+    /// );
+    /// ```
+    pub fn try_prealloc_within(size: usize, max_cells: usize) -> Result<Self, PreallocError> {
+        if size > max_cells {
+            return Err(PreallocError::ExceedsLimit {
+                requested: size,
+                limit: max_cells,
+            });
+        }
+        let requested_bytes = size.saturating_mul(std::mem::size_of::<Link>());
+        if available_memory().is_some_and(|available| requested_bytes > available) {
+            return Err(PreallocError::OutOfMemory { requested_bytes });
+        }
+
+        let mut cells = Vec::new();
+        cells
+            .try_reserve_exact(size)
+            .map_err(|_| PreallocError::OutOfMemory { requested_bytes })?;
+        cells.resize(size, None);
+
+        let mut byte_box = Self::prealloc(0);
+        byte_box.cells = cells;
+        byte_box.alloc = size;
+        Ok(byte_box)
+    }
+
+    /// Creates a copy of `other` allocated up front for its entries.
+    ///
+    /// The allocation is the smallest power of two (at least 16) that holds `other.len()`
+    /// entries under the load factor, so filling it never resizes. Entries are copied in
+    /// `other`'s cell order. Unlike `clone`, the copy is unseeded and sized for its
+    /// contents rather than for `other`'s allocation.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut warm = ByteBox::prealloc(4096);
+    /// for i in 0..100u32 {
+    ///     warm.insert(&i.to_le_bytes(), b"value");
+    /// }
+    ///
+    /// let copy = ByteBox::with_contents_of(&warm);
+    /// assert_eq!(copy.len(), 100);
+    /// assert_eq!(copy.allocation(), 256);
+    /// ```
+    pub fn with_contents_of(other: &ByteBox) -> ByteBox {
+        // Inserting the last entry must not see the load factor reached.
+        let cells = (other.len.saturating_sub(1) as f32 / other.load_factor_threshold) as usize + 1;
+        let allocation = cells.next_power_of_two().max(INITIAL_ALLOCATION);
+        let mut byte_box = Self::prealloc(allocation);
+        for (key, value) in other.iter() {
+            byte_box.insert(key, value);
+        }
+        byte_box
+    }
+
+    /// Returns the cell a key belongs to in a table of `allocation` cells.
+    ///
+    /// Bulk loaders use this to partition entries ahead of time (possibly in parallel)
+    /// before handing them to [`ByteBox::from_raw_parts`] or [`ByteBox::from_hashed_parts`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    ///
+    /// let index = ByteBox::cell_index(b"key", 64);
+    /// assert!(index < 64);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `allocation` is zero.
+    pub fn cell_index(key: &[u8], allocation: usize) -> usize {
+        Self::hash(key, allocation)
+    }
+
+    /// Assembles a `ByteBox` from entries already partitioned into cells.
+    ///
+    /// `cells[i]` must hold exactly the entries whose key satisfies
+    /// `ByteBox::cell_index(key, cells.len()) == i`, and keys must be unique. Nothing is
+    /// hashed or compared, which is what makes this fast; violating the contract does
+    /// not cause undefined behavior, but lookups of misplaced or duplicated keys will be
+    /// wrong. Debug builds verify the placement of every entry.
+    ///
+    /// # Arguments
+    ///
+    /// * `cells` - One vector of `(key, value)` pairs per cell; its length becomes the allocation.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    ///
+    /// let allocation = 8;
+    /// let mut cells = vec![Vec::new(); allocation];
+    /// for (key, value) in [(b"key1", b"value1"), (b"key2", b"value2")] {
+    ///     cells[ByteBox::cell_index(key, allocation)].push((key.to_vec(), value.to_vec()));
+    /// }
+    ///
+    /// let bytebox = ByteBox::from_raw_parts(cells);
+    /// assert_eq!(bytebox.len(), 2);
+    /// assert_eq!(bytebox.get(b"key2"), Some(&b"value2"[..]));
+    /// ```
+    pub fn from_raw_parts(cells: Vec<Vec<(Vec<u8>, Vec<u8>)>>) -> Self {
+        let alloc = cells.len();
+        let mut len = 0;
+        let mut checksum = 0u64;
+        let mut content_bytes = 0;
+        let cells = cells
+            .into_iter()
+            .enumerate()
+            .map(|(index, entries)| {
+                let mut head: Link = None;
+                for (key, value) in entries.into_iter().rev() {
+                    debug_assert_eq!(
+                        Self::hash(&key, alloc),
+                        index,
+                        "entry placed in the wrong cell"
+                    );
+                    len += 1;
+                    checksum = checksum.wrapping_add(entry_hash(&key, &value));
+                    content_bytes += key.len() + value.len();
+                    head = Some(Arc::new(Entry {
+                        key,
+                        value,
+                        next: head,
+                    }));
+                }
+                head
+            })
+            .collect();
+
+        ByteBox {
+            cells,
+            alloc,
+            len,
+            load_factor_threshold: 0.75,
+            generation: 0,
+            seed: 0,
+            checksum,
+            content_bytes,
+            pool: EntryPool::new(),
+            tombstones: TombstoneLog::default(),
+            decoder: None,
+            miss_handler: None,
+            migration_cursor: 0,
+            shrink: None,
+            watermarks: Vec::new(),
+            #[cfg(feature = "profiling")]
+            profiler: profiling::Profiler::default(),
+            #[cfg(feature = "ops-budget")]
+            op_counters: budget::OpCounters::default(),
+        }
+    }
+
+    /// Assembles a `ByteBox` of `allocation` cells from entries tagged with their cell index.
+    ///
+    /// Each item is `(cell, key, value)` where `cell` was computed with
+    /// [`ByteBox::cell_index`]; the same contract as [`ByteBox::from_raw_parts`] applies.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a cell index is not lower than `allocation`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    ///
+    /// let entries = vec![(ByteBox::cell_index(b"key", 16), b"key".to_vec(), b"value".to_vec())];
+    /// let bytebox = ByteBox::from_hashed_parts(16, entries);
+    /// assert_eq!(bytebox.get(b"key"), Some(&b"value"[..]));
+    /// ```
+    pub fn from_hashed_parts<I>(allocation: usize, entries: I) -> Self
+    where
+        I: IntoIterator<Item = (usize, Vec<u8>, Vec<u8>)>,
+    {
+        let mut byte_box = Self::prealloc(allocation);
+        for (cell, key, value) in entries {
+            debug_assert_eq!(
+                Self::hash(&key, allocation),
+                cell,
+                "entry placed in the wrong cell"
+            );
+            byte_box.checksum = byte_box.checksum.wrapping_add(entry_hash(&key, &value));
+            byte_box.content_bytes += key.len() + value.len();
+            let slot = &mut byte_box.cells[cell];
+            *slot = Some(Arc::new(Entry {
+                key,
+                value,
+                next: slot.take(),
+            }));
+            byte_box.len += 1;
         }
+        byte_box
     }
 
     /// Returns the number of key-value pairs stored in the `ByteBox`.
@@ -176,6 +692,22 @@ impl ByteBox {
         self.len
     }
 
+    /// Returns `true` if the `ByteBox` contains no key-value pairs.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut bytebox = ByteBox::new();
+    /// assert!(bytebox.is_empty());
+    /// bytebox.insert(b"key", b"value");
+    /// assert!(!bytebox.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
     /// Returns the current allocation size (number of cells) of the `ByteBox`.
     ///
     /// # Examples
@@ -183,7 +715,9 @@ impl ByteBox {
     /// ```rust
     /// use bytesbox::ByteBox;
     ///
-    /// let bytebox = ByteBox::new();
+    /// let mut bytebox = ByteBox::new();
+    /// assert_eq!(bytebox.allocation(), 0);
+    /// bytebox.insert(b"key", b"value");
     /// assert_eq!(bytebox.allocation(), 16);
     /// ```
     pub fn allocation(&self) -> usize {
@@ -195,6 +729,9 @@ impl ByteBox {
     /// If the key already exists, its value is updated.
     /// If the load factor exceeds the threshold after insertion, the table is resized.
     ///
+    /// Empty keys and empty values are ordinary entries: the empty key is a key like any
+    /// other, and an empty value is stored and returned as `Some(&[])`, never as `None`.
+    ///
     /// # Arguments
     ///
     /// * `key` - A byte slice representing the key.
@@ -216,69 +753,1674 @@ impl ByteBox {
     /// assert_eq!(bytebox.get(b"key1"), Some(&b"value2"[..]));
     /// ```
     pub fn insert(&mut self, key: &[u8], value: &[u8]) -> bool {
-        if (self.len as f32) / (self.alloc as f32) >= self.load_factor_threshold {
+        !matches!(
+            self.insert_report(key, value),
+            InsertOutcome::Updated { .. }
+        )
+    }
+
+    /// Inserts a key-value pair like [`ByteBox::insert`], reporting what the insert did.
+    ///
+    /// The table only grows when a new key is added, so updating an existing key never
+    /// reports [`InsertOutcome::Resized`].
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - A byte slice representing the key.
+    /// * `value` - A byte slice representing the value.
+    ///
+    /// # Returns
+    ///
+    /// * `InsertOutcome::Inserted` if a new key-value pair was inserted.
+    /// * `InsertOutcome::Updated { old_len }` if an existing key was updated.
+    /// * `InsertOutcome::Resized` if a new key-value pair was inserted and the table grew
+    ///   to make room for it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::outcome::InsertOutcome;
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut bytebox = ByteBox::prealloc(1);
+    /// assert_eq!(bytebox.insert_report(b"a", b"1"), InsertOutcome::Inserted);
+    /// assert_eq!(bytebox.insert_report(b"b", b"2"), InsertOutcome::Resized);
+    /// assert_eq!(
+    ///     bytebox.insert_report(b"a", b"one"),
+    ///     InsertOutcome::Updated { old_len: 1 }
+    /// );
+    /// ```
+    pub fn insert_report(&mut self, key: &[u8], value: &[u8]) -> InsertOutcome {
+        #[cfg(feature = "profiling")]
+        let started = std::time::Instant::now();
+        let outcome = self.store(key, value);
+        #[cfg(feature = "profiling")]
+        self.profiler.record(
+            profiling::Op::Insert,
+            matches!(outcome, InsertOutcome::Updated { .. }),
+            started,
+        );
+        outcome
+    }
+
+    /// Stores a key-value pair; the body of [`ByteBox::insert_report`].
+    fn store(&mut self, key: &[u8], value: &[u8]) -> InsertOutcome {
+        // A fresh allocation releases the old buffer's capacity; reusing it is
+        // `replace_in_place`'s job.
+        if let Some(old_len) = self.edit_value(key, |stored| {
+            std::mem::replace(stored, value.to_vec()).len()
+        }) {
+            #[cfg(feature = "ops-budget")]
+            self.op_counters.record_copy(value.len());
+            return InsertOutcome::Updated { old_len };
+        }
+        self.store_new(key, value)
+    }
+
+    /// Adds a key known to be absent, growing the table first if needed.
+    fn store_new(&mut self, key: &[u8], value: &[u8]) -> InsertOutcome {
+        // A key inserted again supersedes its soft removal.
+        self.tombstones.forget(key);
+
+        let mut outcome = InsertOutcome::Inserted;
+        if self.alloc == 0 {
+            self.rehash_to(INITIAL_ALLOCATION);
+            outcome = InsertOutcome::Resized;
+        } else if (self.len as f32) / (self.alloc as f32) >= self.load_factor_threshold {
             self.resize();
+            outcome = InsertOutcome::Resized;
         }
 
-        let idx = Self::hash(key, self.alloc);
-        let mut current = &mut self.cells[idx];
+        let idx = self.cell_of(key);
+        let next = self.cells[idx].take();
+        self.cells[idx] = Some(self.pool.take(key, value, next));
+        #[cfg(feature = "ops-budget")]
+        self.op_counters.record_copy(key.len() + value.len());
+        self.checksum = self.checksum.wrapping_add(entry_hash(key, value));
+        self.content_bytes += key.len() + value.len();
+        self.len += 1;
+        self.generation = self.generation.wrapping_add(1);
+        self.after_mutation();
 
-        while let Some(entry) = current {
-            if entry.key == key {
-                entry.value = value.to_vec();
-                return false;
+        outcome
+    }
+
+    /// Inserts a key-value pair, resolving an existing key according to `policy`.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - A byte slice representing the key.
+    /// * `value` - A byte slice representing the value.
+    /// * `policy` - What to do if the key already exists.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(true)` if a new key-value pair was inserted.
+    /// * `Ok(false)` if the key existed and was overwritten, kept or appended to.
+    /// * `Err(DuplicateKey)` if the key existed under [`DuplicatePolicy::Error`]; the
+    ///   stored value is left untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::policy::DuplicatePolicy;
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut headers = ByteBox::new();
+    /// headers.insert_with_policy(b"accept", b"text/html", DuplicatePolicy::Append(b", ")).unwrap();
+    /// headers.insert_with_policy(b"accept", b"application/json", DuplicatePolicy::Append(b", ")).unwrap();
+    /// assert_eq!(headers.get(b"accept"), Some(&b"text/html, application/json"[..]));
+    ///
+    /// assert_eq!(headers.insert_with_policy(b"accept", b"*/*", DuplicatePolicy::Keep), Ok(false));
+    /// assert!(headers.insert_with_policy(b"accept", b"*/*", DuplicatePolicy::Error).is_err());
+    /// ```
+    pub fn insert_with_policy(
+        &mut self,
+        key: &[u8],
+        value: &[u8],
+        policy: DuplicatePolicy<'_>,
+    ) -> Result<bool, DuplicateKey> {
+        let outcome = self.edit_value(key, |stored| match policy {
+            DuplicatePolicy::Overwrite => {
+                // A fresh allocation, as in `insert`; reusing the buffer is
+                // `replace_in_place`'s job.
+                *stored = value.to_vec();
+                Ok(false)
+            }
+            DuplicatePolicy::Keep => Ok(false),
+            DuplicatePolicy::Append(separator) => {
+                stored.reserve(separator.len() + value.len());
+                stored.extend_from_slice(separator);
+                stored.extend_from_slice(value);
+                Ok(false)
             }
-            current = &mut entry.next;
+            DuplicatePolicy::Error => Err(DuplicateKey { key: key.to_vec() }),
+        });
+        match outcome {
+            Some(outcome) => outcome,
+            None => Ok(self.insert(key, value)),
         }
+    }
 
-        let new_entry = Box::new(Entry {
-            key: key.to_vec(),
-            value: value.to_vec(),
-            next: self.cells[idx].take(),
-        });
-        self.cells[idx] = Some(new_entry);
-        self.len += 1;
+    /// Inserts a key and a primitive value into the `ByteBox`.
+    ///
+    /// The primitive value is converted to its byte representation using the `BytesPrimitives` trait.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `T` - A type that implements the `BytesPrimitives` trait.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - A byte slice representing the key.
+    /// * `value` - A primitive value that can be converted into bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    /// use bytesbox::primitives::BytesPrimitives;
+    ///
+    /// let mut bytebox = ByteBox::new();
+    /// bytebox.insert_primitive(b"number", 42u32);
+    /// assert_eq!(bytebox.get(b"number"), Some(&b"42"[..]));
+    /// ```
+    pub fn insert_primitive<T: BytesPrimitives>(&mut self, key: &[u8], value: T) {
+        self.insert(key, &value.to_bytes());
+    }
+
+    /// Retrieves the value associated with the given key.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - A byte slice representing the key to look up.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(&[u8])` containing the value if the key exists; empty if an empty value was
+    ///   stored.
+    /// * `None` if the key does not exist in the `ByteBox`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut bytebox = ByteBox::new();
+    /// bytebox.insert(b"key", b"value");
+    /// bytebox.insert(b"empty", b"");
+    /// assert_eq!(bytebox.get(b"key"), Some(&b"value"[..]));
+    /// assert_eq!(bytebox.get(b"empty"), Some(&b""[..]));
+    /// assert_eq!(bytebox.get(b"nonexistent"), None);
+    /// ```
+    pub fn get(&self, key: &[u8]) -> Option<&[u8]> {
+        #[cfg(feature = "profiling")]
+        let started = std::time::Instant::now();
+        let value = self.lookup(key);
+        #[cfg(feature = "profiling")]
+        self.profiler
+            .record(profiling::Op::Get, value.is_some(), started);
+        if value.is_none() {
+            if let Some(handler) = &self.miss_handler {
+                handler(key);
+            }
+        }
+        value
+    }
+
+    /// Finds the value stored under `key`; the body of [`ByteBox::get`].
+    fn lookup(&self, key: &[u8]) -> Option<&[u8]> {
+        if self.alloc == 0 {
+            return None;
+        }
+        let idx = self.cell_of(key);
+        let mut current = self.cells[idx].as_ref();
+        #[cfg(feature = "ops-budget")]
+        let mut probes = 0;
+
+        let found = loop {
+            let Some(entry) = current else {
+                break None;
+            };
+            #[cfg(feature = "ops-budget")]
+            {
+                probes += 1;
+            }
+            if entry.key == key {
+                break Some(entry.value.as_slice());
+            }
+            current = entry.next.as_ref();
+        };
+        #[cfg(feature = "ops-budget")]
+        self.op_counters.record_lookup(probes);
+
+        found
+    }
+
+    /// Returns `true` if the table holds an entry for `key`, whatever its value.
+    ///
+    /// Unlike testing the value for emptiness, this tells a key stored with an empty value
+    /// apart from a missing key.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut headers = ByteBox::new();
+    /// headers.insert(b"x-empty", b"");
+    /// assert!(headers.contains_key(b"x-empty"));
+    /// assert!(!headers.contains_key(b"x-missing"));
+    /// ```
+    pub fn contains_key(&self, key: &[u8]) -> bool {
+        self.lookup(key).is_some()
+    }
+
+    /// Returns `true` if the table holds an entry for the empty key.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut bytebox = ByteBox::new();
+    /// assert!(!bytebox.contains_empty_key());
+    /// bytebox.insert(b"", b"root");
+    /// assert!(bytebox.contains_empty_key());
+    /// assert_eq!(bytebox.get(b""), Some(&b"root"[..]));
+    /// ```
+    pub fn contains_empty_key(&self) -> bool {
+        self.contains_key(&[])
+    }
+
+    /// Looks up several keys at once.
+    ///
+    /// The keys are hashed first and visited in cell order, so neighbouring cells are read
+    /// together instead of jumping around the cell array once per key.
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - The keys to look up; duplicates are allowed.
+    ///
+    /// # Returns
+    ///
+    /// One result per key, in the order of `keys`, as [`ByteBox::get`] would return it.
+    /// As with `get`, every missing key is passed to the miss handler, in the order of
+    /// `keys`, after all lookups are done.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut bytebox = ByteBox::new();
+    /// bytebox.insert(b"a", b"1");
+    /// bytebox.insert(b"b", b"2");
+    ///
+    /// assert_eq!(
+    ///     bytebox.get_many(&[b"b", b"missing", b"a"]),
+    ///     vec![Some(&b"2"[..]), None, Some(&b"1"[..])]
+    /// );
+    /// ```
+    pub fn get_many(&self, keys: &[&[u8]]) -> Vec<Option<&[u8]>> {
+        #[cfg(feature = "profiling")]
+        let started = std::time::Instant::now();
+        let mut found = vec![None; keys.len()];
+        if self.alloc != 0 {
+            let mut order: Vec<(usize, usize)> = keys
+                .iter()
+                .enumerate()
+                .map(|(position, key)| (self.cell_of(key), position))
+                .collect();
+            order.sort_unstable();
+
+            for (cell, position) in order {
+                let mut current = self.cells[cell].as_deref();
+                while let Some(entry) = current {
+                    if entry.key == keys[position] {
+                        found[position] = Some(entry.value.as_slice());
+                        break;
+                    }
+                    current = entry.next.as_deref();
+                }
+            }
+        }
+
+        #[cfg(feature = "profiling")]
+        self.profiler.record_batch(
+            profiling::Op::Get,
+            found.iter().map(Option::is_some),
+            started,
+        );
+        if let Some(handler) = &self.miss_handler {
+            for (key, value) in keys.iter().zip(&found) {
+                if value.is_none() {
+                    handler(key);
+                }
+            }
+        }
+        found
+    }
+
+    /// Copies the value associated with the given key into a caller-provided buffer.
+    ///
+    /// At most `buf.len()` bytes are copied. The full length of the value is returned, so a
+    /// result larger than `buf.len()` tells the caller the copy was truncated.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - A byte slice representing the key to look up.
+    /// * `buf` - The buffer receiving the value.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(usize)` containing the length of the stored value.
+    /// * `None` if the key does not exist; `buf` is left untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut bytebox = ByteBox::new();
+    /// bytebox.insert(b"key", b"value");
+    ///
+    /// let mut buf = [0u8; 16];
+    /// let len = bytebox.get_into(b"key", &mut buf).unwrap();
+    /// assert_eq!(&buf[..len], b"value");
+    /// ```
+    pub fn get_into(&self, key: &[u8], buf: &mut [u8]) -> Option<usize> {
+        let value = self.get(key)?;
+        let copied = value.len().min(buf.len());
+        buf[..copied].copy_from_slice(&value[..copied]);
+        Some(value.len())
+    }
+
+    /// Replaces the contents of `buf` with the value associated with the given key.
+    ///
+    /// The vector's existing capacity is reused, so a buffer kept across calls stops
+    /// allocating once it has grown to the largest value read.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(usize)` containing the length of the value now held by `buf`.
+    /// * `None` if the key does not exist; `buf` is left untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut bytebox = ByteBox::new();
+    /// bytebox.insert(b"key", b"value");
+    ///
+    /// let mut buf = Vec::with_capacity(64);
+    /// assert_eq!(bytebox.get_to_vec(b"key", &mut buf), Some(5));
+    /// assert_eq!(buf, b"value");
+    /// ```
+    pub fn get_to_vec(&self, key: &[u8], buf: &mut Vec<u8>) -> Option<usize> {
+        let value = self.get(key)?;
+        buf.clear();
+        buf.extend_from_slice(value);
+        Some(value.len())
+    }
+
+    /// Returns a sub-slice of the value associated with the given key, without copying.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - A byte slice representing the key to look up.
+    /// * `range` - The byte range of the value to return, e.g. `0..16` or `4..`.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(&[u8])` containing the requested part of the value.
+    /// * `None` if the key does not exist or the range does not fit in the value.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut bytebox = ByteBox::new();
+    /// bytebox.insert(b"frame", b"HEADERpayload");
+    /// assert_eq!(bytebox.get_range(b"frame", 0..6), Some(&b"HEADER"[..]));
+    /// assert_eq!(bytebox.get_range(b"frame", 6..), Some(&b"payload"[..]));
+    /// assert_eq!(bytebox.get_range(b"frame", 10..20), None);
+    /// ```
+    pub fn get_range<R: RangeBounds<usize>>(&self, key: &[u8], range: R) -> Option<&[u8]> {
+        self.try_get_range(key, range).ok()
+    }
+
+    /// Returns a sub-slice of the value associated with the given key, explaining failures.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(&[u8])` containing the requested part of the value.
+    /// * `Err(RangeError::MissingKey)` if the key does not exist.
+    /// * `Err(RangeError::OutOfBounds { .. })` if the range does not fit in the value.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::error::RangeError;
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut bytebox = ByteBox::new();
+    /// bytebox.insert(b"frame", b"HEADER");
+    /// assert_eq!(
+    ///     bytebox.try_get_range(b"frame", 4..8),
+    ///     Err(RangeError::OutOfBounds { start: 4, end: 8, len: 6 })
+    /// );
+    /// assert_eq!(bytebox.try_get_range(b"other", ..), Err(RangeError::MissingKey));
+    /// ```
+    pub fn try_get_range<R: RangeBounds<usize>>(
+        &self,
+        key: &[u8],
+        range: R,
+    ) -> Result<&[u8], RangeError> {
+        let value = self.get(key).ok_or(RangeError::MissingKey)?;
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start.saturating_add(1),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end.saturating_add(1),
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => value.len(),
+        };
+        value.get(start..end).ok_or(RangeError::OutOfBounds {
+            start,
+            end,
+            len: value.len(),
+        })
+    }
+
+    /// Exchanges the values of two existing keys without copying the byte buffers.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_a` - The first key.
+    /// * `key_b` - The second key.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if both keys exist and their values were exchanged.
+    /// * `Err(MissingKey)` naming the first missing key; the table is left untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut bytebox = ByteBox::new();
+    /// bytebox.insert(b"active", b"config-v1");
+    /// bytebox.insert(b"staged", b"config-v2");
+    ///
+    /// bytebox.swap(b"active", b"staged").unwrap();
+    /// assert_eq!(bytebox.get(b"active"), Some(&b"config-v2"[..]));
+    /// assert_eq!(bytebox.get(b"staged"), Some(&b"config-v1"[..]));
+    /// assert!(bytebox.swap(b"active", b"missing").is_err());
+    /// ```
+    pub fn swap(&mut self, key_a: &[u8], key_b: &[u8]) -> Result<(), MissingKey> {
+        for key in [key_a, key_b] {
+            if self.lookup(key).is_none() {
+                return Err(MissingKey { key: key.to_vec() });
+            }
+        }
+        if key_a == key_b {
+            return Ok(());
+        }
+
+        // Both keys exist, so every lookup below succeeds; values are moved, never copied.
+        let value_a = self.edit_value(key_a, std::mem::take).unwrap_or_default();
+        let value_b = self
+            .edit_value(key_b, |value| std::mem::replace(value, value_a))
+            .unwrap_or_default();
+        self.edit_value(key_a, |value| *value = value_b);
+        Ok(())
+    }
+
+    /// Appends `bytes` to the value of `key`, or inserts `bytes` as its value if the key is
+    /// absent.
+    ///
+    /// The key's chain is walked once either way and an existing value grows in place,
+    /// which suits accumulating header values or per-key logs. Keeping
+    /// [`ByteBox::content_hash`] current only rehashes the key, the last 64 bytes of the
+    /// old value and the appended bytes, so building a value from appends costs time
+    /// linear in its final length.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - A byte slice representing the key.
+    /// * `bytes` - The bytes to append.
+    /// * `separator` - Written between the existing value and `bytes` when the key exists,
+    ///   even if its value is empty; never written for a new key.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if the key was inserted.
+    /// * `false` if `bytes` was appended to an existing value.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut headers = ByteBox::new();
+    /// assert!(headers.insert_or_append(b"set-cookie", b"id=1", Some(b"\n")));
+    /// assert!(!headers.insert_or_append(b"set-cookie", b"theme=dark", Some(b"\n")));
+    /// assert_eq!(headers.get(b"set-cookie"), Some(&b"id=1\ntheme=dark"[..]));
+    /// ```
+    pub fn insert_or_append(&mut self, key: &[u8], bytes: &[u8], separator: Option<&[u8]>) -> bool {
+        let appended = self.append_value(key, |value| {
+            if let Some(separator) = separator {
+                value.extend_from_slice(separator);
+            }
+            value.extend_from_slice(bytes);
+        });
+        if appended.is_some() {
+            return false;
+        }
+        self.store_new(key, bytes);
+        true
+    }
+
+    /// Overwrites the value of an existing key, reusing its buffer when it is large enough.
+    ///
+    /// Unlike [`ByteBox::insert`], which always allocates a fresh value, this copies
+    /// `new_value` into the existing buffer and only reallocates when `new_value` is
+    /// longer than the buffer's capacity. Fixed-size record updates therefore never
+    /// allocate once the key is in place.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key whose value is replaced.
+    /// * `new_value` - The new value.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(false)` if the value was overwritten in place.
+    /// * `Ok(true)` if the buffer had to grow, which reallocated it.
+    /// * `Err(MissingKey)` if the key does not exist; nothing is inserted.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut bytebox = ByteBox::new();
+    /// bytebox.insert(b"record", b"aaaa");
+    ///
+    /// assert_eq!(bytebox.replace_in_place(b"record", b"bbbb"), Ok(false));
+    /// assert_eq!(bytebox.replace_in_place(b"record", b"cc"), Ok(false));
+    /// assert_eq!(bytebox.replace_in_place(b"record", b"dddddddd"), Ok(true));
+    /// assert_eq!(bytebox.get(b"record"), Some(&b"dddddddd"[..]));
+    /// assert!(bytebox.replace_in_place(b"missing", b"x").is_err());
+    /// ```
+    pub fn replace_in_place(&mut self, key: &[u8], new_value: &[u8]) -> Result<bool, MissingKey> {
+        self.edit_value(key, |value| {
+            let reallocated = new_value.len() > value.capacity();
+            value.clear();
+            value.extend_from_slice(new_value);
+            reallocated
+        })
+        .ok_or_else(|| MissingKey { key: key.to_vec() })
+    }
+
+    /// Applies `edit` to the value stored under `key`, keeping the content hash in sync.
+    ///
+    /// The entry is hashed before and after the edit, so this costs time proportional to
+    /// the key and value lengths however few bytes `edit` touches.
+    ///
+    /// Returns `None`, without calling `edit`, if the key does not exist.
+    fn edit_value<R, F>(&mut self, key: &[u8], edit: F) -> Option<R>
+    where
+        F: FnOnce(&mut Vec<u8>) -> R,
+    {
+        if self.alloc == 0 {
+            return None;
+        }
+        let idx = self.cell_of(key);
+        let mut cursor = ChainCursor::new(&mut self.cells[idx]);
+        let entry = cursor.seek(|entry| entry.key == key)?;
+
+        let before = entry_hash(&entry.key, &entry.value);
+        let before_len = entry.value.len();
+        let result = edit(&mut entry.value);
+        let after = entry_hash(&entry.key, &entry.value);
+        self.checksum = self.checksum.wrapping_sub(before).wrapping_add(after);
+        self.content_bytes = self.content_bytes - before_len + entry.value.len();
+        self.after_mutation();
+        Some(result)
+    }
+
+    /// Applies `append` to the value stored under `key`, keeping the content hash in sync.
+    ///
+    /// `append` must only add bytes to the end of the value; the content hash is then
+    /// updated from the appended bytes and the old value's last chunk alone.
+    ///
+    /// Returns `None`, without calling `append`, if the key does not exist.
+    fn append_value<F>(&mut self, key: &[u8], append: F) -> Option<()>
+    where
+        F: FnOnce(&mut Vec<u8>),
+    {
+        if self.alloc == 0 {
+            return None;
+        }
+        let idx = self.cell_of(key);
+        let mut cursor = ChainCursor::new(&mut self.cells[idx]);
+        let entry = cursor.seek(|entry| entry.key == key)?;
+
+        let before_len = entry.value.len();
+        append(&mut entry.value);
+        let delta = entry_hash_append_delta(&entry.key, &entry.value, before_len);
+        self.checksum = self.checksum.wrapping_add(delta);
+        self.content_bytes += entry.value.len() - before_len;
+        self.after_mutation();
+        Some(())
+    }
+
+    /// Removes the key-value pair associated with the given key from the `ByteBox`.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - A byte slice representing the key to remove.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Vec<u8>)` containing the removed value if the key existed.
+    /// * `None` if the key was not found.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut bytebox = ByteBox::new();
+    /// bytebox.insert(b"key", b"value");
+    /// assert_eq!(bytebox.remove(b"key"), Some(b"value".to_vec()));
+    /// assert_eq!(bytebox.remove(b"key"), None);
+    /// ```
+    pub fn remove(&mut self, key: &[u8]) -> Option<Vec<u8>> {
+        #[cfg(feature = "profiling")]
+        let started = std::time::Instant::now();
+        let removed = self.remove_entry(key);
+        #[cfg(feature = "profiling")]
+        self.profiler
+            .record(profiling::Op::Remove, removed.is_some(), started);
+        removed
+    }
+
+    /// Unlinks the entry holding `key` and returns its value; the body of [`ByteBox::remove`].
+    fn remove_entry(&mut self, key: &[u8]) -> Option<Vec<u8>> {
+        if self.alloc == 0 {
+            return None;
+        }
+        let idx = self.cell_of(key);
+        let mut cursor = ChainCursor::new(&mut self.cells[idx]);
+        cursor.seek(|entry| entry.key == key)?;
+        let mut removed = cursor.remove_current()?;
+
+        self.checksum = self
+            .checksum
+            .wrapping_sub(entry_hash(&removed.key, &removed.value));
+        self.content_bytes -= removed.key.len() + removed.value.len();
+        let removed_val = match Arc::get_mut(&mut removed) {
+            Some(entry) => std::mem::take(&mut entry.value),
+            None => removed.value.clone(),
+        };
+        self.pool.recycle(removed);
+        self.len -= 1;
+        self.generation = self.generation.wrapping_add(1);
+        self.after_mutation();
+        Some(removed_val)
+    }
+
+    /// Removes several keys at once.
+    ///
+    /// The keys are hashed first and grouped by cell, and each affected chain is walked
+    /// once for all of its keys, so invalidating a large batch costs one pass over the
+    /// touched cells instead of one lookup per key. The removed values are dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - The keys to remove; missing keys and duplicates are ignored.
+    ///
+    /// # Returns
+    ///
+    /// The number of removed entries.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut bytebox = ByteBox::new();
+    /// bytebox.insert(b"a", b"1");
+    /// bytebox.insert(b"b", b"2");
+    /// bytebox.insert(b"c", b"3");
+    ///
+    /// assert_eq!(bytebox.remove_many(&[b"a", b"missing", b"c", b"a"]), 2);
+    /// assert_eq!(bytebox.len(), 1);
+    /// assert_eq!(bytebox.get(b"b"), Some(&b"2"[..]));
+    /// ```
+    pub fn remove_many(&mut self, keys: &[&[u8]]) -> usize {
+        if self.alloc == 0 || self.len == 0 {
+            return 0;
+        }
+
+        let mut order: Vec<(usize, &[u8])> =
+            keys.iter().map(|key| (self.cell_of(key), *key)).collect();
+        order.sort_unstable();
+        order.dedup();
+
+        let mut removed = 0;
+        let mut start = 0;
+        while start < order.len() {
+            let cell = order[start].0;
+            let end = start
+                + order[start..]
+                    .iter()
+                    .take_while(|(c, _)| *c == cell)
+                    .count();
+            let group = &order[start..end];
+            start = end;
+
+            let mut cursor = ChainCursor::new(&mut self.cells[cell]);
+            while let Some(entry) = cursor.current_mut() {
+                if !group.iter().any(|(_, key)| entry.key == *key) {
+                    cursor.move_next();
+                } else if let Some(entry) = cursor.remove_current() {
+                    self.checksum = self
+                        .checksum
+                        .wrapping_sub(entry_hash(&entry.key, &entry.value));
+                    self.content_bytes -= entry.key.len() + entry.value.len();
+                    self.pool.recycle(entry);
+                    removed += 1;
+                }
+            }
+        }
+        if removed > 0 {
+            self.len -= removed;
+            self.generation = self.generation.wrapping_add(1);
+            self.after_mutation();
+        }
+        removed
+    }
+
+    /// Keeps only the entries for which `keep` returns `true`, removing the others in place.
+    ///
+    /// # Arguments
+    ///
+    /// * `keep` - A closure receiving each key and a mutable reference to its value.
+    ///
+    /// # Returns
+    ///
+    /// The number of removed entries.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut bytebox = ByteBox::new();
+    /// bytebox.insert(b"a", b"1");
+    /// bytebox.insert(b"b", b"22");
+    /// bytebox.insert(b"c", b"333");
+    ///
+    /// assert_eq!(bytebox.retain(|_, value| value.len() > 1), 1);
+    /// assert_eq!(bytebox.len(), 2);
+    /// assert_eq!(bytebox.get(b"a"), None);
+    /// ```
+    pub fn retain<F>(&mut self, mut keep: F) -> usize
+    where
+        F: FnMut(&[u8], &mut Vec<u8>) -> bool,
+    {
+        let mut removed = 0;
+        for cell in self.cells.iter_mut() {
+            let mut cursor = ChainCursor::new(cell);
+            while let Some(entry) = cursor.current_mut() {
+                let before = entry_hash(&entry.key, &entry.value);
+                self.content_bytes -= entry.key.len() + entry.value.len();
+                let kept = keep(&entry.key, &mut entry.value);
+                self.checksum = self.checksum.wrapping_sub(before);
+                if kept {
+                    self.checksum = self
+                        .checksum
+                        .wrapping_add(entry_hash(&entry.key, &entry.value));
+                    self.content_bytes += entry.key.len() + entry.value.len();
+                    cursor.move_next();
+                } else if let Some(entry) = cursor.remove_current() {
+                    self.pool.recycle(entry);
+                    removed += 1;
+                }
+            }
+        }
+        if removed > 0 {
+            self.len -= removed;
+            self.generation = self.generation.wrapping_add(1);
+        }
+        self.after_mutation();
+        removed
+    }
+
+    /// Removes every entry whose key starts with `prefix`.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - The key prefix to remove; an empty prefix removes everything.
+    ///
+    /// # Returns
+    ///
+    /// The number of removed entries.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut bytebox = ByteBox::new();
+    /// bytebox.insert(b"session:1", b"a");
+    /// bytebox.insert(b"session:2", b"b");
+    /// bytebox.insert(b"config", b"c");
+    ///
+    /// assert_eq!(bytebox.remove_prefix(b"session:"), 2);
+    /// assert_eq!(bytebox.len(), 1);
+    /// ```
+    pub fn remove_prefix(&mut self, prefix: &[u8]) -> usize {
+        self.retain(|key, _| !key.starts_with(prefix))
+    }
+
+    /// Removes all key-value pairs from the `ByteBox`, resetting it to an empty state.
+    ///
+    /// This method retains the current capacity of the hash table, allowing it to be reused
+    /// without the overhead of reallocating memory. All existing entries are removed, and
+    /// the length (`len`) is set to zero.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut bytebox = ByteBox::new();
+    /// bytebox.insert(b"key1", b"value1");
+    /// bytebox.insert(b"key2", b"value2");
+    /// assert_eq!(bytebox.len(), 2);
+    ///
+    /// bytebox.clear();
+    /// assert_eq!(bytebox.len(), 0);
+    /// assert_eq!(bytebox.get(b"key1"), None);
+    /// assert_eq!(bytebox.get(b"key2"), None);
+    /// ```
+    pub fn clear(&mut self) {
+        for cell in &mut self.cells {
+            let mut current = cell.take();
+            while let Some(mut entry) = current {
+                // The rest of a shared chain stays alive for the table sharing it.
+                current = Arc::get_mut(&mut entry).and_then(|entry| entry.next.take());
+                self.pool.recycle(entry);
+            }
+        }
+        self.len = 0;
+        self.checksum = 0;
+        self.content_bytes = 0;
+        self.tombstones.clear();
+        self.generation = self.generation.wrapping_add(1);
+        self.after_mutation();
+    }
+
+    /// Doubles the current capacity of the `ByteBox` and rehashes all existing entries.
+    ///
+    /// This method is called internally when the load factor exceeds the threshold.
+    fn resize(&mut self) {
+        self.rehash_to(self.alloc * 2);
+    }
+
+    /// Moves every entry into a fresh array of `new_cap` cells.
+    fn rehash_to(&mut self, new_cap: usize) {
+        let mut new_cells: Vec<Link> = vec![None; new_cap];
+
+        for cell in self.cells.iter_mut() {
+            let mut current = cell.take();
+            while let Some(mut entry) = current {
+                let idx = Self::seeded_hash(&entry.key, self.seed, new_cap);
+                let relinked = Arc::make_mut(&mut entry);
+                current = relinked.next.take();
+                relinked.next = new_cells[idx].take();
+                new_cells[idx] = Some(entry);
+            }
+        }
+
+        self.cells = new_cells;
+        self.alloc = new_cap;
+        self.shrink = None;
+        self.generation = self.generation.wrapping_add(1);
+        #[cfg(feature = "ops-budget")]
+        self.op_counters.record_rehash(self.len);
+        self.debug_check_invariants();
+    }
+
+    /// Runs the hooks that follow every change of the entries: the invariant checks of the
+    /// `strict-debug` feature, then the watermark callbacks.
+    fn after_mutation(&mut self) {
+        self.debug_check_invariants();
+        self.check_watermarks();
+    }
+
+    /// Rebuilds the table so that keys are hashed with a new seed.
+    ///
+    /// Every entry is moved to the cell chosen by the seeded hash; the allocation is
+    /// kept. Trigger this when chains grow suspiciously long (see [`ByteBox::analyze`]),
+    /// which points at keys crafted against the current seed, or after loading a table
+    /// built with a weak one. A seed of zero restores the unseeded hash.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - The new hash seed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut bytebox = ByteBox::new();
+    /// bytebox.insert(b"key", b"value");
+    ///
+    /// bytebox.rehash_with_seed(0x5eed);
+    /// assert_eq!(bytebox.seed(), 0x5eed);
+    /// assert_eq!(bytebox.get(b"key"), Some(&b"value"[..]));
+    /// ```
+    pub fn rehash_with_seed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.rehash_to(self.alloc);
+    }
+
+    /// Returns the seed keys are currently hashed with; zero means unseeded.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Returns a hash of the table contents that does not depend on insertion order,
+    /// capacity or hash seed.
+    ///
+    /// The hash is the wrapping sum of a hash of every entry and is kept up to date by each
+    /// mutation, so calling this method is `O(1)`. Equal tables always have equal content
+    /// hashes; unequal tables collide only by chance.
+    ///
+    /// Each entry hashes to the wrapping sum of SipHash-1-3 values: one under zero keys
+    /// over the key length, key and value length, and one per 64-byte chunk of the value
+    /// (the last may be shorter) over the chunk, keyed with the zero-keyed hash of the key
+    /// length and key and with the chunk's index. Lengths and indices are little-endian
+    /// `u64`s. The algorithm and encoding are part of the crate's contract: the same contents hash the
+    /// same on every platform, Rust release and version of this crate, so content hashes
+    /// can be stored or compared between processes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut a = ByteBox::new();
+    /// a.insert(b"x", b"1");
+    /// a.insert(b"y", b"2");
+    ///
+    /// let mut b = ByteBox::prealloc(64);
+    /// b.rehash_with_seed(7);
+    /// b.insert(b"y", b"2");
+    /// b.insert(b"x", b"1");
+    /// assert_eq!(a.content_hash(), b.content_hash());
+    ///
+    /// b.insert(b"x", b"changed");
+    /// assert_ne!(a.content_hash(), b.content_hash());
+    /// ```
+    pub fn content_hash(&self) -> u64 {
+        self.checksum
+    }
+
+    /// Computes the hash index for a given key based on the current capacity.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - A byte slice representing the key to hash.
+    /// * `capacity` - The current or new capacity of the hash table.
+    ///
+    /// # Returns
+    ///
+    /// * `usize` representing the index in the cells vector.
+    fn hash(key: &[u8], capacity: usize) -> usize {
+        Self::seeded_hash(key, 0, capacity)
+    }
+
+    /// Computes the hash index for a given key under a hash seed.
+    ///
+    /// A seed of zero hashes exactly like [`ByteBox::hash`].
+    fn seeded_hash(key: &[u8], seed: u64, capacity: usize) -> usize {
+        (Self::full_hash(key, seed) as usize) % capacity
+    }
+
+    /// Returns the cell holding `key`.
+    fn cell_of(&self, key: &[u8]) -> usize {
+        self.cell_of_hash(Self::full_hash(key, self.seed))
+    }
+
+    /// Returns the cell holding the keys whose 64-bit hash is `hash`.
+    ///
+    /// While [`ByteBox::maintain`] is shrinking the table, cells it has already folded
+    /// into the first `target` cells are empty, and their keys live at `index % target`.
+    fn cell_of_hash(&self, hash: u64) -> usize {
+        let index = (hash as usize) % self.alloc;
+        match self.shrink {
+            Some(Shrink { target, cursor }) if index >= target && index < cursor => index % target,
+            _ => index,
+        }
+    }
+
+    /// Computes the 64-bit hash a key's cell index is derived from.
+    fn full_hash(key: &[u8], seed: u64) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        if seed != 0 {
+            hasher.write_u64(seed);
+        }
+        key.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Returns the 64-bit hash this table uses to place `key`.
+    ///
+    /// External indexes can keep this hash instead of the key and resolve it later with
+    /// [`ByteBox::get_by_hash_and_key`]. The hash does not depend on the allocation, so it
+    /// stays valid across resizes, but it changes with [`ByteBox::rehash_with_seed`] and is
+    /// only guaranteed to be stable within one build of the program.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut bytebox = ByteBox::new();
+    /// bytebox.insert(b"key", b"value");
+    /// let hash = bytebox.hash_of(b"key");
+    ///
+    /// for i in 0..100u32 {
+    ///     bytebox.insert(&i.to_le_bytes(), b"grow");
+    /// }
+    /// assert_eq!(bytebox.get_by_hash_and_key(hash, b"key"), Some(&b"value"[..]));
+    /// ```
+    pub fn hash_of(&self, key: &[u8]) -> u64 {
+        Self::full_hash(key, self.seed)
+    }
+
+    /// Looks up `key` in the cell selected by `hash`, skipping the hashing step of
+    /// [`ByteBox::get`].
+    ///
+    /// # Arguments
+    ///
+    /// * `hash` - The value [`ByteBox::hash_of`] returned for `key`.
+    /// * `key` - The key, still compared in full.
+    ///
+    /// # Returns
+    ///
+    /// The value stored under `key`, or `None` if the key is absent or `hash` is not its
+    /// hash.
+    pub fn get_by_hash_and_key(&self, hash: u64, key: &[u8]) -> Option<&[u8]> {
+        if self.alloc == 0 {
+            return None;
+        }
+        let mut current = self.cells[self.cell_of_hash(hash)].as_deref();
+        while let Some(entry) = current {
+            if entry.key == key {
+                return Some(entry.value.as_slice());
+            }
+            current = entry.next.as_deref();
+        }
+        None
+    }
+
+    /// Provides an iterator over the `ByteBox` that allows for iteration using `for` loops.
+    ///
+    /// This enables the use of `ByteBox` in contexts where an iterator is expected.
+    ///
+    /// # Iteration Semantics
+    ///
+    /// * Every entry is yielded exactly once.
+    /// * The order is unspecified: it follows the internal cell layout, which depends on
+    ///   the hash of the keys and on the allocation.
+    /// * The order is stable while the table is not mutated: two iterations over an
+    ///   unchanged table yield the entries in the same order. Any insertion of a new key,
+    ///   removal, clear or resize may reorder them.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut bytebox = ByteBox::new();
+    /// bytebox.insert(b"key1", b"value1");
+    /// bytebox.insert(b"key2", b"value2");
+    ///
+    /// for (key, value) in bytebox.iter() {
+    ///     println!("{:?}: {:?}", key, value);
+    /// }
+    /// ```
+    pub fn iter(&self) -> ByteBoxIterator<'_> {
+        ByteBoxIterator {
+            byte_box: self,
+            entry: None,
+            index: 0,
+        }
+    }
+
+    /// Returns an iterator over batches of up to `size` entries, in iteration order.
+    ///
+    /// Every batch but the last holds exactly `size` entries, which suits streaming a
+    /// table to a peer in bounded frames.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - The largest number of entries in a batch.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut bytebox = ByteBox::new();
+    /// for i in 0..5u8 {
+    ///     bytebox.insert(&[i], b"value");
+    /// }
+    ///
+    /// let sizes: Vec<usize> = bytebox.iter_chunks(2).map(|chunk| chunk.len()).collect();
+    /// assert_eq!(sizes, vec![2, 2, 1]);
+    /// ```
+    pub fn iter_chunks(&self, size: usize) -> Chunks<'_> {
+        assert!(size > 0, "chunk size must be non-zero");
+        Chunks {
+            inner: self.iter(),
+            size,
+        }
+    }
+
+    /// Provides an iterator that first snapshots the indices of the occupied cells.
+    ///
+    /// It follows the same [iteration semantics](ByteBox::iter) as `iter`, but skips empty
+    /// cells without scanning them, and the snapshot makes the visiting order explicit:
+    /// two consecutive calls on an unchanged table visit the entries in the same order,
+    /// which keeps paginated reads consistent.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut bytebox = ByteBox::new();
+    /// bytebox.insert(b"key1", b"value1");
+    /// bytebox.insert(b"key2", b"value2");
+    ///
+    /// let first: Vec<_> = bytebox.iter_stable().collect();
+    /// let second: Vec<_> = bytebox.iter_stable().collect();
+    /// assert_eq!(first, second);
+    ///
+    /// let mut reversed: Vec<_> = bytebox.iter_stable().rev().collect();
+    /// reversed.reverse();
+    /// assert_eq!(reversed, first);
+    /// ```
+    pub fn iter_stable(&self) -> StableIter<'_> {
+        let cells = self
+            .cells
+            .iter()
+            .enumerate()
+            .filter(|(_, cell)| cell.is_some())
+            .map(|(index, _)| index)
+            .collect::<Vec<usize>>();
+        StableIter {
+            byte_box: self,
+            cells: cells.into_iter(),
+            entry: None,
+            back: VecDeque::new(),
+        }
+    }
+
+    /// Returns the entries ordered by value length, largest first when `descending` is set.
+    ///
+    /// Entries of equal value length keep their iteration order. The entries are collected
+    /// and sorted up front, which costs `O(n log n)` time and one pointer pair per entry.
+    ///
+    /// # Arguments
+    ///
+    /// * `descending` - `true` to start with the largest values, `false` with the smallest.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut bytebox = ByteBox::new();
+    /// bytebox.insert(b"small", b"x");
+    /// bytebox.insert(b"large", &[0u8; 1024]);
+    /// bytebox.insert(b"medium", &[0u8; 64]);
+    ///
+    /// let heaviest: Vec<&[u8]> = bytebox
+    ///     .iter_by_value_size(true)
+    ///     .take(2)
+    ///     .map(|(key, _)| key)
+    ///     .collect();
+    /// assert_eq!(heaviest, vec![&b"large"[..], &b"medium"[..]]);
+    /// ```
+    pub fn iter_by_value_size(
+        &self,
+        descending: bool,
+    ) -> impl Iterator<Item = (&[u8], &[u8])> + '_ {
+        let mut entries: Vec<(&[u8], &[u8])> = self.iter().collect();
+        if descending {
+            entries.sort_by_key(|(_, value)| std::cmp::Reverse(value.len()));
+        } else {
+            entries.sort_by_key(|(_, value)| value.len());
+        }
+        entries.into_iter()
+    }
+
+    /// Returns an owned copy of every key, in iteration order.
+    ///
+    /// The snapshot does not borrow the table, so it can drive later mutations of it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut bytebox = ByteBox::new();
+    /// bytebox.insert(b"tmp:1", b"a");
+    /// bytebox.insert(b"keep", b"b");
+    ///
+    /// for key in bytebox.keys_owned_snapshot() {
+    ///     if key.starts_with(b"tmp:") {
+    ///         bytebox.remove(&key);
+    ///     }
+    /// }
+    /// assert_eq!(bytebox.len(), 1);
+    /// ```
+    pub fn keys_owned_snapshot(&self) -> Vec<Vec<u8>> {
+        self.iter().map(|(key, _)| key.to_vec()).collect()
+    }
+
+    /// Calls `f` with the table and each key present when the call started.
+    ///
+    /// Keys are snapshotted first, so `f` receives `&mut ByteBox` and may insert, update or
+    /// remove entries, including the current key. Keys inserted by `f` are not visited;
+    /// keys removed by `f` before their turn are still passed to it.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - A closure receiving the table and a key.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut bytebox = ByteBox::new();
+    /// bytebox.insert(b"a", b"1");
+    /// bytebox.insert(b"b", b"2");
+    ///
+    /// bytebox.for_each_key(|table, key| {
+    ///     let mut backup = key.to_vec();
+    ///     backup.extend_from_slice(b".bak");
+    ///     let value = table.get(key).unwrap().to_vec();
+    ///     table.insert(&backup, &value);
+    /// });
+    /// assert_eq!(bytebox.len(), 4);
+    /// assert_eq!(bytebox.get(b"a.bak"), Some(&b"1"[..]));
+    /// ```
+    pub fn for_each_key<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&mut ByteBox, &[u8]),
+    {
+        for key in self.keys_owned_snapshot() {
+            f(self, &key);
+        }
+    }
+
+    /// Creates a lazy iterator that removes and yields every entry matching `pred`.
+    ///
+    /// Entries for which `pred` returns `true` are unlinked from the table and handed out
+    /// as owned `(key, value)` pairs; the others stay in place. Entries are only removed
+    /// as the iterator advances, so dropping it early keeps the unvisited ones. The entries
+    /// that stay keep their chain positions, so their iteration order does not change.
+    ///
+    /// # Arguments
+    ///
+    /// * `pred` - A closure receiving each key and value, returning `true` to extract the entry.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut sessions = ByteBox::new();
+    /// sessions.insert(b"session:1", b"expired");
+    /// sessions.insert(b"session:2", b"active");
+    /// sessions.insert(b"session:3", b"expired");
+    ///
+    /// let mut archive = ByteBox::new();
+    /// for (key, value) in sessions.extract_if(|_, value| value == b"expired") {
+    ///     archive.insert(&key, &value);
+    /// }
+    ///
+    /// assert_eq!(sessions.len(), 1);
+    /// assert_eq!(archive.len(), 2);
+    /// ```
+    pub fn extract_if<F>(&mut self, pred: F) -> ExtractIf<'_, F>
+    where
+        F: FnMut(&[u8], &[u8]) -> bool,
+    {
+        ExtractIf {
+            byte_box: self,
+            index: 0,
+            pending: None,
+            kept: None,
+            pred,
+        }
+    }
+
+    /// Copies every entry into a flat [`RawExport`] made of offsets and concatenated bytes.
+    ///
+    /// The layout is documented on [`RawExport`] and does not depend on Rust memory
+    /// layout, so it can be handed to other languages (see the `ffi` feature).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut bytebox = ByteBox::new();
+    /// bytebox.insert(b"key", b"value");
+    ///
+    /// let raw = bytebox.export_raw();
+    /// assert_eq!(raw.offsets, vec![0, 3, 8]);
+    /// assert_eq!(raw.bytes, b"keyvalue".to_vec());
+    /// ```
+    pub fn export_raw(&self) -> RawExport {
+        let bytes = self
+            .iter()
+            .map(|(key, value)| key.len() + value.len())
+            .sum();
+        let mut raw = RawExport::with_capacity(self.len, bytes);
+        for (key, value) in self.iter() {
+            raw.push(key, value);
+        }
+        raw
+    }
+
+    /// Performs bounded housekeeping, meant to be called from an application's periodic tick.
+    ///
+    /// Two tasks run, sharing the budget:
+    ///
+    /// - Tombstones older than the retention window (see
+    ///   [`ByteBox::set_tombstone_retention`]) are dropped, oldest first.
+    /// - When the table uses less than a quarter of its load factor threshold, it is
+    ///   shrunk. The shrink is incremental: each call folds the next cells into the
+    ///   smaller allocation, and later calls resume where the previous one stopped, so a
+    ///   shrink of any size completes after enough calls. The table stays fully usable
+    ///   in between, and a growth resize started by inserts meanwhile cancels the shrink.
+    ///
+    /// Allocations are halved while they stay above the fitted size. An allocation that
+    /// cannot be halved, such as an odd `prealloc` size, is instead rehashed in one
+    /// step, when `allocation() + len()` fits in the budget.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_items` - The maximum number of tombstones, cells and entries the call may
+    ///   touch. A cell's chain is always moved whole, so a call may exceed the budget by
+    ///   the length of one chain.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut bytebox = ByteBox::prealloc(1024);
+    /// bytebox.insert(b"key", b"value");
+    ///
+    /// let report = bytebox.maintain(usize::MAX);
+    /// assert_eq!(report.shrunk_to, Some(16));
+    /// assert_eq!(bytebox.allocation(), 16);
+    /// assert_eq!(bytebox.get(b"key"), Some(&b"value"[..]));
+    /// ```
+    pub fn maintain(&mut self, max_items: usize) -> MaintenanceReport {
+        self.maintain_within(max_items, None)
+    }
+
+    /// Performs the housekeeping of [`ByteBox::maintain`], also stopping at `deadline`.
+    ///
+    /// The clock is read after every 64 cells, so the call may run past `deadline` by
+    /// the time those cells take to fold; at least one such batch is folded even when
+    /// `deadline` has already passed. Work left over is resumed by the next call.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_items` - The maximum number of tombstones, cells and entries the call may
+    ///   touch.
+    /// * `deadline` - The instant after which no further work is started.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// let mut bytebox = ByteBox::prealloc(1 << 16);
+    /// bytebox.insert(b"key", b"value");
+    ///
+    /// // Each tick spends at most about a millisecond on housekeeping.
+    /// while bytebox
+    ///     .maintain_until(4096, Instant::now() + Duration::from_millis(1))
+    ///     .deferred
+    /// {}
+    /// assert_eq!(bytebox.allocation(), 16);
+    /// assert_eq!(bytebox.get(b"key"), Some(&b"value"[..]));
+    /// ```
+    pub fn maintain_until(&mut self, max_items: usize, deadline: Instant) -> MaintenanceReport {
+        self.maintain_within(max_items, Some(deadline))
+    }
+
+    fn maintain_within(
+        &mut self,
+        max_items: usize,
+        deadline: Option<Instant>,
+    ) -> MaintenanceReport {
+        let mut report = MaintenanceReport::default();
+        let mut budget = max_items;
+        report.purged_tombstones = self.tombstones.purge_expired_within(&mut budget);
+
+        if self.shrink.is_none() {
+            let load = self.len as f32 / self.alloc.max(1) as f32;
+            if self.alloc <= 16 || load >= self.load_factor_threshold / 4.0 {
+                return report;
+            }
+            let fitted = self.fitted_allocation();
+            if fitted >= self.alloc {
+                return report;
+            }
+
+            let mut target = self.alloc;
+            while target % 2 == 0 && target / 2 >= fitted {
+                target /= 2;
+            }
+            if target < self.alloc {
+                self.shrink = Some(Shrink {
+                    target,
+                    cursor: target,
+                });
+            } else if self.alloc + self.len <= budget {
+                self.rehash_to(fitted);
+                report.shrunk_to = Some(fitted);
+                return report;
+            } else {
+                report.deferred = true;
+                return report;
+            }
+        }
+
+        if let Some(shrink) = self.shrink {
+            report.shrunk_to = self.fold_cells(shrink, budget, deadline);
+            report.deferred = report.shrunk_to.is_none();
+        }
+        report
+    }
+
+    /// Folds cells of a shrink in progress until the budget or the deadline runs out, and
+    /// completes the shrink once every cell past the target has been folded.
+    ///
+    /// # Returns
+    ///
+    /// The new allocation if the shrink completed.
+    fn fold_cells(
+        &mut self,
+        mut shrink: Shrink,
+        mut budget: usize,
+        deadline: Option<Instant>,
+    ) -> Option<usize> {
+        let mut moved = 0;
+        let mut folded = 0usize;
+        while shrink.cursor < self.alloc && budget > 0 {
+            // The first batch is always folded, so a late call still makes progress.
+            if folded > 0
+                && folded % 64 == 0
+                && deadline.is_some_and(|deadline| Instant::now() >= deadline)
+            {
+                break;
+            }
+            let home = shrink.cursor % shrink.target;
+            let mut current = self.cells[shrink.cursor].take();
+            while let Some(mut entry) = current {
+                let relinked = Arc::make_mut(&mut entry);
+                current = relinked.next.take();
+                relinked.next = self.cells[home].take();
+                self.cells[home] = Some(entry);
+                moved += 1;
+                budget = budget.saturating_sub(1);
+            }
+            shrink.cursor += 1;
+            folded += 1;
+            budget = budget.saturating_sub(1);
+        }
+
+        if moved > 0 {
+            // Folded entries changed cell or chain position.
+            self.generation = self.generation.wrapping_add(1);
+            #[cfg(feature = "ops-budget")]
+            self.op_counters.record_rehash(moved);
+        }
+        let shrunk_to = if shrink.cursor == self.alloc {
+            self.cells.truncate(shrink.target);
+            self.cells.shrink_to_fit();
+            self.alloc = shrink.target;
+            self.shrink = None;
+            Some(shrink.target)
+        } else {
+            self.shrink = Some(shrink);
+            None
+        };
+        self.debug_check_invariants();
+        shrunk_to
+    }
 
-        true
+    /// The smallest power-of-two allocation, at least 16, holding the current entries
+    /// below the load factor threshold.
+    fn fitted_allocation(&self) -> usize {
+        ((self.len as f32 / self.load_factor_threshold).ceil() as usize + 1)
+            .next_power_of_two()
+            .max(16)
     }
 
-    /// Inserts a key and a primitive value into the `ByteBox`.
+    /// Returns the length of the collision chain of every cell, in cell order.
     ///
-    /// The primitive value is converted to its byte representation using the `BytesPrimitives` trait.
+    /// Empty cells yield `0`, so the iterator always produces `allocation()` items.
+    /// This is the raw material for bucket depth histograms in external monitoring.
     ///
-    /// # Type Parameters
+    /// # Examples
     ///
-    /// * `T` - A type that implements the `BytesPrimitives` trait.
+    /// ```rust
+    /// use bytesbox::ByteBox;
     ///
-    /// # Arguments
+    /// let mut bytebox = ByteBox::prealloc(1);
+    /// bytebox.insert(b"key", b"value");
     ///
-    /// * `key` - A byte slice representing the key.
-    /// * `value` - A primitive value that can be converted into bytes.
+    /// let lengths: Vec<usize> = bytebox.chain_lengths().collect();
+    /// assert_eq!(lengths, vec![1]);
+    /// ```
+    pub fn chain_lengths(&self) -> impl Iterator<Item = usize> + '_ {
+        self.cells.iter().map(|cell| {
+            let mut depth = 0;
+            let mut current = cell.as_deref();
+            while let Some(entry) = current {
+                depth += 1;
+                current = entry.next.as_deref();
+            }
+            depth
+        })
+    }
+
+    /// Returns memory and occupancy figures for the table.
     ///
     /// # Examples
     ///
     /// ```rust
     /// use bytesbox::ByteBox;
-    /// use bytesbox::primitives::BytesPrimitives;
     ///
     /// let mut bytebox = ByteBox::new();
-    /// bytebox.insert_primitive(b"number", 42u32);
-    /// assert_eq!(bytebox.get(b"number"), Some(&b"42"[..]));
+    /// bytebox.insert(b"key", b"value");
+    ///
+    /// let stats = bytebox.stats();
+    /// assert_eq!(stats.len, 1);
+    /// assert_eq!(stats.key_bytes, 3);
+    /// assert_eq!(stats.value_bytes, 5);
     /// ```
-    pub fn insert_primitive<T: BytesPrimitives>(&mut self, key: &[u8], value: T) {
-        self.insert(key, &value.to_bytes());
+    pub fn stats(&self) -> Stats {
+        let mut stats = Stats {
+            len: self.len,
+            allocation: self.alloc,
+            ..Stats::default()
+        };
+        for depth in self.chain_lengths() {
+            if depth > 0 {
+                stats.occupied_cells += 1;
+            }
+            stats.longest_chain = stats.longest_chain.max(depth);
+        }
+        for (key, value) in self.iter() {
+            stats.key_bytes += key.len();
+            stats.value_bytes += value.len();
+        }
+        stats.stored_key_bytes = stats.key_bytes;
+        stats.stored_value_bytes = stats.value_bytes;
+        stats.shared_bytes = self.shared_bytes();
+        stats
     }
 
-    /// Retrieves the value associated with the given key.
+    /// Computes percentiles of the value sizes, in bytes, using the nearest-rank method.
+    ///
+    /// Value sizes are gathered in one pass over the table and sorted once, however many
+    /// percentiles are requested.
     ///
     /// # Arguments
     ///
-    /// * `key` - A byte slice representing the key to look up.
+    /// * `percentiles` - Percentiles to compute, each between `0.0` and `100.0`.
     ///
     /// # Returns
     ///
-    /// * `Some(&[u8])` containing the value if the key exists.
-    /// * `None` if the key does not exist in the `ByteBox`.
+    /// One size per requested percentile, in the same order. Every size is `0` for an
+    /// empty table.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a percentile is outside `0.0..=100.0` or is NaN.
     ///
     /// # Examples
     ///
@@ -286,34 +2428,22 @@ impl ByteBox {
     /// use bytesbox::ByteBox;
     ///
     /// let mut bytebox = ByteBox::new();
-    /// bytebox.insert(b"key", b"value");
-    /// assert_eq!(bytebox.get(b"key"), Some(&b"value"[..]));
-    /// assert_eq!(bytebox.get(b"nonexistent"), None);
+    /// for size in 1..=100u32 {
+    ///     bytebox.insert(&size.to_le_bytes(), &vec![0; size as usize]);
+    /// }
+    /// assert_eq!(bytebox.value_size_percentiles(&[50.0, 99.0, 100.0]), vec![50, 99, 100]);
     /// ```
-    pub fn get(&self, key: &[u8]) -> Option<&[u8]> {
-        let idx = Self::hash(key, self.alloc);
-        let mut current = self.cells[idx].as_ref();
-
-        while let Some(entry) = current {
-            if entry.key == key {
-                return Some(&entry.value.as_slice());
-            }
-            current = entry.next.as_ref();
-        }
-
-        None
+    pub fn value_size_percentiles(&self, percentiles: &[f64]) -> Vec<usize> {
+        Self::size_percentiles(self.iter().map(|(_, value)| value.len()), percentiles)
     }
 
-    /// Removes the key-value pair associated with the given key from the `ByteBox`.
-    ///
-    /// # Arguments
+    /// Computes percentiles of the key lengths, in bytes, using the nearest-rank method.
     ///
-    /// * `key` - A byte slice representing the key to remove.
+    /// Works like [`ByteBox::value_size_percentiles`], on the keys.
     ///
-    /// # Returns
+    /// # Panics
     ///
-    /// * `Some(Vec<u8>)` containing the removed value if the key existed.
-    /// * `None` if the key was not found.
+    /// Panics if a percentile is outside `0.0..=100.0` or is NaN.
     ///
     /// # Examples
     ///
@@ -321,38 +2451,42 @@ impl ByteBox {
     /// use bytesbox::ByteBox;
     ///
     /// let mut bytebox = ByteBox::new();
-    /// bytebox.insert(b"key", b"value");
-    /// assert_eq!(bytebox.remove(b"key"), Some(b"value".to_vec()));
-    /// assert_eq!(bytebox.remove(b"key"), None);
+    /// bytebox.insert(b"id", b"1");
+    /// bytebox.insert(b"ts", b"2");
+    /// bytebox.insert(b"user:name", b"alice");
+    /// assert_eq!(bytebox.key_size_percentiles(&[50.0, 100.0]), vec![2, 9]);
     /// ```
-    pub fn remove(&mut self, key: &[u8]) -> Option<Vec<u8>> {
-        let idx = Self::hash(key, self.alloc);
-        let cell = &mut self.cells[idx];
-
-        let mut prev = cell as *mut Option<Box<Entry>>;
-        let mut curr = cell.as_mut();
+    pub fn key_size_percentiles(&self, percentiles: &[f64]) -> Vec<usize> {
+        Self::size_percentiles(self.iter().map(|(key, _)| key.len()), percentiles)
+    }
 
-        while let Some(entry) = curr {
-            if entry.key == key {
-                let removed_val = entry.value.clone();
-                unsafe {
-                    *prev = entry.next.take();
-                }
-                self.len -= 1;
-                return Some(removed_val);
-            }
-            prev = &mut entry.next as *mut Option<Box<Entry>>;
-            curr = entry.next.as_mut();
+    /// Picks the nearest-rank percentiles of `sizes`; the body of the `*_percentiles` methods.
+    fn size_percentiles(sizes: impl Iterator<Item = usize>, percentiles: &[f64]) -> Vec<usize> {
+        assert!(
+            percentiles.iter().all(|p| (0.0..=100.0).contains(p)),
+            "percentiles must be between 0 and 100"
+        );
+        let mut sizes: Vec<usize> = sizes.collect();
+        if sizes.is_empty() {
+            return vec![0; percentiles.len()];
         }
-
-        None
+        sizes.sort_unstable();
+        percentiles
+            .iter()
+            .map(|p| {
+                let rank = (p / 100.0 * sizes.len() as f64).ceil() as usize;
+                sizes[rank.max(1) - 1]
+            })
+            .collect()
     }
 
-    /// Removes all key-value pairs from the `ByteBox`, resetting it to an empty state.
+    /// Returns counters describing the pool of recycled entries.
     ///
-    /// This method retains the current capacity of the hash table, allowing it to be reused
-    /// without the overhead of reallocating memory. All existing entries are removed, and
-    /// the length (`len`) is set to zero.
+    /// Entries released by [`ByteBox::remove`] and [`ByteBox::clear`] are kept in a pool
+    /// (up to [`pool::DEFAULT_POOL_LIMIT`] entries and [`pool::DEFAULT_POOL_BYTE_LIMIT`]
+    /// bytes by default) and reused by later inserts, so high-churn workloads stop going
+    /// through the allocator for every entry. Only small key and value buffers are kept;
+    /// removing or clearing large values gives their memory back immediately.
     ///
     /// # Examples
     ///
@@ -361,63 +2495,66 @@ impl ByteBox {
     ///
     /// let mut bytebox = ByteBox::new();
     /// bytebox.insert(b"key1", b"value1");
+    /// bytebox.remove(b"key1");
     /// bytebox.insert(b"key2", b"value2");
-    /// assert_eq!(bytebox.len(), 2);
     ///
-    /// bytebox.clear();
-    /// assert_eq!(bytebox.len(), 0);
-    /// assert_eq!(bytebox.get(b"key1"), None);
-    /// assert_eq!(bytebox.get(b"key2"), None);
+    /// let stats = bytebox.pool_stats();
+    /// assert_eq!(stats.recycled, 1);
+    /// assert_eq!(stats.reused, 1);
+    /// assert_eq!(stats.pooled, 0);
     /// ```
-    pub fn clear(&mut self) {
-        for cell in &mut self.cells {
-            *cell = None;
-        }
-        self.len = 0;
+    pub fn pool_stats(&self) -> PoolStats {
+        self.pool.stats()
     }
 
-    /// Doubles the current capacity of the `ByteBox` and rehashes all existing entries.
+    /// Sets the maximum number of recycled entries kept in the pool.
     ///
-    /// This method is called internally when the load factor exceeds the threshold.
-    fn resize(&mut self) {
-        let new_cap = self.alloc * 2;
-        let mut new_cells: Vec<Option<Box<Entry>>> = vec![None; new_cap];
-
-        for (_, cell) in self.cells.iter_mut().enumerate() {
-            let mut current = cell.take();
-            while let Some(mut entry) = current {
-                let idx = Self::hash(&entry.key, new_cap);
-                current = entry.next.take();
-                entry.next = new_cells[idx].take();
-                new_cells[idx] = Some(entry);
-            }
-        }
-
-        self.cells = new_cells;
-        self.alloc = new_cap;
+    /// A limit of zero disables pooling. Entries beyond the new limit are released.
+    ///
+    /// # Arguments
+    ///
+    /// * `limit` - The maximum number of pooled entries.
+    pub fn set_pool_limit(&mut self, limit: usize) {
+        self.pool.set_limit(limit);
     }
 
-    /// Computes the hash index for a given key based on the current capacity.
+    /// Sets the maximum number of key and value bytes reserved by pooled entries.
+    ///
+    /// Entries recycled beyond this budget are pooled without their buffers. Lowering
+    /// the limit releases pooled entries until the pool fits.
     ///
     /// # Arguments
     ///
-    /// * `key` - A byte slice representing the key to hash.
-    /// * `capacity` - The current or new capacity of the hash table.
+    /// * `byte_limit` - The maximum number of pooled key and value bytes.
+    pub fn set_pool_byte_limit(&mut self, byte_limit: usize) {
+        self.pool.set_byte_limit(byte_limit);
+    }
+
+    /// Releases every pooled entry back to the allocator.
     ///
-    /// # Returns
+    /// Call this when churn subsides to give the memory held by the pool back.
     ///
-    /// * `usize` representing the index in the cells vector.
-    fn hash(key: &[u8], capacity: usize) -> usize {
-        let mut hasher = DefaultHasher::new();
-        key.hash(&mut hasher);
-        let hash = hasher.finish();
-        let index = (hash as usize) % capacity;
-        index
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut bytebox = ByteBox::new();
+    /// bytebox.insert(b"key", b"value");
+    /// bytebox.clear();
+    /// assert_eq!(bytebox.pool_stats().pooled, 1);
+    ///
+    /// bytebox.trim_pool();
+    /// assert_eq!(bytebox.pool_stats().pooled, 0);
+    /// ```
+    pub fn trim_pool(&mut self) {
+        self.pool.trim();
     }
 
-    /// Provides an iterator over the `ByteBox` that allows for iteration using `for` loops.
+    /// Returns a `Debug` view that lists every entry of the table.
     ///
-    /// This enables the use of `ByteBox` in contexts where an iterator is expected.
+    /// The `Debug` implementation of `ByteBox` only shows a summary and a bounded sample
+    /// of entries so that logging a large table stays cheap; use this for complete dumps.
     ///
     /// # Examples
     ///
@@ -425,20 +2562,37 @@ impl ByteBox {
     /// use bytesbox::ByteBox;
     ///
     /// let mut bytebox = ByteBox::new();
-    /// bytebox.insert(b"key1", b"value1");
-    /// bytebox.insert(b"key2", b"value2");
+    /// bytebox.insert(b"key", b"value");
     ///
-    /// for (key, value) in bytebox.iter() {
-    ///     println!("{:?}: {:?}", key, value);
-    /// }
+    /// println!("{:?}", bytebox);
+    /// println!("{:#?}", bytebox.debug_full());
     /// ```
-    pub fn iter(&self) -> ByteBoxIterator {
-        ByteBoxIterator {
-            byte_box: &self,
-            entry: None,
-            index: 0,
-        }
+    pub fn debug_full(&self) -> DebugFull<'_> {
+        DebugFull { byte_box: self }
     }
+
+    /// Inspects how entries are distributed across the cells and recommends tuning changes.
+    ///
+    /// The returned [`TuningReport`] can be printed for capacity planning or consumed
+    /// programmatically through its public fields and [`Suggestion`] list.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut bytebox = ByteBox::prealloc(1024);
+    /// bytebox.insert(b"key", b"value");
+    ///
+    /// let report = bytebox.analyze();
+    /// assert_eq!(report.len, 1);
+    /// assert_eq!(report.longest_chain, 1);
+    /// println!("{}", report);
+    /// ```
+    pub fn analyze(&self) -> TuningReport {
+        TuningReport::new(self)
+    }
+
     /// Provides a detailed, colored visualization of the hash table.
     ///
     /// This function prints the structure of the `ByteBox`, including each cell and its entries.
@@ -591,127 +2745,166 @@ impl ByteBox {
     }
     #[cfg(not(feature = "color"))]
     pub fn view_table(&self) {
+        print!("{}", self.render_table());
+    }
+
+    /// Renders the visualization printed by [`ByteBox::view_table`] into a `String`.
+    ///
+    /// Unlike `view_table`, this never touches stdout, which keeps it usable on targets
+    /// without a console such as `wasm32-unknown-unknown`. The output is never colored.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut bytebox = ByteBox::new();
+    /// bytebox.insert(b"key", b"value");
+    /// let table = bytebox.render_table();
+    /// assert!(table.contains("| k |->| key   |"));
+    /// ```
+    pub fn render_table(&self) -> String {
+        self.render_table_with(RenderOptions::default())
+    }
+
+    /// Renders the table visualization like [`ByteBox::render_table`], laid out according
+    /// to `options`.
+    ///
+    /// Ordering cells by occupancy and collapsing the empty ones keeps collision hot spots
+    /// at the top of the output, even for tables with tens of thousands of cells.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - How cells are ordered and whether empty cells are listed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::debug::RenderOptions;
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut bytebox = ByteBox::prealloc(1024);
+    /// bytebox.insert(b"key", b"value");
+    ///
+    /// let table = bytebox.render_table_with(RenderOptions {
+    ///     densest_first: true,
+    ///     collapse_empty: true,
+    /// });
+    /// assert!(table.contains("  1023 empty cells"));
+    /// assert_eq!(table.matches("  Cell ").count(), 1);
+    /// ```
+    pub fn render_table_with(&self, options: RenderOptions) -> String {
+        let mut out = String::new();
+        self.write_table(&mut out, options)
+            .expect("writing into a String cannot fail");
+        out
+    }
+
+    /// Writes the plain table visualization into `out`.
+    fn write_table<W: fmt::Write>(&self, out: &mut W, options: RenderOptions) -> fmt::Result {
         // Cell Header
-        let bytebox_header = format!(
-            "{}, number of cell ({}), allocation ({})",
-            "ByteBox",
+        writeln!(out, "────────────────────────────────────────────────")?;
+        writeln!(
+            out,
+            "ByteBox, number of cell ({}), allocation ({})",
             self.len(),
             self.allocation()
-        );
-        // Print separator before each cell
-        println!("{}", "────────────────────────────────────────────────");
-        println!("{}", bytebox_header);
-        for (index, cell) in self.cells.iter().enumerate() {
+        )?;
+        let mut order: Vec<usize> = (0..self.cells.len()).collect();
+        if options.densest_first {
+            let lengths: Vec<usize> = self.chain_lengths().collect();
+            // Stable, so cells of equal occupancy keep their index order.
+            order.sort_by_key(|&index| std::cmp::Reverse(lengths[index]));
+        }
+        let mut empty_cells = 0;
+        for index in order {
+            let cell = &self.cells[index];
+            if options.collapse_empty && cell.is_none() {
+                empty_cells += 1;
+                continue;
+            }
             let mut current = cell.as_ref();
-            // Cell Header
-            let cell_header = format!("  Cell {}:", index);
             // Print separator before each cell
-            println!("{}", "────────────────────────────────────────────────");
-            println!("{}", cell_header);
+            writeln!(out, "────────────────────────────────────────────────")?;
+            writeln!(out, "  Cell {}:", index)?;
 
             while let Some(entry) = current {
-                let mut max_key_len = 0;
-                let mut max_val_len = 0;
-
                 let k_len = entry.key.len();
                 let v_len = entry.value.len();
 
-                if k_len > max_key_len {
-                    max_key_len = k_len;
-                }
-                if v_len > max_val_len {
-                    max_val_len = v_len;
-                }
-
                 // Determine the longest length
-                let get_longest_len = std::cmp::max(max_key_len, max_val_len);
+                let get_longest_len = std::cmp::max(k_len, v_len);
                 let k_closing_pipe = get_longest_len - k_len;
                 let v_closing_pipe = get_longest_len - v_len;
-                // Start of the cell box
                 // key val display Start
-                println!(
-                    "    {}",
-                    format!("+---+  +-{}-+", "-".repeat(get_longest_len))
-                );
+                writeln!(out, "    +---+  +-{}-+", "-".repeat(get_longest_len))?;
                 // Key and value with arrows
-                println!(
-                    "    {}",
-                    format!(
-                        "| {} |->| {}{} |",
-                        "k",
-                        format!("{}", String::from_utf8_lossy(&entry.key)),
-                        " ".repeat(k_closing_pipe)
-                    )
-                );
-                println!(
-                    "    {}",
-                    format!("+---+  +-{}-+", "-".repeat(get_longest_len))
-                );
-                println!(
-                    "    {}",
-                    format!(
-                        "| {} |->| {}{} |",
-                        "v",
-                        format!("{}", String::from_utf8_lossy(&entry.value)),
-                        " ".repeat(v_closing_pipe)
-                    )
-                );
-                println!(
-                    "    {}",
-                    format!("+---+  +-{}-+", "-".repeat(get_longest_len))
-                );
+                writeln!(
+                    out,
+                    "    | k |->| {}{} |",
+                    String::from_utf8_lossy(&entry.key),
+                    " ".repeat(k_closing_pipe)
+                )?;
+                writeln!(out, "    +---+  +-{}-+", "-".repeat(get_longest_len))?;
+                writeln!(
+                    out,
+                    "    | v |->| {}{} |",
+                    String::from_utf8_lossy(&entry.value),
+                    " ".repeat(v_closing_pipe)
+                )?;
+                writeln!(out, "    +---+  +-{}-+", "-".repeat(get_longest_len))?;
                 // key val display END
 
                 // represantation on the Entry START
-                println!("    | byte_box | contains:");
+                writeln!(out, "    | byte_box | contains:")?;
                 let box_container = format!(
-                    "    {}{}+",
-                    "|           +-------------------------------",
+                    "    |           +-------------------------------{}+",
                     "-".repeat(get_longest_len)
                 );
-                println!("{}", box_container);
+                writeln!(out, "{}", box_container)?;
                 let box_container_len = box_container.len() - 36;
-                println!(
-                    "    {}{}|",
-                    "|           | Entry:                        ",
+                writeln!(
+                    out,
+                    "    |           | Entry:                        {}|",
                     " ".repeat(get_longest_len)
-                );
-                println!(
-                    "    {}{}|",
-                    format!(
-                        "|           | - key: Vec<u8> ({})",
-                        format!("{}", String::from_utf8_lossy(&entry.key))
-                    ),
+                )?;
+                writeln!(
+                    out,
+                    "    |           | - key: Vec<u8> ({}){}|",
+                    String::from_utf8_lossy(&entry.key),
                     " ".repeat(box_container_len - k_len)
-                );
-                println!(
-                    "    {}{}|",
-                    format!(
-                        "|           | - val: Vec<u8> ({})",
-                        format!("{}", String::from_utf8_lossy(&entry.value))
-                    ),
+                )?;
+                writeln!(
+                    out,
+                    "    |           | - val: Vec<u8> ({}){}|",
+                    String::from_utf8_lossy(&entry.value),
                     " ".repeat(box_container_len - v_len)
-                );
-                println!(
+                )?;
+                writeln!(
+                    out,
                     "    |           | - next: None                  {}|",
                     " ".repeat(get_longest_len)
-                );
-                println!(
-                    "    {}{}+",
-                    "|           +-------------------------------",
+                )?;
+                writeln!(
+                    out,
+                    "    |           +-------------------------------{}+",
                     "-".repeat(get_longest_len)
-                );
-                println!("    {}{}+", "+-------", "-".repeat(box_container_len + 24));
+                )?;
+                writeln!(out, "    +-------{}+", "-".repeat(box_container_len + 24))?;
                 current = entry.next.as_ref();
             }
-            // Indicate that the cell is empty in red
-            println!("    {}", "Empty");
+            // Indicate that the cell is empty
+            writeln!(out, "    Empty")?;
 
             // representation of the Entry END
         }
+        if empty_cells > 0 {
+            writeln!(out, "────────────────────────────────────────────────")?;
+            writeln!(out, "  {} empty cells", empty_cells)?;
+        }
 
         // Separator line
-        println!("{}", "────────────────────────────────────────────────");
-        println!("{}", "────────────────────────────────────────────────");
+        writeln!(out, "────────────────────────────────────────────────")?;
+        writeln!(out, "────────────────────────────────────────────────")
     }
 }
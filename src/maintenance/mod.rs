@@ -0,0 +1,99 @@
+//! Bounded housekeeping: shrinking oversized tables and purging expired tombstones.
+
+#[cfg(feature = "background")]
+use super::*;
+
+#[cfg(feature = "background")]
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+#[cfg(feature = "background")]
+use std::sync::{Arc, Mutex, PoisonError};
+#[cfg(feature = "background")]
+use std::thread::{self, JoinHandle};
+#[cfg(feature = "background")]
+use std::time::Duration;
+
+/// What a call to [`ByteBox::maintain`](crate::ByteBox::maintain) did.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MaintenanceReport {
+    /// The new allocation if a shrink completed during this call.
+    pub shrunk_to: Option<usize>,
+    /// `true` if a shrink is due but did not complete within the budget; the next call
+    /// resumes it where this one stopped.
+    pub deferred: bool,
+    /// Number of expired tombstones dropped.
+    pub purged_tombstones: usize,
+}
+
+/// A shrink in progress: the cells from `target` up to `cursor` have been folded into
+/// the first `target` cells, and `target` divides the allocation.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Shrink {
+    pub(crate) target: usize,
+    pub(crate) cursor: usize,
+}
+
+/// A background thread calling [`ByteBox::maintain`] on a shared table at a fixed interval.
+///
+/// Available with the `background` feature. The thread stops when [`stop`](Self::stop)
+/// is called or the handle is dropped.
+///
+/// # Examples
+///
+/// ```rust
+/// use bytesbox::maintenance::MaintenanceThread;
+/// use bytesbox::ByteBox;
+/// use std::sync::{Arc, Mutex};
+/// use std::time::Duration;
+///
+/// let table = Arc::new(Mutex::new(ByteBox::new()));
+/// let maintenance = MaintenanceThread::spawn(Arc::clone(&table), Duration::from_secs(1), 4096);
+/// table.lock().unwrap().insert(b"key", b"value");
+/// maintenance.stop();
+/// ```
+#[cfg(feature = "background")]
+#[derive(Debug)]
+pub struct MaintenanceThread {
+    stop: Option<Sender<()>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+#[cfg(feature = "background")]
+impl MaintenanceThread {
+    /// Spawns a thread calling `maintain(max_items)` on `table` every `interval`.
+    pub fn spawn(table: Arc<Mutex<ByteBox>>, interval: Duration, max_items: usize) -> Self {
+        let (stop, stopped) = mpsc::channel::<()>();
+        let handle = thread::spawn(move || {
+            while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(interval) {
+                table
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .maintain(max_items);
+            }
+        });
+        MaintenanceThread {
+            stop: Some(stop),
+            handle: Some(handle),
+        }
+    }
+
+    /// Stops the thread and waits for it to finish its current pass.
+    pub fn stop(mut self) {
+        self.shutdown();
+    }
+
+    fn shutdown(&mut self) {
+        if let Some(stop) = self.stop.take() {
+            let _ = stop.send(());
+        }
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+#[cfg(feature = "background")]
+impl Drop for MaintenanceThread {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
@@ -0,0 +1,262 @@
+//! A hash table with byte keys and values of any type.
+use super::*;
+
+/// A hash table keyed by bytes whose values are any `V`.
+///
+/// `ByteMap` uses the same hashing, chaining and growth policy as [`ByteBox`], so it suits
+/// byte keys (header names, wire identifiers) pointing at structured Rust values that
+/// would otherwise have to be serialized into a `ByteBox`. Like `ByteBox::new`,
+/// [`ByteMap::new`] allocates nothing until the first insert.
+///
+/// # Examples
+///
+/// ```rust
+/// use bytesbox::map::ByteMap;
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Route {
+///     upstream: &'static str,
+///     weight: u32,
+/// }
+///
+/// let mut routes = ByteMap::new();
+/// routes.insert(b"/api", Route { upstream: "api:8080", weight: 3 });
+/// routes.get_mut(b"/api").unwrap().weight += 1;
+///
+/// assert_eq!(routes.get(b"/api"), Some(&Route { upstream: "api:8080", weight: 4 }));
+/// assert_eq!(routes.get(b"/static"), None);
+/// ```
+#[derive(Debug)]
+pub struct ByteMap<V> {
+    cells: Vec<Link<V>>,
+    alloc: usize,
+    len: usize,
+    load_factor_threshold: f32,
+}
+
+impl<V: Clone> Clone for ByteMap<V> {
+    /// Returns a deep copy; a map never shares its entries.
+    fn clone(&self) -> Self {
+        let mut cells = self.cells.clone();
+        cells.iter_mut().for_each(Entry::unshare_chain);
+        ByteMap {
+            cells,
+            alloc: self.alloc,
+            len: self.len,
+            load_factor_threshold: self.load_factor_threshold,
+        }
+    }
+}
+
+impl<V> Default for ByteMap<V> {
+    /// Creates an empty `ByteMap` that allocates on first insert, same as [`ByteMap::new`].
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V> ByteMap<V> {
+    /// Creates a new, empty `ByteMap` without allocating.
+    pub fn new() -> Self {
+        Self::prealloc(0)
+    }
+
+    /// Creates a new `ByteMap` with a specified initial number of cells.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - The initial number of cells to allocate; zero defers allocation to the
+    ///   first insert.
+    pub fn prealloc(size: usize) -> Self {
+        let mut cells = Vec::with_capacity(size);
+        cells.resize_with(size, || None);
+        ByteMap {
+            cells,
+            alloc: size,
+            len: 0,
+            load_factor_threshold: 0.75,
+        }
+    }
+
+    /// Returns the number of key-value pairs stored.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no key-value pair is stored.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the current allocation size (number of cells).
+    pub fn allocation(&self) -> usize {
+        self.alloc
+    }
+
+    /// Inserts a key-value pair, replacing the value if the key already exists.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - A byte slice representing the key.
+    /// * `value` - The value to store.
+    ///
+    /// # Returns
+    ///
+    /// * `None` if a new key-value pair was inserted.
+    /// * `Some(V)` holding the previous value if an existing key was updated.
+    pub fn insert(&mut self, key: &[u8], value: V) -> Option<V> {
+        if self.alloc == 0 {
+            self.rehash_to(INITIAL_ALLOCATION);
+        } else if (self.len as f32) / (self.alloc as f32) >= self.load_factor_threshold {
+            self.rehash_to(self.alloc * 2);
+        }
+
+        let idx = ByteBox::hash(key, self.alloc);
+        let mut cursor = ChainCursor::unique(&mut self.cells[idx]);
+        if let Some(entry) = cursor.seek(|entry| entry.key == key) {
+            return Some(std::mem::replace(&mut entry.value, value));
+        }
+
+        let next = self.cells[idx].take();
+        self.cells[idx] = Some(Arc::new(Entry {
+            key: key.to_vec(),
+            value,
+            next,
+        }));
+        self.len += 1;
+        None
+    }
+
+    /// Returns a reference to the value stored under `key`, if any.
+    pub fn get(&self, key: &[u8]) -> Option<&V> {
+        if self.alloc == 0 {
+            return None;
+        }
+        let mut current = self.cells[ByteBox::hash(key, self.alloc)].as_deref();
+        while let Some(entry) = current {
+            if entry.key == key {
+                return Some(&entry.value);
+            }
+            current = entry.next.as_deref();
+        }
+        None
+    }
+
+    /// Returns a mutable reference to the value stored under `key`, if any.
+    pub fn get_mut(&mut self, key: &[u8]) -> Option<&mut V> {
+        if self.alloc == 0 {
+            return None;
+        }
+        let mut current = self.cells[ByteBox::hash(key, self.alloc)]
+            .as_mut()
+            .map(Entry::unique);
+        while let Some(entry) = current {
+            if entry.key == key {
+                return Some(&mut entry.value);
+            }
+            current = entry.next.as_mut().map(Entry::unique);
+        }
+        None
+    }
+
+    /// Returns `true` if the map holds an entry for `key`.
+    pub fn contains_key(&self, key: &[u8]) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Removes `key` from the map.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(V)` holding the removed value if the key existed.
+    /// * `None` otherwise.
+    pub fn remove(&mut self, key: &[u8]) -> Option<V> {
+        if self.alloc == 0 {
+            return None;
+        }
+        let idx = ByteBox::hash(key, self.alloc);
+        let mut cursor = ChainCursor::unique(&mut self.cells[idx]);
+        cursor.seek(|entry| entry.key == key)?;
+        let removed = cursor.remove_current()?;
+        self.len -= 1;
+        Arc::into_inner(removed).map(|entry| entry.value)
+    }
+
+    /// Removes every entry, keeping the allocated cells.
+    pub fn clear(&mut self) {
+        for cell in &mut self.cells {
+            let mut current = cell.take();
+            // Unlink iteratively so long chains cannot overflow the stack on drop.
+            while let Some(mut entry) = current {
+                current = Entry::unique(&mut entry).next.take();
+            }
+        }
+        self.len = 0;
+    }
+
+    /// Returns an iterator over the key-value pairs, in cell order.
+    pub fn iter(&self) -> ByteMapIter<'_, V> {
+        ByteMapIter {
+            cells: self.cells.iter(),
+            entry: None,
+        }
+    }
+
+    /// Returns the length of the collision chain of every cell, in cell order.
+    pub(crate) fn chain_lengths(&self) -> impl Iterator<Item = usize> + '_ {
+        self.cells.iter().map(|cell| {
+            std::iter::successors(cell.as_deref(), |entry| entry.next.as_deref()).count()
+        })
+    }
+
+    /// Moves every entry into a fresh array of `new_cap` cells.
+    fn rehash_to(&mut self, new_cap: usize) {
+        let mut new_cells: Vec<Link<V>> = Vec::with_capacity(new_cap);
+        new_cells.resize_with(new_cap, || None);
+
+        for cell in self.cells.iter_mut() {
+            let mut current = cell.take();
+            while let Some(mut entry) = current {
+                let idx = ByteBox::hash(&entry.key, new_cap);
+                let relinked = Entry::unique(&mut entry);
+                current = relinked.next.take();
+                relinked.next = new_cells[idx].take();
+                new_cells[idx] = Some(entry);
+            }
+        }
+
+        self.cells = new_cells;
+        self.alloc = new_cap;
+    }
+}
+
+/// An iterator over the key-value pairs of a [`ByteMap`].
+///
+/// This struct is created by the [`ByteMap::iter`] method.
+pub struct ByteMapIter<'a, V> {
+    cells: std::slice::Iter<'a, Link<V>>,
+    entry: Option<&'a Entry<V>>,
+}
+
+impl<'a, V> Iterator for ByteMapIter<'a, V> {
+    type Item = (&'a [u8], &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(entry) = self.entry {
+                self.entry = entry.next.as_deref();
+                return Some((&entry.key[..], &entry.value));
+            }
+            self.entry = self.cells.next()?.as_deref();
+        }
+    }
+}
+
+impl<'a, V> IntoIterator for &'a ByteMap<V> {
+    type Item = (&'a [u8], &'a V);
+    type IntoIter = ByteMapIter<'a, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
@@ -0,0 +1,92 @@
+//! Incremental migration of entries from one table to another.
+use super::*;
+
+/// How far a [`ByteBox::migrate_into`] migration has progressed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MigrationProgress {
+    /// Entries moved by this call.
+    pub moved: usize,
+    /// Entries still left in the source table.
+    pub remaining: usize,
+}
+
+impl MigrationProgress {
+    /// Returns `true` once the source table has been drained.
+    pub fn is_complete(&self) -> bool {
+        self.remaining == 0
+    }
+}
+
+impl ByteBox {
+    /// Moves up to `budget` entries from this table into `target`.
+    ///
+    /// Calling it repeatedly drains this table in bounded steps, so a table can be moved to
+    /// one with a different hasher, seed or allocation without a single long copy. Between
+    /// calls both tables stay fully usable: a reader can look a key up in the source first
+    /// and fall back to the target, and keys inserted into the source meanwhile are picked
+    /// up by a later call. A migrated key replaces any value `target` already holds for it.
+    ///
+    /// Each call resumes scanning where the previous one stopped, so its cost is
+    /// proportional to `budget` plus the empty cells skipped since the last call.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The table receiving the entries.
+    /// * `budget` - The largest number of entries to move in this call.
+    ///
+    /// # Returns
+    ///
+    /// A [`MigrationProgress`] with the number of entries moved and the number left.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut old = ByteBox::new();
+    /// for i in 0..10u8 {
+    ///     old.insert(&[i], b"value");
+    /// }
+    ///
+    /// let mut new = ByteBox::prealloc(64);
+    /// let progress = old.migrate_into(&mut new, 4);
+    /// assert_eq!((progress.moved, progress.remaining), (4, 6));
+    ///
+    /// while !old.migrate_into(&mut new, 4).is_complete() {}
+    /// assert!(old.is_empty());
+    /// assert_eq!(new.len(), 10);
+    /// ```
+    pub fn migrate_into(&mut self, target: &mut ByteBox, budget: usize) -> MigrationProgress {
+        let mut moved = 0;
+        // The cursor is only a hint: inserts may land in cells it has already passed, so
+        // the scan wraps around until the table is empty or the budget is spent.
+        let mut scanned = 0;
+        while moved < budget && self.len > 0 && scanned <= self.alloc {
+            let index = self.migration_cursor % self.alloc;
+            match self.cells[index].take() {
+                Some(mut entry) => {
+                    self.cells[index] = Entry::take_next(&mut entry);
+                    target.insert(&entry.key, &entry.value);
+                    self.checksum = self
+                        .checksum
+                        .wrapping_sub(entry_hash(&entry.key, &entry.value));
+                    self.content_bytes -= entry.key.len() + entry.value.len();
+                    self.len -= 1;
+                    self.generation = self.generation.wrapping_add(1);
+                    self.pool.recycle(entry);
+                    moved += 1;
+                }
+                None => {
+                    self.migration_cursor = (index + 1) % self.alloc;
+                    scanned += 1;
+                }
+            }
+        }
+
+        self.after_mutation();
+        MigrationProgress {
+            moved,
+            remaining: self.len,
+        }
+    }
+}
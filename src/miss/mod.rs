@@ -0,0 +1,56 @@
+//! Notification of lookups that find nothing.
+use super::*;
+
+use std::sync::Arc;
+
+/// A miss handler: receives the key of every [`ByteBox::get`] that finds nothing.
+pub type MissHandler = Arc<dyn Fn(&[u8]) + Send + Sync>;
+
+impl ByteBox {
+    /// Registers `handler` to be called with the key of every lookup that finds nothing,
+    /// replacing any previous handler.
+    ///
+    /// The handler runs synchronously inside [`ByteBox::get`] and the reads built on it
+    /// (such as [`ByteBox::get_range`] or [`ByteBox::get_decoded`]), and once per missing
+    /// key inside [`ByteBox::get_many`], so a read-through
+    /// cache can log misses or queue them for loading in one place. Existence checks such
+    /// as [`ByteBox::contains_key`] do not count as misses. The table is borrowed while
+    /// the handler runs, so it cannot insert the missing key itself; collect the keys and
+    /// populate them afterwards. Clones of the table share the handler.
+    ///
+    /// # Arguments
+    ///
+    /// * `handler` - Receives the key that was not found.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// let misses = Arc::new(Mutex::new(Vec::new()));
+    /// let mut cache = ByteBox::new();
+    /// let log = Arc::clone(&misses);
+    /// cache.on_miss(move |key| log.lock().unwrap().push(key.to_vec()));
+    ///
+    /// cache.insert(b"user:1", b"ada");
+    /// assert!(cache.get(b"user:1").is_some());
+    /// assert!(cache.get(b"user:2").is_none());
+    ///
+    /// for key in misses.lock().unwrap().drain(..) {
+    ///     cache.insert(&key, b"loaded");
+    /// }
+    /// assert_eq!(cache.get(b"user:2"), Some(&b"loaded"[..]));
+    /// ```
+    pub fn on_miss<F>(&mut self, handler: F)
+    where
+        F: Fn(&[u8]) + Send + Sync + 'static,
+    {
+        self.miss_handler = Some(Arc::new(handler));
+    }
+
+    /// Removes the miss handler.
+    pub fn clear_miss_handler(&mut self) {
+        self.miss_handler = None;
+    }
+}
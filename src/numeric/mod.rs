@@ -0,0 +1,248 @@
+//! Checked arithmetic on integers stored as little-endian values.
+use super::*;
+
+/// An integer type that can be stored as a fixed-width, little-endian value and updated
+/// with [`ByteBox::checked_add`], [`ByteBox::checked_sub`] and [`ByteBox::checked_mul`].
+///
+/// Implemented for every primitive integer type.
+pub trait StoredInt: Copy + Sized {
+    /// Size of the encoded value, in bytes.
+    const WIDTH: usize;
+
+    /// Decodes a value from exactly [`Self::WIDTH`] little-endian bytes.
+    fn read_le(bytes: &[u8]) -> Self;
+
+    /// Encodes the value into exactly [`Self::WIDTH`] little-endian bytes.
+    fn write_le(self, out: &mut [u8]);
+
+    /// Adds, returning `None` on overflow.
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+
+    /// Subtracts, returning `None` on overflow.
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+
+    /// Multiplies, returning `None` on overflow.
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! impl_stored_int {
+    ($($int:ty),*) => {
+        $(
+            impl StoredInt for $int {
+                const WIDTH: usize = std::mem::size_of::<$int>();
+
+                fn read_le(bytes: &[u8]) -> Self {
+                    let mut buf = [0u8; std::mem::size_of::<$int>()];
+                    buf.copy_from_slice(bytes);
+                    <$int>::from_le_bytes(buf)
+                }
+
+                fn write_le(self, out: &mut [u8]) {
+                    out.copy_from_slice(&self.to_le_bytes());
+                }
+
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$int>::checked_add(self, rhs)
+                }
+
+                fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    <$int>::checked_sub(self, rhs)
+                }
+
+                fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    <$int>::checked_mul(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_stored_int!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+impl ByteBox {
+    /// Adds `operand` to the integer stored under `key`, in place.
+    ///
+    /// The value must be a little-endian `T`, e.g. written with
+    /// `insert(key, &n.to_le_bytes())`. On error the stored value is left untouched.
+    ///
+    /// Keeping [`ByteBox::content_hash`] current rehashes the key and the value, so an
+    /// update costs time proportional to the key length rather than constant time.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key holding the integer.
+    /// * `operand` - The amount to add.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(T)` containing the new value.
+    /// * `Err(NumericError)` if the key is missing, the value is not `T::WIDTH` bytes
+    ///   long, or the result overflows.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::error::NumericError;
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut buckets = ByteBox::new();
+    /// buckets.insert(b"client:1", &250u8.to_le_bytes());
+    ///
+    /// assert_eq!(buckets.checked_add(b"client:1", 5u8), Ok(255));
+    /// assert_eq!(buckets.checked_add(b"client:1", 1u8), Err(NumericError::Overflow));
+    /// assert_eq!(buckets.get(b"client:1"), Some(&[255u8][..]));
+    /// ```
+    pub fn checked_add<T: StoredInt>(&mut self, key: &[u8], operand: T) -> Result<T, NumericError> {
+        self.checked_op(key, |value: T| T::checked_add(value, operand))
+    }
+
+    /// Subtracts `operand` from the integer stored under `key`, in place.
+    ///
+    /// See [`ByteBox::checked_add`] for the encoding and the errors.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::error::NumericError;
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut buckets = ByteBox::new();
+    /// buckets.insert(b"tokens", &3u32.to_le_bytes());
+    ///
+    /// assert_eq!(buckets.checked_sub(b"tokens", 3u32), Ok(0));
+    /// assert_eq!(buckets.checked_sub(b"tokens", 1u32), Err(NumericError::Overflow));
+    /// ```
+    pub fn checked_sub<T: StoredInt>(&mut self, key: &[u8], operand: T) -> Result<T, NumericError> {
+        self.checked_op(key, |value: T| T::checked_sub(value, operand))
+    }
+
+    /// Multiplies the integer stored under `key` by `operand`, in place.
+    ///
+    /// See [`ByteBox::checked_add`] for the encoding and the errors.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut table = ByteBox::new();
+    /// table.insert(b"backoff", &(-2i64).to_le_bytes());
+    /// assert_eq!(table.checked_mul(b"backoff", 3i64), Ok(-6));
+    /// ```
+    pub fn checked_mul<T: StoredInt>(&mut self, key: &[u8], operand: T) -> Result<T, NumericError> {
+        self.checked_op(key, |value: T| T::checked_mul(value, operand))
+    }
+
+    fn checked_op<T, F>(&mut self, key: &[u8], op: F) -> Result<T, NumericError>
+    where
+        T: StoredInt,
+        F: FnOnce(T) -> Option<T>,
+    {
+        self.edit_value(key, |value| {
+            if value.len() != T::WIDTH {
+                return Err(NumericError::WidthMismatch {
+                    expected: T::WIDTH,
+                    found: value.len(),
+                });
+            }
+            let result = op(T::read_le(value)).ok_or(NumericError::Overflow)?;
+            result.write_le(value);
+            Ok(result)
+        })
+        .unwrap_or(Err(NumericError::MissingKey))
+    }
+}
+
+impl ByteBox {
+    /// Sets bit `index` of the value stored under `key`, treating it as a bitmap.
+    ///
+    /// Bit `index` lives in byte `index / 8`, at position `index % 8` counting from the
+    /// least significant bit. The value is zero-extended as needed, and a missing key is
+    /// created with an all-zero bitmap.
+    ///
+    /// Changing a bit rehashes the key and the whole bitmap to keep
+    /// [`ByteBox::content_hash`] current, so it costs time proportional to the bitmap's
+    /// length. Setting a bit that is already set changes nothing and skips the rehash.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key holding the bitmap.
+    /// * `index` - The bit to set.
+    ///
+    /// # Returns
+    ///
+    /// The previous state of the bit.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut flags = ByteBox::new();
+    /// assert!(!flags.set_bit(b"user:1", 9));
+    /// assert!(flags.test_bit(b"user:1", 9));
+    /// assert_eq!(flags.get(b"user:1"), Some(&[0b0000_0000, 0b0000_0010][..]));
+    /// ```
+    pub fn set_bit(&mut self, key: &[u8], index: usize) -> bool {
+        let (byte, mask) = (index / 8, 1u8 << (index % 8));
+        match self.lookup(key) {
+            Some(value) if value.get(byte).is_some_and(|bits| bits & mask != 0) => return true,
+            Some(_) => {}
+            None => {
+                self.insert(key, &[]);
+            }
+        }
+        self.edit_value(key, |value| {
+            if value.len() <= byte {
+                value.resize(byte + 1, 0);
+            }
+            value[byte] |= mask;
+        });
+        false
+    }
+
+    /// Clears bit `index` of the value stored under `key`.
+    ///
+    /// Bits beyond the end of the value are already clear, so the value is never grown
+    /// and a missing key is left missing. As with [`ByteBox::set_bit`], changing a bit
+    /// costs time proportional to the bitmap's length, and clearing a clear bit is free.
+    ///
+    /// # Returns
+    ///
+    /// The previous state of the bit.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut flags = ByteBox::new();
+    /// flags.set_bit(b"user:1", 3);
+    /// assert!(flags.clear_bit(b"user:1", 3));
+    /// assert!(!flags.clear_bit(b"user:1", 100));
+    /// assert_eq!(flags.get(b"user:1"), Some(&[0][..]));
+    /// ```
+    pub fn clear_bit(&mut self, key: &[u8], index: usize) -> bool {
+        let (byte, mask) = (index / 8, 1u8 << (index % 8));
+        let set = self
+            .lookup(key)
+            .and_then(|value| value.get(byte))
+            .is_some_and(|bits| bits & mask != 0);
+        if set {
+            self.edit_value(key, |value| value[byte] &= !mask);
+        }
+        set
+    }
+
+    /// Returns the state of bit `index` of the value stored under `key`.
+    ///
+    /// Missing keys and bits beyond the end of the value read as clear. Like
+    /// [`ByteBox::contains_key`], this is an existence check: a missing key is not
+    /// reported to the miss handler.
+    pub fn test_bit(&self, key: &[u8], index: usize) -> bool {
+        let (byte, mask) = (index / 8, 1u8 << (index % 8));
+        self.lookup(key)
+            .and_then(|value| value.get(byte))
+            .is_some_and(|bits| bits & mask != 0)
+    }
+}
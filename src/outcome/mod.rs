@@ -0,0 +1,15 @@
+//! Detailed results of inserts.
+
+/// What [`ByteBox::insert_report`](crate::ByteBox::insert_report) did with a key-value pair.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InsertOutcome {
+    /// A new key was added without growing the table.
+    Inserted,
+    /// An existing key was given the new value.
+    Updated {
+        /// Length of the value that was replaced.
+        old_len: usize,
+    },
+    /// A new key was added after the table grew to make room for it.
+    Resized,
+}
@@ -0,0 +1,114 @@
+//! Several byte fields packed into a single value.
+//!
+//! A packed value is the concatenation of every field, each preceded by its length as an
+//! unsigned LEB128 varint: seven bits per byte, least significant group first, with the
+//! high bit set on every byte but the last. An empty slice packs to an empty value.
+use super::*;
+
+/// Packs `fields` into a single value, each field prefixed with its varint length.
+///
+/// # Examples
+///
+/// ```rust
+/// use bytesbox::packed::{pack_values, unpack_values};
+///
+/// let packed = pack_values(&[b"alice", b"", b"admin"]);
+/// assert_eq!(packed, b"\x05alice\x00\x05admin");
+/// assert_eq!(unpack_values(&packed).unwrap(), vec![&b"alice"[..], b"", b"admin"]);
+/// ```
+pub fn pack_values(fields: &[&[u8]]) -> Vec<u8> {
+    let len = fields.iter().map(|field| field.len() + 10).sum();
+    let mut out = Vec::with_capacity(len);
+    for field in fields {
+        let mut n = field.len() as u64;
+        while n >= 0x80 {
+            out.push(n as u8 | 0x80);
+            n >>= 7;
+        }
+        out.push(n as u8);
+        out.extend_from_slice(field);
+    }
+    out
+}
+
+/// Splits a value written by [`pack_values`] back into its fields.
+///
+/// # Errors
+///
+/// Returns a [`PackedValueError`] if a length prefix is malformed or a field runs past
+/// the end of `packed`.
+pub fn unpack_values(packed: &[u8]) -> Result<Vec<&[u8]>, PackedValueError> {
+    let mut fields = Vec::new();
+    let mut offset = 0;
+    while offset < packed.len() {
+        let start = offset;
+        let mut len: u64 = 0;
+        let mut shift = 0;
+        loop {
+            let byte = *packed
+                .get(offset)
+                .ok_or(PackedValueError::Truncated { offset: start })?;
+            offset += 1;
+            if shift == 63 && byte > 1 {
+                return Err(PackedValueError::LengthOverflow { offset: start });
+            }
+            len |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                break;
+            }
+            shift += 7;
+        }
+        let end = usize::try_from(len)
+            .ok()
+            .and_then(|len| offset.checked_add(len))
+            .filter(|&end| end <= packed.len())
+            .ok_or(PackedValueError::Truncated { offset: start })?;
+        fields.push(&packed[offset..end]);
+        offset = end;
+    }
+    Ok(fields)
+}
+
+impl ByteBox {
+    /// Inserts the packed form of `fields` under `key`; see [`pack_values`].
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - A byte slice representing the key.
+    /// * `fields` - The fields to pack into the value.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if a new key-value pair was inserted.
+    /// * `false` if an existing key was updated.
+    pub fn insert_packed(&mut self, key: &[u8], fields: &[&[u8]]) -> bool {
+        self.insert(key, &pack_values(fields))
+    }
+
+    /// Returns the fields of the packed value stored under `key`, borrowed from the table.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(fields))` if the key exists.
+    /// * `Ok(None)` if the key does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`PackedValueError`] if the stored value is not a valid packed value.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut bytebox = ByteBox::new();
+    /// bytebox.insert_packed(b"user:1", &[b"ada", b"ada@example.com"]);
+    ///
+    /// let fields = bytebox.get_unpacked(b"user:1").unwrap().unwrap();
+    /// assert_eq!(fields, vec![&b"ada"[..], b"ada@example.com"]);
+    /// assert_eq!(bytebox.get_unpacked(b"user:2"), Ok(None));
+    /// ```
+    pub fn get_unpacked(&self, key: &[u8]) -> Result<Option<Vec<&[u8]>>, PackedValueError> {
+        self.get(key).map(unpack_values).transpose()
+    }
+}
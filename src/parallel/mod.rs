@@ -0,0 +1,48 @@
+//! Parallel consumption of a table, enabled by the `rayon` feature.
+use super::*;
+
+use rayon::prelude::*;
+
+/// The entries of one cell, in chain order; the layout accepted by
+/// [`ByteBox::from_raw_parts`].
+pub type Bucket = Vec<(Vec<u8>, Vec<u8>)>;
+
+impl ByteBox {
+    /// Consumes the table and yields the entries of every non-empty cell as an owned
+    /// [`Bucket`], in parallel.
+    ///
+    /// Keys and values are moved out of the table, never cloned, and unlinking the
+    /// chains happens on the rayon workers along with the caller's processing. Buckets
+    /// partition the keys by hash, so per-bucket results can be combined without
+    /// coordinating between workers.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    /// use rayon::prelude::*;
+    ///
+    /// let mut bytebox = ByteBox::new();
+    /// for i in 0..1_000u32 {
+    ///     bytebox.insert(&i.to_le_bytes(), &[(i % 7) as u8]);
+    /// }
+    ///
+    /// let total: u64 = bytebox
+    ///     .into_par_buckets()
+    ///     .map(|bucket| bucket.iter().map(|(_, value)| u64::from(value[0])).sum::<u64>())
+    ///     .sum();
+    /// assert_eq!(total, (0..1_000u64).map(|i| i % 7).sum());
+    /// ```
+    pub fn into_par_buckets(self) -> impl ParallelIterator<Item = Bucket> {
+        self.cells.into_par_iter().filter_map(|cell| {
+            let mut bucket = Vec::new();
+            let mut current = cell;
+            while let Some(mut entry) = current {
+                current = Entry::take_next(&mut entry);
+                let entry = Entry::into_owned(entry);
+                bucket.push((entry.key, entry.value));
+            }
+            (!bucket.is_empty()).then_some(bucket)
+        })
+    }
+}
@@ -0,0 +1,91 @@
+//! Byte glob patterns over keys.
+use super::*;
+
+/// Returns `true` if `key` matches the byte glob `pattern`.
+///
+/// `*` matches any run of bytes (including none), `?` matches exactly one byte and `\`
+/// makes the next byte literal. Every other byte matches itself. Matching runs in
+/// `O(pattern.len() * key.len())` time at worst, without recursion.
+///
+/// # Examples
+///
+/// ```rust
+/// use bytesbox::pattern::glob_match;
+///
+/// assert!(glob_match(b"user:*:session", b"user:42:session"));
+/// assert!(glob_match(b"user:?", b"user:7"));
+/// assert!(!glob_match(b"user:?", b"user:42"));
+/// assert!(glob_match(br"literal\*", b"literal*"));
+/// ```
+pub fn glob_match(pattern: &[u8], key: &[u8]) -> bool {
+    let (mut p, mut k) = (0, 0);
+    // Position after the last `*` seen, and the key position it is currently matched up to.
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while k < key.len() {
+        match pattern.get(p) {
+            Some(b'*') => {
+                p += 1;
+                backtrack = Some((p, k));
+                continue;
+            }
+            Some(b'?') => {
+                p += 1;
+                k += 1;
+                continue;
+            }
+            Some(b'\\') if pattern.get(p + 1) == Some(&key[k]) => {
+                p += 2;
+                k += 1;
+                continue;
+            }
+            Some(&literal) if literal != b'\\' && literal == key[k] => {
+                p += 1;
+                k += 1;
+                continue;
+            }
+            _ => {}
+        }
+        match backtrack {
+            Some((star, matched)) => {
+                p = star;
+                k = matched + 1;
+                backtrack = Some((star, matched + 1));
+            }
+            None => return false,
+        }
+    }
+
+    pattern[p..].iter().all(|&byte| byte == b'*')
+}
+
+impl ByteBox {
+    /// Returns an iterator over the entries whose key matches the byte glob `pattern`.
+    ///
+    /// See [`glob_match`] for the pattern syntax. Entries are visited in iteration order.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - The glob the keys are matched against.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut bytebox = ByteBox::new();
+    /// bytebox.insert(b"user:1:session", b"a");
+    /// bytebox.insert(b"user:2:session", b"b");
+    /// bytebox.insert(b"user:2:profile", b"c");
+    ///
+    /// let mut sessions: Vec<_> = bytebox.iter_match(b"user:*:session").map(|(_, v)| v).collect();
+    /// sessions.sort();
+    /// assert_eq!(sessions, vec![&b"a"[..], &b"b"[..]]);
+    /// ```
+    pub fn iter_match<'a>(
+        &'a self,
+        pattern: &'a [u8],
+    ) -> impl Iterator<Item = (&'a [u8], &'a [u8])> + 'a {
+        self.iter().filter(move |(key, _)| glob_match(pattern, key))
+    }
+}
@@ -0,0 +1,16 @@
+//! What an insert does when its key is already present.
+
+/// What [`ByteBox::insert_with_policy`](crate::ByteBox::insert_with_policy) does when the
+/// key is already present.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DuplicatePolicy<'a> {
+    /// Replace the stored value, like [`ByteBox::insert`](crate::ByteBox::insert).
+    Overwrite,
+    /// Leave the stored value untouched.
+    Keep,
+    /// Append the separator and then the new value to the stored value, which folds
+    /// list-valued headers such as `Accept` with `b", "`.
+    Append(&'a [u8]),
+    /// Leave the stored value untouched and report a [`DuplicateKey`](crate::error::DuplicateKey) error.
+    Error,
+}
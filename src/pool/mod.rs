@@ -0,0 +1,158 @@
+//! A bounded pool recycling the entries of removed keys.
+use super::*;
+
+/// Maximum number of recycled entries kept by a new table.
+pub const DEFAULT_POOL_LIMIT: usize = 1024;
+
+/// Maximum number of key and value bytes reserved by the pool of a new table.
+pub const DEFAULT_POOL_BYTE_LIMIT: usize = 256 * 1024;
+
+/// Largest key or value buffer a recycled entry keeps; bigger buffers are freed so a
+/// large removed value is never handed to a small insert.
+const MAX_POOLED_BUFFER: usize = 1024;
+
+/// Counters describing the entry pool of a `ByteBox`.
+///
+/// This struct is created by the [`ByteBox::pool_stats`] method.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PoolStats {
+    /// Number of entries currently waiting to be reused.
+    pub pooled: usize,
+    /// Key and value bytes reserved by the pooled entries.
+    pub pooled_bytes: usize,
+    /// Maximum number of entries the pool retains.
+    pub limit: usize,
+    /// Maximum number of key and value bytes the pool reserves.
+    pub byte_limit: usize,
+    /// Inserts served from the pool instead of the allocator.
+    pub reused: u64,
+    /// Entries handed back to the pool by `remove` and `clear`.
+    pub recycled: u64,
+}
+
+/// A free-list of entry allocations released by removals.
+///
+/// Recycled entries keep their buffers when they are small, so churn of similarly sized
+/// keys reuses the `Entry` box as well as the key allocation (and the value allocation
+/// when the table was cleared rather than drained by `remove`, which hands the value to
+/// the caller). Buffers over `MAX_POOLED_BUFFER`, or that would take the pool past its
+/// byte limit, are freed and the entry is pooled without them.
+#[derive(Debug)]
+pub(crate) struct EntryPool {
+    /// Entries no table refers to, so each is reused without copying.
+    free: Vec<Arc<Entry>>,
+    limit: usize,
+    byte_limit: usize,
+    /// Capacity of the key and value buffers held by `free`.
+    bytes: usize,
+    reused: u64,
+    recycled: u64,
+}
+
+impl Clone for EntryPool {
+    /// Copies the limits and counters; the pooled entries stay with `self`, which is the
+    /// only owner they may have.
+    fn clone(&self) -> Self {
+        EntryPool {
+            free: Vec::new(),
+            bytes: 0,
+            ..*self
+        }
+    }
+}
+
+impl EntryPool {
+    pub(crate) fn new() -> Self {
+        EntryPool {
+            free: Vec::new(),
+            limit: DEFAULT_POOL_LIMIT,
+            byte_limit: DEFAULT_POOL_BYTE_LIMIT,
+            bytes: 0,
+            reused: 0,
+            recycled: 0,
+        }
+    }
+
+    /// Builds an entry, reusing a pooled allocation when one is available.
+    pub(crate) fn take(&mut self, key: &[u8], value: &[u8], next: Link) -> Arc<Entry> {
+        match self.free.pop() {
+            Some(mut node) => {
+                self.reused += 1;
+                self.bytes -= Self::reserved(&node);
+                let entry = Entry::unique(&mut node);
+                entry.key.clear();
+                entry.key.extend_from_slice(key);
+                entry.value.clear();
+                entry.value.extend_from_slice(value);
+                entry.next = next;
+                node
+            }
+            None => Arc::new(Entry {
+                key: key.to_vec(),
+                value: value.to_vec(),
+                next,
+            }),
+        }
+    }
+
+    /// Hands an unlinked entry back to the pool, or frees it when the pool is full.
+    ///
+    /// An entry still shared with another table is only released.
+    pub(crate) fn recycle(&mut self, mut node: Arc<Entry>) {
+        let Some(entry) = Arc::get_mut(&mut node) else {
+            return;
+        };
+        debug_assert!(entry.next.is_none());
+        if self.free.len() < self.limit {
+            for buffer in [&mut entry.key, &mut entry.value] {
+                if buffer.capacity() > MAX_POOLED_BUFFER
+                    || self.bytes + buffer.capacity() > self.byte_limit
+                {
+                    *buffer = Vec::new();
+                } else {
+                    buffer.clear();
+                    self.bytes += buffer.capacity();
+                }
+            }
+            self.free.push(node);
+            self.recycled += 1;
+        }
+    }
+
+    pub(crate) fn set_limit(&mut self, limit: usize) {
+        self.limit = limit;
+        for entry in self.free.drain(limit.min(self.free.len())..) {
+            self.bytes -= Self::reserved(&entry);
+        }
+    }
+
+    pub(crate) fn set_byte_limit(&mut self, byte_limit: usize) {
+        self.byte_limit = byte_limit;
+        while self.bytes > byte_limit {
+            match self.free.pop() {
+                Some(entry) => self.bytes -= Self::reserved(&entry),
+                None => break,
+            }
+        }
+    }
+
+    pub(crate) fn trim(&mut self) {
+        self.free = Vec::new();
+        self.bytes = 0;
+    }
+
+    fn reserved(entry: &Entry) -> usize {
+        entry.key.capacity() + entry.value.capacity()
+    }
+
+    pub(crate) fn stats(&self) -> PoolStats {
+        PoolStats {
+            pooled: self.free.len(),
+            pooled_bytes: self.bytes,
+            limit: self.limit,
+            byte_limit: self.byte_limit,
+            reused: self.reused,
+            recycled: self.recycled,
+        }
+    }
+}
@@ -0,0 +1,297 @@
+//! A table storing keys front-coded against the previous key of their bucket.
+use super::*;
+
+use std::cmp::Ordering;
+
+/// A key stored as the length of the prefix it shares with the previous key of its
+/// bucket, followed by the remaining bytes.
+#[derive(Debug, Clone)]
+struct CompressedEntry {
+    shared: usize,
+    suffix: Vec<u8>,
+    value: Vec<u8>,
+}
+
+/// Where a missing key would be inserted into a bucket.
+struct Vacancy {
+    /// Index of the first entry that sorts after the key.
+    position: usize,
+    /// Length of the prefix the key shares with the entry before `position`.
+    shared: usize,
+    /// Length of the prefix the key shares with the entry at `position`, if any.
+    next_shared: usize,
+}
+
+/// A `ByteBox` variant that prefix-compresses the keys of each bucket.
+///
+/// Entries of a bucket are kept sorted by key and front-coded: each key only stores the
+/// bytes that differ from the previous key of the same bucket. Workloads whose keys share
+/// long common prefixes, such as URLs or file paths, store far fewer key bytes; the
+/// savings are reported by [`PrefixByteBox::stats`].
+///
+/// Compression only pays off when buckets hold several keys, so the table resizes once
+/// buckets hold eight entries on average instead of following `ByteBox`'s load factor.
+/// Because keys are not stored contiguously, iteration yields owned keys.
+///
+/// # Examples
+///
+/// ```rust
+/// use bytesbox::prefix::PrefixByteBox;
+///
+/// let mut urls = PrefixByteBox::prealloc(1);
+/// urls.insert(b"https://example.com/assets/app.js", b"1");
+/// urls.insert(b"https://example.com/assets/app.css", b"2");
+///
+/// assert_eq!(urls.get(b"https://example.com/assets/app.css"), Some(&b"2"[..]));
+/// assert!(urls.stats().key_savings() > 0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct PrefixByteBox {
+    buckets: Vec<Vec<CompressedEntry>>,
+    len: usize,
+    load_factor_threshold: f32,
+}
+
+impl Default for PrefixByteBox {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PrefixByteBox {
+    /// Creates a new `PrefixByteBox` with a default initial capacity of 4 buckets.
+    pub fn new() -> Self {
+        Self::prealloc(4)
+    }
+
+    /// Creates a new `PrefixByteBox` with the given number of buckets.
+    pub fn prealloc(size: usize) -> Self {
+        PrefixByteBox {
+            buckets: vec![Vec::new(); size.max(1)],
+            len: 0,
+            load_factor_threshold: 8.0,
+        }
+    }
+
+    /// Returns the number of key-value pairs stored.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no key-value pair is stored.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of buckets.
+    pub fn allocation(&self) -> usize {
+        self.buckets.len()
+    }
+
+    /// Inserts a key-value pair, updating the value if the key already exists.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if a new key-value pair was inserted.
+    /// * `false` if an existing key was updated.
+    pub fn insert(&mut self, key: &[u8], value: &[u8]) -> bool {
+        if (self.len as f32) / (self.buckets.len() as f32) >= self.load_factor_threshold {
+            self.resize();
+        }
+
+        let idx = ByteBox::hash(key, self.buckets.len());
+        let bucket = &mut self.buckets[idx];
+        match Self::search(bucket, key) {
+            Ok(position) => {
+                bucket[position].value = value.to_vec();
+                false
+            }
+            Err(vacancy) => {
+                // The following key is now coded against `key`, which shares at least as
+                // much of it as the previous key did, so its suffix only loses bytes.
+                if let Some(next) = bucket.get_mut(vacancy.position) {
+                    next.suffix.drain(..vacancy.next_shared - next.shared);
+                    next.shared = vacancy.next_shared;
+                }
+                bucket.insert(
+                    vacancy.position,
+                    CompressedEntry {
+                        shared: vacancy.shared,
+                        suffix: key[vacancy.shared..].to_vec(),
+                        value: value.to_vec(),
+                    },
+                );
+                self.len += 1;
+                true
+            }
+        }
+    }
+
+    /// Retrieves the value associated with the given key, without decompressing the bucket.
+    pub fn get(&self, key: &[u8]) -> Option<&[u8]> {
+        let bucket = &self.buckets[ByteBox::hash(key, self.buckets.len())];
+        let position = Self::search(bucket, key).ok()?;
+        Some(&bucket[position].value)
+    }
+
+    /// Removes the key-value pair associated with the given key, returning its value.
+    pub fn remove(&mut self, key: &[u8]) -> Option<Vec<u8>> {
+        let idx = ByteBox::hash(key, self.buckets.len());
+        let bucket = &mut self.buckets[idx];
+        let position = Self::search(bucket, key).ok()?;
+        let removed = bucket.remove(position);
+        // The following key is now coded against the key before the removed one. It
+        // shares the shorter of the two prefixes with it, and any bytes it shared only
+        // with the removed key move back into its suffix.
+        if let Some(next) = bucket.get_mut(position) {
+            if next.shared > removed.shared {
+                let restored = &removed.suffix[..next.shared - removed.shared];
+                next.suffix.splice(..0, restored.iter().copied());
+                next.shared = removed.shared;
+            }
+        }
+        self.len -= 1;
+        Some(removed.value)
+    }
+
+    /// Returns an iterator over owned keys and borrowed values, bucket by bucket.
+    pub fn iter(&self) -> impl Iterator<Item = (Vec<u8>, &[u8])> + '_ {
+        self.buckets.iter().flat_map(|bucket| {
+            let mut key = Vec::new();
+            bucket.iter().map(move |entry| {
+                key.truncate(entry.shared);
+                key.extend_from_slice(&entry.suffix);
+                (key.clone(), entry.value.as_slice())
+            })
+        })
+    }
+
+    /// Returns memory and occupancy figures, including the key bytes saved by compression.
+    pub fn stats(&self) -> Stats {
+        let mut stats = Stats {
+            len: self.len,
+            allocation: self.buckets.len(),
+            ..Stats::default()
+        };
+        for bucket in &self.buckets {
+            if !bucket.is_empty() {
+                stats.occupied_cells += 1;
+            }
+            stats.longest_chain = stats.longest_chain.max(bucket.len());
+            for entry in bucket {
+                stats.key_bytes += entry.shared + entry.suffix.len();
+                stats.stored_key_bytes += entry.suffix.len();
+                stats.value_bytes += entry.value.len();
+            }
+        }
+        stats.stored_value_bytes = stats.value_bytes;
+        stats
+    }
+
+    /// Finds `key` in a bucket by walking its front-coded keys, without decoding them.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(position)` if the key is stored at `position`.
+    /// * `Err(Vacancy)` describing where the key would be inserted otherwise.
+    fn search(bucket: &[CompressedEntry], key: &[u8]) -> Result<usize, Vacancy> {
+        // Length of the prefix shared by `key` and the previous entry, which sorts before `key`.
+        let mut matched = 0;
+
+        for (position, entry) in bucket.iter().enumerate() {
+            match entry.shared.cmp(&matched) {
+                // The entry diverges from the previous key earlier, so it sorts after `key`.
+                Ordering::Less => {
+                    return Err(Vacancy {
+                        position,
+                        shared: matched,
+                        next_shared: entry.shared,
+                    })
+                }
+                // The entry keeps the previous key's byte that was smaller than `key`'s.
+                Ordering::Greater => continue,
+                Ordering::Equal => {}
+            }
+
+            let rest = &key[matched..];
+            let common = entry
+                .suffix
+                .iter()
+                .zip(rest)
+                .take_while(|(a, b)| a == b)
+                .count();
+            if common == entry.suffix.len() && common == rest.len() {
+                return Ok(position);
+            }
+            let before_key = common == entry.suffix.len()
+                || (common < rest.len() && entry.suffix[common] < rest[common]);
+            if !before_key {
+                return Err(Vacancy {
+                    position,
+                    shared: matched,
+                    next_shared: matched + common,
+                });
+            }
+            matched += common;
+        }
+
+        Err(Vacancy {
+            position: bucket.len(),
+            shared: matched,
+            next_shared: 0,
+        })
+    }
+
+    /// Doubles the number of buckets and redistributes every entry.
+    fn resize(&mut self) {
+        let new_cap = self.buckets.len() * 2;
+        let mut decoded: Vec<Vec<(Vec<u8>, Vec<u8>)>> = vec![Vec::new(); new_cap];
+        for bucket in std::mem::take(&mut self.buckets) {
+            for (key, value) in Self::decode(bucket) {
+                decoded[ByteBox::hash(&key, new_cap)].push((key, value));
+            }
+        }
+        self.buckets = decoded
+            .into_iter()
+            .map(|mut entries| {
+                entries.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+                Self::encode(entries)
+            })
+            .collect();
+    }
+
+    /// Restores the full keys of a bucket, moving the values out of it.
+    fn decode(bucket: Vec<CompressedEntry>) -> Vec<(Vec<u8>, Vec<u8>)> {
+        let mut key = Vec::new();
+        bucket
+            .into_iter()
+            .map(|entry| {
+                key.truncate(entry.shared);
+                key.extend_from_slice(&entry.suffix);
+                (key.clone(), entry.value)
+            })
+            .collect()
+    }
+
+    /// Front-codes entries that are already sorted by key.
+    fn encode(entries: Vec<(Vec<u8>, Vec<u8>)>) -> Vec<CompressedEntry> {
+        let mut previous: Vec<u8> = Vec::new();
+        entries
+            .into_iter()
+            .map(|(key, value)| {
+                let shared = previous
+                    .iter()
+                    .zip(&key)
+                    .take_while(|(a, b)| a == b)
+                    .count();
+                let suffix = key[shared..].to_vec();
+                previous = key;
+                CompressedEntry {
+                    shared,
+                    suffix,
+                    value,
+                }
+            })
+            .collect()
+    }
+}
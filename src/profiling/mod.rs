@@ -0,0 +1,216 @@
+//! Per-operation latency histograms, enabled by the `profiling` feature.
+use super::*;
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// Number of histogram buckets; bucket `i` counts latencies below `2^i` nanoseconds that
+/// were not counted by bucket `i - 1`.
+pub const LATENCY_BUCKETS: usize = 64;
+
+/// The operations whose latency is recorded.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Op {
+    Insert,
+    Get,
+    Remove,
+}
+
+/// A histogram with power-of-two nanosecond buckets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LatencyHistogram {
+    buckets: [u64; LATENCY_BUCKETS],
+    /// Number of recorded operations.
+    pub count: u64,
+    /// Sum of all recorded latencies.
+    pub total: Duration,
+    /// Largest recorded latency.
+    pub max: Duration,
+}
+
+impl LatencyHistogram {
+    /// Returns the number of operations in every bucket.
+    pub fn buckets(&self) -> &[u64; LATENCY_BUCKETS] {
+        &self.buckets
+    }
+
+    /// Returns the mean latency, or zero if nothing was recorded.
+    pub fn mean(&self) -> Duration {
+        match self.count {
+            0 => Duration::ZERO,
+            count => self.total / count.min(u32::MAX as u64) as u32,
+        }
+    }
+
+    /// Returns an upper bound of the given percentile, the upper edge of its bucket.
+    ///
+    /// # Arguments
+    ///
+    /// * `percentile` - Between `0.0` and `100.0`.
+    pub fn percentile(&self, percentile: f64) -> Duration {
+        let rank = ((percentile / 100.0) * self.count as f64).ceil().max(1.0) as u64;
+        let mut seen = 0;
+        for (bucket, &count) in self.buckets.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                return Duration::from_nanos(bucket_limit(bucket)).min(self.max);
+            }
+        }
+        self.max
+    }
+}
+
+fn bucket_limit(bucket: usize) -> u64 {
+    match bucket {
+        0 => 0,
+        bucket => 1u64.checked_shl(bucket as u32).unwrap_or(u64::MAX),
+    }
+}
+
+/// Latency histograms of a `ByteBox`, returned by [`ByteBox::op_latency_stats`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpLatencyStats {
+    /// Inserts that added a new key.
+    pub insert_new: LatencyHistogram,
+    /// Inserts that updated an existing key.
+    pub insert_update: LatencyHistogram,
+    /// Lookups that found the key.
+    pub get_hit: LatencyHistogram,
+    /// Lookups that did not find the key.
+    pub get_miss: LatencyHistogram,
+    /// Removals that found the key.
+    pub remove_hit: LatencyHistogram,
+    /// Removals that did not find the key.
+    pub remove_miss: LatencyHistogram,
+}
+
+#[derive(Debug)]
+struct AtomicHistogram {
+    buckets: [AtomicU64; LATENCY_BUCKETS],
+    count: AtomicU64,
+    total_nanos: AtomicU64,
+    max_nanos: AtomicU64,
+}
+
+impl Default for AtomicHistogram {
+    fn default() -> Self {
+        AtomicHistogram {
+            buckets: std::array::from_fn(|_| AtomicU64::new(0)),
+            count: AtomicU64::new(0),
+            total_nanos: AtomicU64::new(0),
+            max_nanos: AtomicU64::new(0),
+        }
+    }
+}
+
+impl AtomicHistogram {
+    fn record(&self, nanos: u64) {
+        let bucket = (u64::BITS - nanos.leading_zeros()) as usize;
+        self.buckets[bucket.min(LATENCY_BUCKETS - 1)].fetch_add(1, Ordering::Relaxed);
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.total_nanos.fetch_add(nanos, Ordering::Relaxed);
+        self.max_nanos.fetch_max(nanos, Ordering::Relaxed);
+    }
+
+    fn snapshot(&self) -> LatencyHistogram {
+        LatencyHistogram {
+            buckets: std::array::from_fn(|bucket| self.buckets[bucket].load(Ordering::Relaxed)),
+            count: self.count.load(Ordering::Relaxed),
+            total: Duration::from_nanos(self.total_nanos.load(Ordering::Relaxed)),
+            max: Duration::from_nanos(self.max_nanos.load(Ordering::Relaxed)),
+        }
+    }
+
+    fn restore(histogram: &LatencyHistogram) -> Self {
+        AtomicHistogram {
+            buckets: std::array::from_fn(|bucket| AtomicU64::new(histogram.buckets[bucket])),
+            count: AtomicU64::new(histogram.count),
+            total_nanos: AtomicU64::new(histogram.total.as_nanos() as u64),
+            max_nanos: AtomicU64::new(histogram.max.as_nanos() as u64),
+        }
+    }
+}
+
+/// The histograms embedded in a `ByteBox`. Counters are atomic so that `get`, which only
+/// borrows the table, can record without making `ByteBox` lose `Sync`.
+#[derive(Debug, Default)]
+pub(crate) struct Profiler {
+    // Indexed by `Op as usize * 2 + hit as usize`.
+    histograms: [AtomicHistogram; 6],
+}
+
+impl Clone for Profiler {
+    fn clone(&self) -> Self {
+        Profiler {
+            histograms: std::array::from_fn(|index| {
+                AtomicHistogram::restore(&self.histograms[index].snapshot())
+            }),
+        }
+    }
+}
+
+impl Profiler {
+    pub(crate) fn record(&self, op: Op, hit: bool, started: Instant) {
+        let nanos = u64::try_from(started.elapsed().as_nanos()).unwrap_or(u64::MAX);
+        self.histograms[op as usize * 2 + hit as usize].record(nanos);
+    }
+
+    /// Records one operation per item of `hits`, splitting the time elapsed since
+    /// `started` evenly between them.
+    pub(crate) fn record_batch<I>(&self, op: Op, hits: I, started: Instant)
+    where
+        I: ExactSizeIterator<Item = bool>,
+    {
+        let nanos = u64::try_from(started.elapsed().as_nanos()).unwrap_or(u64::MAX);
+        let each = nanos / hits.len().max(1) as u64;
+        for hit in hits {
+            self.histograms[op as usize * 2 + hit as usize].record(each);
+        }
+    }
+
+    fn stats(&self) -> OpLatencyStats {
+        let [insert_new, insert_update, get_miss, get_hit, remove_miss, remove_hit] =
+            std::array::from_fn(|index| self.histograms[index].snapshot());
+        OpLatencyStats {
+            insert_new,
+            insert_update,
+            get_hit,
+            get_miss,
+            remove_hit,
+            remove_miss,
+        }
+    }
+}
+
+impl ByteBox {
+    /// Returns the latency histograms of every `insert`, `get` and `remove` so far.
+    ///
+    /// Methods built on these operations, such as `get_range` or `insert_with_policy` for
+    /// a new key, are recorded under the operation they use. `get_many` records one `get`
+    /// per key, splitting the batch's time evenly between them. Existence checks such as
+    /// `contains_key` and `test_bit` are not recorded.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut bytebox = ByteBox::new();
+    /// bytebox.insert(b"key", b"value");
+    /// bytebox.get(b"key");
+    /// bytebox.get(b"missing");
+    ///
+    /// let stats = bytebox.op_latency_stats();
+    /// assert_eq!(stats.insert_new.count, 1);
+    /// assert_eq!((stats.get_hit.count, stats.get_miss.count), (1, 1));
+    /// println!("p99 get: {:?}", stats.get_hit.percentile(99.0));
+    /// ```
+    pub fn op_latency_stats(&self) -> OpLatencyStats {
+        self.profiler.stats()
+    }
+
+    /// Clears every latency histogram.
+    pub fn reset_latency_stats(&mut self) {
+        self.profiler = Profiler::default();
+    }
+}
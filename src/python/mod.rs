@@ -0,0 +1,131 @@
+//! Python bindings for `ByteBox`, enabled by the `python` feature.
+//!
+//! The extension module is named `bytesbox` and exposes a `ByteBox` class implementing
+//! the mapping protocol with `bytes` keys and values:
+//!
+//! ```python
+//! from bytesbox import ByteBox
+//!
+//! table = ByteBox()
+//! table[b"a"] = b"1"
+//! table[b"b"] = b"2"
+//! assert sorted(table) == [b"a", b"b"]
+//! assert dict(table) == {b"a": b"1", b"b": b"2"}
+//! ```
+use super::*;
+
+use pyo3::exceptions::PyKeyError;
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyIterator, PyList};
+// `Bound` is also glob-imported from `std::ops` through `super::*`.
+use pyo3::Bound;
+
+/// A `ByteBox` exposed to Python as a `bytes`-keyed mapping.
+#[pyclass(name = "ByteBox", skip_from_py_object)]
+#[derive(Debug, Clone, Default)]
+pub struct PyByteBox {
+    inner: ByteBox,
+}
+
+#[pymethods]
+impl PyByteBox {
+    #[new]
+    fn new() -> Self {
+        PyByteBox::default()
+    }
+
+    fn __len__(&self) -> usize {
+        self.inner.len()
+    }
+
+    fn __contains__(&self, key: &[u8]) -> bool {
+        self.inner.contains_key(key)
+    }
+
+    fn __getitem__<'py>(&self, py: Python<'py>, key: &[u8]) -> PyResult<Bound<'py, PyBytes>> {
+        self.inner
+            .get(key)
+            .map(|value| PyBytes::new(py, value))
+            .ok_or_else(|| PyKeyError::new_err(key.to_vec()))
+    }
+
+    fn __setitem__(&mut self, key: &[u8], value: &[u8]) {
+        self.inner.insert(key, value);
+    }
+
+    fn __delitem__(&mut self, key: &[u8]) -> PyResult<()> {
+        self.inner
+            .remove(key)
+            .map(|_| ())
+            .ok_or_else(|| PyKeyError::new_err(key.to_vec()))
+    }
+
+    /// Returns the value for `key`, or `default` when it is missing.
+    #[pyo3(signature = (key, default = None))]
+    fn get<'py>(
+        &self,
+        py: Python<'py>,
+        key: &[u8],
+        default: Option<Bound<'py, PyAny>>,
+    ) -> Option<Bound<'py, PyAny>> {
+        match self.inner.get(key) {
+            Some(value) => Some(PyBytes::new(py, value).into_any()),
+            None => default,
+        }
+    }
+
+    /// Iterates over a snapshot of the keys, so the table may change during the loop.
+    fn __iter__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyIterator>> {
+        PyList::new(py, self.keys(py))?.try_iter()
+    }
+
+    /// Returns the stored keys as a list of `bytes`.
+    fn keys<'py>(&self, py: Python<'py>) -> Vec<Bound<'py, PyBytes>> {
+        self.inner
+            .iter()
+            .map(|(key, _)| PyBytes::new(py, key))
+            .collect()
+    }
+
+    /// Returns the stored values as a list of `bytes`.
+    fn values<'py>(&self, py: Python<'py>) -> Vec<Bound<'py, PyBytes>> {
+        self.inner
+            .iter()
+            .map(|(_, value)| PyBytes::new(py, value))
+            .collect()
+    }
+
+    /// Returns the stored pairs as a list of `(bytes, bytes)` tuples.
+    fn items<'py>(&self, py: Python<'py>) -> Vec<(Bound<'py, PyBytes>, Bound<'py, PyBytes>)> {
+        self.inner
+            .iter()
+            .map(|(key, value)| (PyBytes::new(py, key), PyBytes::new(py, value)))
+            .collect()
+    }
+
+    fn clear(&mut self) {
+        self.inner.clear();
+    }
+
+    fn __repr__(&self) -> String {
+        format!("ByteBox({})", self.inner)
+    }
+}
+
+impl From<ByteBox> for PyByteBox {
+    fn from(inner: ByteBox) -> Self {
+        PyByteBox { inner }
+    }
+}
+
+impl From<PyByteBox> for ByteBox {
+    fn from(table: PyByteBox) -> Self {
+        table.inner
+    }
+}
+
+/// The `bytesbox` Python extension module.
+#[pymodule]
+fn bytesbox(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyByteBox>()
+}
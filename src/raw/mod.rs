@@ -0,0 +1,78 @@
+//! A flat offsets-and-bytes export of a table's entries.
+
+/// A flat, position-independent copy of the entries of a `ByteBox`.
+///
+/// The layout is stable and meant to be read from other languages:
+///
+/// * `bytes` holds every key and value back to back, with no separators.
+/// * `offsets` holds `2 * len + 1` increasing byte offsets into `bytes`, starting at `0`.
+///   Entry `i` has its key at `bytes[offsets[2 * i]..offsets[2 * i + 1]]` and its value at
+///   `bytes[offsets[2 * i + 1]..offsets[2 * i + 2]]`.
+///
+/// Entries appear in the table's iteration order.
+///
+/// This struct is created by the [`ByteBox::export_raw`](crate::ByteBox::export_raw) method.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawExport {
+    /// Boundaries of every key and value inside `bytes`.
+    pub offsets: Vec<u64>,
+    /// The concatenated keys and values.
+    pub bytes: Vec<u8>,
+}
+
+impl RawExport {
+    pub(crate) fn with_capacity(entries: usize, bytes: usize) -> Self {
+        let mut offsets = Vec::with_capacity(entries * 2 + 1);
+        offsets.push(0);
+        RawExport {
+            offsets,
+            bytes: Vec::with_capacity(bytes),
+        }
+    }
+
+    pub(crate) fn push(&mut self, key: &[u8], value: &[u8]) {
+        self.bytes.extend_from_slice(key);
+        self.offsets.push(self.bytes.len() as u64);
+        self.bytes.extend_from_slice(value);
+        self.offsets.push(self.bytes.len() as u64);
+    }
+
+    /// Returns the number of entries in the export.
+    pub fn len(&self) -> usize {
+        self.offsets.len() / 2
+    }
+
+    /// Returns `true` if the export holds no entries.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the key and value of entry `index`, or `None` if it is out of range.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut bytebox = ByteBox::new();
+    /// bytebox.insert(b"key", b"value");
+    ///
+    /// let raw = bytebox.export_raw();
+    /// assert_eq!(raw.entry(0), Some((&b"key"[..], &b"value"[..])));
+    /// assert_eq!(raw.entry(1), None);
+    /// ```
+    pub fn entry(&self, index: usize) -> Option<(&[u8], &[u8])> {
+        if index >= self.len() {
+            return None;
+        }
+        let start = self.offsets[2 * index] as usize;
+        let middle = self.offsets[2 * index + 1] as usize;
+        let end = self.offsets[2 * index + 2] as usize;
+        Some((&self.bytes[start..middle], &self.bytes[middle..end]))
+    }
+
+    /// Returns an iterator over the exported key-value pairs.
+    pub fn iter(&self) -> impl Iterator<Item = (&[u8], &[u8])> + '_ {
+        (0..self.len()).filter_map(move |index| self.entry(index))
+    }
+}
@@ -0,0 +1,232 @@
+//! Fixed-layout binary records stored as `ByteBox` values.
+//!
+//! A record is a struct whose fields are packed one after the other at fixed widths,
+//! integers and floats in little-endian order. With the `derive` feature,
+//! `#[derive(ByteRecord)]` implements the packing for plain structs of primitives, byte
+//! arrays and other records.
+use super::*;
+
+use std::slice::ChunksExact;
+
+#[cfg(feature = "derive")]
+pub use bytesbox_derive::ByteRecord;
+
+/// A value with a fixed-width encoding that can be a field of a [`ByteRecord`].
+///
+/// Implemented for every primitive integer and float type, `bool`, `[u8; N]`, and every
+/// type deriving [`ByteRecord`].
+pub trait RecordField: Sized {
+    /// Size of the encoded field, in bytes.
+    const WIDTH: usize;
+
+    /// Appends exactly [`Self::WIDTH`] bytes encoding the field to `out`.
+    fn write_field(&self, out: &mut Vec<u8>);
+
+    /// Decodes the field from exactly [`Self::WIDTH`] bytes.
+    fn read_field(bytes: &[u8]) -> Self;
+}
+
+macro_rules! impl_record_field {
+    ($($ty:ty),*) => {
+        $(
+            impl RecordField for $ty {
+                const WIDTH: usize = std::mem::size_of::<$ty>();
+
+                fn write_field(&self, out: &mut Vec<u8>) {
+                    out.extend_from_slice(&self.to_le_bytes());
+                }
+
+                fn read_field(bytes: &[u8]) -> Self {
+                    let mut buf = [0u8; std::mem::size_of::<$ty>()];
+                    buf.copy_from_slice(bytes);
+                    <$ty>::from_le_bytes(buf)
+                }
+            }
+        )*
+    };
+}
+
+impl_record_field!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
+
+impl RecordField for bool {
+    const WIDTH: usize = 1;
+
+    fn write_field(&self, out: &mut Vec<u8>) {
+        out.push(*self as u8);
+    }
+
+    fn read_field(bytes: &[u8]) -> Self {
+        bytes[0] != 0
+    }
+}
+
+impl<const N: usize> RecordField for [u8; N] {
+    const WIDTH: usize = N;
+
+    fn write_field(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(self);
+    }
+
+    fn read_field(bytes: &[u8]) -> Self {
+        let mut buf = [0u8; N];
+        buf.copy_from_slice(bytes);
+        buf
+    }
+}
+
+/// A struct stored in a `ByteBox` as a fixed-layout binary value.
+///
+/// Usually derived with `#[derive(ByteRecord)]` (feature `derive`), which packs the fields
+/// in declaration order.
+///
+/// # Examples
+///
+/// ```rust
+/// use bytesbox::error::RecordSizeMismatch;
+/// use bytesbox::record::{ByteRecord, RecordField};
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Sample {
+///     ts: u64,
+///     level: u8,
+/// }
+///
+/// impl ByteRecord for Sample {
+///     const SIZE: usize = 9;
+///
+///     fn to_bytes(&self) -> Vec<u8> {
+///         let mut out = Vec::with_capacity(Self::SIZE);
+///         self.ts.write_field(&mut out);
+///         self.level.write_field(&mut out);
+///         out
+///     }
+///
+///     fn from_bytes(bytes: &[u8]) -> Result<Self, RecordSizeMismatch> {
+///         if bytes.len() != Self::SIZE {
+///             return Err(RecordSizeMismatch { expected: Self::SIZE, found: bytes.len() });
+///         }
+///         Ok(Sample {
+///             ts: u64::read_field(&bytes[..8]),
+///             level: u8::read_field(&bytes[8..]),
+///         })
+///     }
+/// }
+///
+/// let sample = Sample { ts: 1_700_000_000, level: 3 };
+/// assert_eq!(Sample::from_bytes(&sample.to_bytes()), Ok(sample));
+/// ```
+pub trait ByteRecord: Sized {
+    /// Size of the encoded record, in bytes.
+    const SIZE: usize;
+
+    /// Encodes the record into exactly [`Self::SIZE`] bytes.
+    fn to_bytes(&self) -> Vec<u8>;
+
+    /// Decodes a record.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RecordSizeMismatch`] if `bytes` is not exactly [`Self::SIZE`] bytes long.
+    fn from_bytes(bytes: &[u8]) -> Result<Self, RecordSizeMismatch>;
+}
+
+impl ByteBox {
+    /// Inserts `record` under `key`, encoded with [`ByteRecord::to_bytes`].
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - A byte slice representing the key.
+    /// * `record` - The record to store.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if a new key-value pair was inserted.
+    /// * `false` if an existing key was updated.
+    pub fn insert_record<T: ByteRecord>(&mut self, key: &[u8], record: &T) -> bool {
+        self.insert(key, &record.to_bytes())
+    }
+
+    /// Decodes the value stored under `key` as a `T`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(T))` if the key exists.
+    /// * `Ok(None)` if the key does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RecordSizeMismatch`] if the stored value does not have the size of a `T`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "derive")]
+    /// # {
+    /// use bytesbox::record::ByteRecord;
+    /// use bytesbox::ByteBox;
+    ///
+    /// #[derive(ByteRecord, Debug, PartialEq)]
+    /// struct Reading {
+    ///     ts: u64,
+    ///     celsius: f32,
+    ///     sensor: [u8; 4],
+    /// }
+    ///
+    /// let reading = Reading { ts: 1_700_000_000, celsius: 21.5, sensor: *b"t-01" };
+    /// let mut bytebox = ByteBox::new();
+    /// bytebox.insert_record(b"last", &reading);
+    ///
+    /// assert_eq!(bytebox.get_record::<Reading>(b"last"), Ok(Some(reading)));
+    /// assert_eq!(bytebox.get_record::<Reading>(b"missing"), Ok(None));
+    /// # }
+    /// ```
+    pub fn get_record<T: ByteRecord>(&self, key: &[u8]) -> Result<Option<T>, RecordSizeMismatch> {
+        self.get(key).map(T::from_bytes).transpose()
+    }
+
+    /// Returns the fixed-width records packed one after the other in the value under `key`.
+    ///
+    /// The records are borrowed from the table, so a per-sensor buffer of samples can be
+    /// consumed without copying. Trailing bytes that do not form a whole record are left
+    /// out of the iteration and available through `remainder()`; the iterator also runs
+    /// backwards and knows its length.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - A byte slice representing the key.
+    /// * `record_size` - The width of a record, in bytes.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(ChunksExact)` iterating over the records if the key exists.
+    /// * `None` if the key does not exist.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `record_size` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut bytebox = ByteBox::new();
+    /// let mut samples = Vec::new();
+    /// for reading in [20u16, 21, 23] {
+    ///     samples.extend_from_slice(&reading.to_le_bytes());
+    /// }
+    /// bytebox.insert(b"sensor:1", &samples);
+    ///
+    /// let readings: Vec<u16> = bytebox
+    ///     .get_records(b"sensor:1", 2)
+    ///     .unwrap()
+    ///     .map(|record| u16::from_le_bytes([record[0], record[1]]))
+    ///     .collect();
+    /// assert_eq!(readings, vec![20, 21, 23]);
+    /// assert!(bytebox.get_records(b"sensor:2", 2).is_none());
+    /// ```
+    pub fn get_records(&self, key: &[u8], record_size: usize) -> Option<ChunksExact<'_, u8>> {
+        assert!(record_size > 0, "record size must be non-zero");
+        self.get(key).map(|value| value.chunks_exact(record_size))
+    }
+}
@@ -0,0 +1,221 @@
+//! Consistent-hashing router over several `ByteBox` shards.
+use super::*;
+
+/// Number of points every shard gets on the ring of a [`ByteBoxRouter::new`] router.
+pub const DEFAULT_VIRTUAL_NODES: usize = 128;
+
+/// Key of the ring hash, so that ring positions are independent of the cell a key gets
+/// inside its shard.
+const RING_SEED: u64 = 0x9e37_79b9_7f4a_7c15;
+
+/// Identifies a shard of a [`ByteBoxRouter`]. Identifiers are never reused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ShardId(pub u64);
+
+/// A set of `ByteBox` shards with keys assigned by consistent hashing.
+///
+/// Every shard owns `virtual_nodes` points on a 64-bit hash ring and a key belongs to the
+/// shard owning the first point at or after the key's hash. Adding or removing a shard
+/// therefore only moves the keys of the ring arcs that change hands, about `1 / n` of the
+/// entries, which keeps cache frontends warm while they scale.
+///
+/// Ring positions are SipHash-1-3 keyed with a fixed constant, so every build and platform
+/// routes a key to the same shard and several processes can share one assignment.
+///
+/// # Examples
+///
+/// ```rust
+/// use bytesbox::router::ByteBoxRouter;
+///
+/// let mut router = ByteBoxRouter::with_shards(3);
+/// for i in 0..1000 {
+///     router.insert(format!("key{}", i).as_bytes(), b"value");
+/// }
+///
+/// let moved = router.add_shard().1;
+/// assert!(moved > 0 && moved < 500);
+/// assert_eq!(router.len(), 1000);
+/// assert_eq!(router.get(b"key42"), Some(&b"value"[..]));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ByteBoxRouter {
+    shards: Vec<(ShardId, ByteBox)>,
+    /// Ring points sorted by position.
+    ring: Vec<(u64, ShardId)>,
+    virtual_nodes: usize,
+    next_id: u64,
+}
+
+impl Default for ByteBoxRouter {
+    /// Creates a router without shards, same as [`ByteBoxRouter::new`].
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ByteBoxRouter {
+    /// Creates a router without shards, using [`DEFAULT_VIRTUAL_NODES`] points per shard.
+    pub fn new() -> Self {
+        Self::with_virtual_nodes(DEFAULT_VIRTUAL_NODES)
+    }
+
+    /// Creates a router without shards, giving every shard `virtual_nodes` ring points.
+    ///
+    /// More points spread the keys more evenly at the cost of a larger ring.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `virtual_nodes` is zero.
+    pub fn with_virtual_nodes(virtual_nodes: usize) -> Self {
+        assert!(virtual_nodes > 0, "a shard needs at least one virtual node");
+        ByteBoxRouter {
+            shards: Vec::new(),
+            ring: Vec::new(),
+            virtual_nodes,
+            next_id: 0,
+        }
+    }
+
+    /// Creates a router with `count` empty shards.
+    pub fn with_shards(count: usize) -> Self {
+        let mut router = Self::new();
+        for _ in 0..count {
+            router.add_shard();
+        }
+        router
+    }
+
+    /// Returns the total number of entries across the shards.
+    pub fn len(&self) -> usize {
+        self.shards.iter().map(|(_, shard)| shard.len()).sum()
+    }
+
+    /// Returns `true` if no shard holds an entry.
+    pub fn is_empty(&self) -> bool {
+        self.shards.iter().all(|(_, shard)| shard.is_empty())
+    }
+
+    /// Returns the identifiers of the shards, in the order they were added.
+    pub fn shard_ids(&self) -> impl Iterator<Item = ShardId> + '_ {
+        self.shards.iter().map(|(id, _)| *id)
+    }
+
+    /// Returns the shard with identifier `id`, if it is part of the router.
+    pub fn shard(&self, id: ShardId) -> Option<&ByteBox> {
+        self.shards
+            .iter()
+            .find(|(shard_id, _)| *shard_id == id)
+            .map(|(_, shard)| shard)
+    }
+
+    /// Returns the shard `key` is routed to, or `None` if the router has no shard.
+    pub fn shard_for(&self, key: &[u8]) -> Option<ShardId> {
+        owner(&self.ring, key)
+    }
+
+    /// Inserts a key-value pair into the shard owning `key`.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if a new key-value pair was inserted.
+    /// * `false` if an existing key was updated.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the router has no shard.
+    pub fn insert(&mut self, key: &[u8], value: &[u8]) -> bool {
+        let id = self.shard_for(key).expect("router has no shard");
+        self.shard_mut(id).insert(key, value)
+    }
+
+    /// Returns the value stored under `key` in the shard owning it.
+    pub fn get(&self, key: &[u8]) -> Option<&[u8]> {
+        self.shard(self.shard_for(key)?)?.get(key)
+    }
+
+    /// Removes `key` from the shard owning it, returning its value.
+    pub fn remove(&mut self, key: &[u8]) -> Option<Vec<u8>> {
+        let id = self.shard_for(key)?;
+        self.shard_mut(id).remove(key)
+    }
+
+    /// Adds an empty shard and moves to it the entries of the ring arcs it takes over.
+    ///
+    /// # Returns
+    ///
+    /// The identifier of the new shard and the number of entries moved to it.
+    pub fn add_shard(&mut self) -> (ShardId, usize) {
+        let id = ShardId(self.next_id);
+        self.next_id += 1;
+        for vnode in 0..self.virtual_nodes {
+            self.ring.push((ring_point(id, vnode), id));
+        }
+        self.ring.sort_unstable();
+
+        let mut shard = ByteBox::new();
+        for (_, other) in &mut self.shards {
+            for (key, value) in other.extract_if(|key, _| owner(&self.ring, key) == Some(id)) {
+                shard.insert(&key, &value);
+            }
+        }
+        let moved = shard.len();
+        self.shards.push((id, shard));
+        (id, moved)
+    }
+
+    /// Removes the shard `id`, moving its entries to the shards that take over its arcs.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(ByteBox)` holding the removed shard. It is empty unless it was the last
+    ///   shard, in which case it keeps every entry.
+    /// * `None` if `id` is not part of the router.
+    pub fn remove_shard(&mut self, id: ShardId) -> Option<ByteBox> {
+        let position = self
+            .shards
+            .iter()
+            .position(|(shard_id, _)| *shard_id == id)?;
+        let (_, mut removed) = self.shards.remove(position);
+        self.ring.retain(|(_, owner)| *owner != id);
+        if self.shards.is_empty() {
+            return Some(removed);
+        }
+
+        for (key, value) in removed.extract_if(|_, _| true) {
+            let owner = owner(&self.ring, &key).expect("router has a shard");
+            self.shard_mut(owner).insert(&key, &value);
+        }
+        Some(removed)
+    }
+
+    fn shard_mut(&mut self, id: ShardId) -> &mut ByteBox {
+        self.shards
+            .iter_mut()
+            .find(|(shard_id, _)| *shard_id == id)
+            .map(|(_, shard)| shard)
+            .expect("ring points at a shard of the router")
+    }
+}
+
+/// Returns the position of virtual node `vnode` of shard `id` on the ring.
+fn ring_point(id: ShardId, vnode: usize) -> u64 {
+    let mut point = [0u8; 16];
+    point[..8].copy_from_slice(&id.0.to_le_bytes());
+    point[8..].copy_from_slice(&(vnode as u64).to_le_bytes());
+    ring_hash(&point)
+}
+
+/// Returns the shard owning the first ring point at or after the hash of `key`.
+fn owner(ring: &[(u64, ShardId)], key: &[u8]) -> Option<ShardId> {
+    let hash = ring_hash(key);
+    let index = ring.partition_point(|(point, _)| *point < hash);
+    ring.get(index).or_else(|| ring.first()).map(|(_, id)| *id)
+}
+
+/// Hashes `bytes` onto the ring with a specified function, unlike the cell hash of a
+/// `ByteBox`, which is only stable within one build.
+fn ring_hash(bytes: &[u8]) -> u64 {
+    let mut hasher = SipHasher13::new_with_keys(RING_SEED, 0);
+    hasher.write(bytes);
+    hasher.finish()
+}
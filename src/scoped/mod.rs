@@ -0,0 +1,89 @@
+//! Temporary insertions undone when a guard goes out of scope.
+use super::*;
+
+use std::ops::{Deref, DerefMut};
+
+/// Restores a key to its previous state when dropped.
+///
+/// This struct is created by [`ByteBox::insert_scoped`]. It borrows the table mutably and
+/// dereferences to it, so the table stays usable through the guard while the temporary
+/// entry is in place.
+#[derive(Debug)]
+pub struct ScopedEntryGuard<'a> {
+    byte_box: &'a mut ByteBox,
+    key: Vec<u8>,
+    previous: Option<Vec<u8>>,
+}
+
+impl ScopedEntryGuard<'_> {
+    /// Returns the value the key held before the scoped insert, if any.
+    pub fn previous(&self) -> Option<&[u8]> {
+        self.previous.as_deref()
+    }
+}
+
+impl Deref for ScopedEntryGuard<'_> {
+    type Target = ByteBox;
+
+    fn deref(&self) -> &ByteBox {
+        self.byte_box
+    }
+}
+
+impl DerefMut for ScopedEntryGuard<'_> {
+    fn deref_mut(&mut self) -> &mut ByteBox {
+        self.byte_box
+    }
+}
+
+impl Drop for ScopedEntryGuard<'_> {
+    /// Puts the previous value back, or removes the key if it did not exist.
+    fn drop(&mut self) {
+        match self.previous.take() {
+            Some(previous) => {
+                self.byte_box.insert(&self.key, &previous);
+            }
+            None => {
+                self.byte_box.remove(&self.key);
+            }
+        }
+    }
+}
+
+impl ByteBox {
+    /// Inserts a key-value pair for as long as the returned guard lives.
+    ///
+    /// When the guard is dropped the key gets its previous value back, or is removed if it
+    /// did not exist before. Changes made to the key through the guard in the meantime are
+    /// discarded as well.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - A byte slice representing the key.
+    /// * `value` - The temporary value.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut config = ByteBox::new();
+    /// config.insert(b"log-level", b"info");
+    ///
+    /// {
+    ///     let guard = config.insert_scoped(b"log-level", b"trace");
+    ///     assert_eq!(guard.get(b"log-level"), Some(&b"trace"[..]));
+    ///     assert_eq!(guard.previous(), Some(&b"info"[..]));
+    /// }
+    /// assert_eq!(config.get(b"log-level"), Some(&b"info"[..]));
+    /// ```
+    pub fn insert_scoped(&mut self, key: &[u8], value: &[u8]) -> ScopedEntryGuard<'_> {
+        let previous = self.lookup(key).map(<[u8]>::to_vec);
+        self.insert(key, value);
+        ScopedEntryGuard {
+            byte_box: self,
+            key: key.to_vec(),
+            previous,
+        }
+    }
+}
@@ -0,0 +1,106 @@
+//! Substring search over stored values.
+use super::*;
+
+use std::sync::Arc;
+
+/// Returns the offset of every occurrence of `needle` in `haystack`, in increasing order.
+///
+/// Overlapping occurrences are all reported. The scan is Knuth-Morris-Pratt: it reads
+/// each haystack byte once and never backs up, so it runs in `O(haystack + needle)` time
+/// whatever the input, after building a table of `needle.len()` offsets. An empty needle
+/// matches nothing.
+///
+/// # Examples
+///
+/// ```rust
+/// use bytesbox::search::find_all;
+///
+/// let offsets: Vec<usize> = find_all(b"abababa", b"aba").collect();
+/// assert_eq!(offsets, vec![0, 2, 4]);
+/// assert_eq!(find_all(b"abc", b"").count(), 0);
+/// ```
+pub fn find_all<'a>(haystack: &'a [u8], needle: &'a [u8]) -> impl Iterator<Item = usize> + 'a {
+    matches(haystack, needle, failure_table(needle).into())
+}
+
+/// Runs the Knuth-Morris-Pratt scan of `haystack` with the `failure` table of `needle`.
+fn matches<'a>(
+    haystack: &'a [u8],
+    needle: &'a [u8],
+    failure: Arc<[usize]>,
+) -> impl Iterator<Item = usize> + 'a {
+    let mut position = 0;
+    // Length of the needle prefix ending just before `position`.
+    let mut matched = 0;
+    std::iter::from_fn(move || {
+        if needle.is_empty() {
+            return None;
+        }
+        while let Some(&byte) = haystack.get(position) {
+            while matched > 0 && needle[matched] != byte {
+                matched = failure[matched - 1];
+            }
+            if needle[matched] == byte {
+                matched += 1;
+            }
+            position += 1;
+            if matched == needle.len() {
+                matched = failure[matched - 1];
+                return Some(position - needle.len());
+            }
+        }
+        None
+    })
+}
+
+/// Returns, for every prefix `needle[..=i]`, the length of its longest proper prefix
+/// that is also a suffix.
+fn failure_table(needle: &[u8]) -> Vec<usize> {
+    let mut failure = vec![0; needle.len()];
+    let mut matched = 0;
+    for (i, &byte) in needle.iter().enumerate().skip(1) {
+        while matched > 0 && needle[matched] != byte {
+            matched = failure[matched - 1];
+        }
+        if needle[matched] == byte {
+            matched += 1;
+        }
+        failure[i] = matched;
+    }
+    failure
+}
+
+impl ByteBox {
+    /// Returns an iterator over every occurrence of `needle` in the stored values, as the
+    /// key of the value and the offset of the occurrence in it.
+    ///
+    /// Values are scanned in iteration order, each with [`find_all`], so a value holding
+    /// the needle several times yields one item per occurrence. The needle's table is
+    /// built once for the whole scan, which reads every value once and copies nothing.
+    ///
+    /// # Arguments
+    ///
+    /// * `needle` - The byte string to look for; an empty needle matches nothing.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut cache = ByteBox::new();
+    /// cache.insert(b"resp:1", b"{\"token\":\"sk-leak\"}");
+    /// cache.insert(b"resp:2", b"{\"ok\":true}");
+    ///
+    /// let hits: Vec<(&[u8], usize)> = cache.find_in_values(b"sk-leak").collect();
+    /// assert_eq!(hits, vec![(&b"resp:1"[..], 10)]);
+    /// ```
+    pub fn find_in_values<'a>(
+        &'a self,
+        needle: &'a [u8],
+    ) -> impl Iterator<Item = (&'a [u8], usize)> + 'a {
+        let failure: Arc<[usize]> = failure_table(needle).into();
+        self.iter().flat_map(move |(key, value)| {
+            matches(value, needle, Arc::clone(&failure)).map(move |offset| (key, offset))
+        })
+    }
+}
@@ -0,0 +1,274 @@
+//! An extendible-hashing table that grows one segment at a time.
+use super::*;
+
+/// Number of cells of every segment.
+pub const SEGMENT_CELLS: usize = 256;
+
+/// Number of entries a segment holds before it splits, a 0.75 load factor.
+const SEGMENT_LIMIT: usize = SEGMENT_CELLS * 3 / 4;
+
+/// Hash bits available to the directory; the high 32 bits select the cell in a segment.
+const MAX_DEPTH: u32 = 32;
+
+/// A fixed-size chained table covering the keys whose hash ends with the same
+/// `local_depth` bits.
+#[derive(Debug)]
+struct Segment {
+    local_depth: u32,
+    len: usize,
+    cells: Vec<Link>,
+}
+
+impl Clone for Segment {
+    /// Returns a deep copy; segments never share their entries.
+    fn clone(&self) -> Self {
+        let mut cells = self.cells.clone();
+        cells.iter_mut().for_each(Entry::unshare_chain);
+        Segment {
+            local_depth: self.local_depth,
+            len: self.len,
+            cells,
+        }
+    }
+}
+
+impl Segment {
+    fn new(local_depth: u32) -> Self {
+        Segment {
+            local_depth,
+            len: 0,
+            cells: vec![None; SEGMENT_CELLS],
+        }
+    }
+
+    /// Picks the cell from the high bits, which the directory does not use.
+    fn cell(hash: u64) -> usize {
+        ((hash >> 32) as usize) % SEGMENT_CELLS
+    }
+
+    fn push(&mut self, hash: u64, mut entry: Arc<Entry>) {
+        let cell = &mut self.cells[Self::cell(hash)];
+        Arc::make_mut(&mut entry).next = cell.take();
+        *cell = Some(entry);
+        self.len += 1;
+    }
+}
+
+/// A `ByteBox` variant built on extendible hashing: a directory of fixed-size segments.
+///
+/// The low bits of a key's hash select a directory slot, which points to a segment of
+/// [`SEGMENT_CELLS`] cells. When a segment fills up, only that segment is split in two;
+/// the directory doubles when needed, but it only holds segment indices. Growing the
+/// table therefore never moves more than one segment's entries at a time, and never
+/// needs a single multi-gigabyte contiguous allocation, which makes it suited to tables
+/// with hundreds of millions of entries.
+///
+/// # Examples
+///
+/// ```rust
+/// use bytesbox::segmented::SegmentedByteBox;
+///
+/// let mut table = SegmentedByteBox::new();
+/// for i in 0..10_000u32 {
+///     table.insert(&i.to_be_bytes(), b"value");
+/// }
+///
+/// assert_eq!(table.len(), 10_000);
+/// assert_eq!(table.get(&42u32.to_be_bytes()), Some(&b"value"[..]));
+/// assert!(table.segment_count() > 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SegmentedByteBox {
+    directory: Vec<usize>,
+    segments: Vec<Segment>,
+    global_depth: u32,
+    len: usize,
+}
+
+impl Default for SegmentedByteBox {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SegmentedByteBox {
+    /// Creates an empty table made of a single segment.
+    pub fn new() -> Self {
+        SegmentedByteBox {
+            directory: vec![0],
+            segments: vec![Segment::new(0)],
+            global_depth: 0,
+            len: 0,
+        }
+    }
+
+    /// Returns the number of key-value pairs stored.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no key-value pair is stored.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of segments.
+    pub fn segment_count(&self) -> usize {
+        self.segments.len()
+    }
+
+    /// Returns the number of hash bits used to index the directory.
+    pub fn global_depth(&self) -> u32 {
+        self.global_depth
+    }
+
+    /// Inserts a key-value pair, updating the value if the key already exists.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if a new key-value pair was inserted.
+    /// * `false` if an existing key was updated.
+    pub fn insert(&mut self, key: &[u8], value: &[u8]) -> bool {
+        let hash = Self::hash(key);
+        let segment = self.segment_of(hash);
+
+        let mut cursor = ChainCursor::new(&mut self.segments[segment].cells[Segment::cell(hash)]);
+        if let Some(entry) = cursor.seek(|entry| entry.key == key) {
+            entry.value = value.to_vec();
+            return false;
+        }
+
+        // Past `MAX_DEPTH` the segment keeps growing its chains instead of splitting.
+        let full = &self.segments[segment];
+        if full.len >= SEGMENT_LIMIT && full.local_depth < MAX_DEPTH {
+            self.split(hash);
+            return self.insert(key, value);
+        }
+
+        self.segments[segment].push(
+            hash,
+            Arc::new(Entry {
+                key: key.to_vec(),
+                value: value.to_vec(),
+                next: None,
+            }),
+        );
+        self.len += 1;
+        true
+    }
+
+    /// Retrieves the value associated with the given key.
+    pub fn get(&self, key: &[u8]) -> Option<&[u8]> {
+        let hash = Self::hash(key);
+        let segment = &self.segments[self.segment_of(hash)];
+        let mut current = segment.cells[Segment::cell(hash)].as_ref();
+        while let Some(entry) = current {
+            if entry.key == key {
+                return Some(&entry.value);
+            }
+            current = entry.next.as_ref();
+        }
+        None
+    }
+
+    /// Removes the key-value pair associated with the given key, returning its value.
+    ///
+    /// Segments are not merged back when they empty out.
+    pub fn remove(&mut self, key: &[u8]) -> Option<Vec<u8>> {
+        let hash = Self::hash(key);
+        let segment_index = self.segment_of(hash);
+        let segment = &mut self.segments[segment_index];
+
+        let mut cursor = ChainCursor::new(&mut segment.cells[Segment::cell(hash)]);
+        cursor.seek(|entry| entry.key == key)?;
+        let removed = cursor.remove_current()?;
+        segment.len -= 1;
+        self.len -= 1;
+        Some(Entry::into_owned(removed).value)
+    }
+
+    /// Returns an iterator over the key-value pairs, segment by segment.
+    pub fn iter(&self) -> impl Iterator<Item = (&[u8], &[u8])> + '_ {
+        self.segments.iter().flat_map(|segment| {
+            segment.cells.iter().flat_map(|cell| {
+                std::iter::successors(cell.as_deref(), |entry| entry.next.as_deref())
+                    .map(|entry| (entry.key.as_slice(), entry.value.as_slice()))
+            })
+        })
+    }
+
+    /// Returns memory and occupancy figures, counting every segment's cells.
+    pub fn stats(&self) -> Stats {
+        let mut stats = Stats {
+            len: self.len,
+            allocation: self.segments.len() * SEGMENT_CELLS,
+            ..Stats::default()
+        };
+        for cell in self.segments.iter().flat_map(|segment| &segment.cells) {
+            let chain = std::iter::successors(cell.as_deref(), |entry| entry.next.as_deref());
+            let mut depth = 0;
+            for entry in chain {
+                depth += 1;
+                stats.key_bytes += entry.key.len();
+                stats.value_bytes += entry.value.len();
+            }
+            if depth > 0 {
+                stats.occupied_cells += 1;
+            }
+            stats.longest_chain = stats.longest_chain.max(depth);
+        }
+        stats.stored_key_bytes = stats.key_bytes;
+        stats.stored_value_bytes = stats.value_bytes;
+        stats
+    }
+
+    fn hash(key: &[u8]) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    fn segment_of(&self, hash: u64) -> usize {
+        let mask = (1u64 << self.global_depth) - 1;
+        self.directory[(hash & mask) as usize]
+    }
+
+    /// Splits the full segment `hash` maps to on its next hash bit, doubling the
+    /// directory if needed.
+    fn split(&mut self, hash: u64) {
+        let segment = self.segment_of(hash);
+        let local_depth = self.segments[segment].local_depth;
+        if local_depth == self.global_depth {
+            self.directory.extend_from_within(..);
+            self.global_depth += 1;
+        }
+
+        let bit = 1u64 << local_depth;
+        let sibling = self.segments.len();
+        let mut low = Segment::new(local_depth + 1);
+        let mut high = Segment::new(local_depth + 1);
+        for cell in std::mem::take(&mut self.segments[segment].cells) {
+            let mut current = cell;
+            while let Some(mut entry) = current {
+                current = Entry::take_next(&mut entry);
+                let hash = Self::hash(&entry.key);
+                if hash & bit == 0 {
+                    low.push(hash, entry);
+                } else {
+                    high.push(hash, entry);
+                }
+            }
+        }
+        self.segments[segment] = low;
+        self.segments.push(high);
+
+        // The slots pointing at the segment are exactly those sharing its low
+        // `local_depth` bits; the ones with `bit` set now belong to the sibling.
+        let prefix = (hash & (bit - 1)) as usize;
+        let step = (bit as usize) << 1;
+        for slot in ((prefix | bit as usize)..self.directory.len()).step_by(step) {
+            debug_assert_eq!(self.directory[slot], segment);
+            self.directory[slot] = sibling;
+        }
+    }
+}
@@ -0,0 +1,46 @@
+//! `serde` support, enabled by the `serde` feature.
+//!
+//! A `ByteBox` serializes as a map from byte strings to byte strings, using `serde_bytes`
+//! so that formats with a native byte-string type (CBOR, MessagePack, bincode...) store
+//! keys and values as raw bytes instead of sequences of integers.
+use super::*;
+
+use ::serde::de::{Deserialize, Deserializer, MapAccess, Visitor};
+use ::serde::ser::{Serialize, SerializeMap, Serializer};
+use serde_bytes::{ByteBuf, Bytes};
+
+impl Serialize for ByteBox {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.len))?;
+        for (key, value) in self.iter() {
+            map.serialize_entry(Bytes::new(key), Bytes::new(value))?;
+        }
+        map.end()
+    }
+}
+
+struct ByteBoxVisitor;
+
+impl<'de> Visitor<'de> for ByteBoxVisitor {
+    type Value = ByteBox;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a map of byte strings")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut access: A) -> Result<ByteBox, A::Error> {
+        // The size hint comes from the input, so it is only trusted up to a point.
+        let hint = access.size_hint().unwrap_or(0).min(1 << 16);
+        let mut byte_box = ByteBox::prealloc(((hint as f32 / 0.75) as usize + 1).max(16));
+        while let Some((key, value)) = access.next_entry::<ByteBuf, ByteBuf>()? {
+            byte_box.insert(&key, &value);
+        }
+        Ok(byte_box)
+    }
+}
+
+impl<'de> Deserialize<'de> for ByteBox {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_map(ByteBoxVisitor)
+    }
+}
@@ -0,0 +1,236 @@
+//! Per-thread `ByteBox` shards merged on demand.
+use super::*;
+
+use std::collections::HashMap;
+use std::sync::{PoisonError, TryLockError};
+
+// Building with `RUSTFLAGS="--cfg loom"` swaps in loom's primitives so `tests/loom.rs`
+// can model-check the shard registry. No feature enables this, so it never leaks into
+// dependent crates.
+#[cfg(loom)]
+use loom::sync::{Arc, Mutex, RwLock};
+#[cfg(loom)]
+use loom::thread::{self, ThreadId};
+#[cfg(not(loom))]
+use std::sync::{Arc, Mutex, RwLock};
+#[cfg(not(loom))]
+use std::thread::{self, ThreadId};
+
+/// A set of per-thread `ByteBox` shards that can be consolidated on demand.
+///
+/// Every thread writes into its own table, so writers never contend with each other;
+/// the shards are only combined when [`merge_all`](ThreadLocalByteBox::merge_all) or
+/// [`aggregate`](ThreadLocalByteBox::aggregate) is called, typically from a periodic
+/// flush in a metrics pipeline.
+///
+/// An insert is visible to every merge that starts after it returns. A merge locks the
+/// shards one at a time, so it sees a prefix of each shard's writes rather than a
+/// snapshot across shards. These guarantees are model-checked in `tests/loom.rs`.
+///
+/// # Examples
+///
+/// ```rust
+/// use bytesbox::sharded::ThreadLocalByteBox;
+/// use std::sync::Arc;
+/// use std::thread;
+///
+/// let counters = Arc::new(ThreadLocalByteBox::new());
+/// let handles: Vec<_> = (0..4)
+///     .map(|_| {
+///         let counters = Arc::clone(&counters);
+///         thread::spawn(move || counters.insert(b"hits", &[1]))
+///     })
+///     .collect();
+/// for handle in handles {
+///     handle.join().unwrap();
+/// }
+///
+/// let totals = counters.aggregate(|_, acc, value| vec![acc[0] + value[0]]);
+/// assert_eq!(totals.get(b"hits"), Some(&[4u8][..]));
+/// ```
+#[derive(Debug, Default)]
+pub struct ThreadLocalByteBox {
+    shards: RwLock<HashMap<ThreadId, Arc<Mutex<ByteBox>>>>,
+}
+
+impl ThreadLocalByteBox {
+    /// Creates a new `ThreadLocalByteBox` without any shard.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of threads that own a shard.
+    pub fn shard_count(&self) -> usize {
+        self.shards
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .len()
+    }
+
+    /// Runs `f` with exclusive access to the calling thread's shard, creating it if needed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::sharded::ThreadLocalByteBox;
+    ///
+    /// let shards = ThreadLocalByteBox::new();
+    /// shards.with_local(|local| local.insert(b"key", b"value"));
+    /// assert_eq!(shards.with_local(|local| local.len()), 1);
+    /// ```
+    pub fn with_local<R>(&self, f: impl FnOnce(&mut ByteBox) -> R) -> R {
+        let shard = self.local();
+        let mut local = shard.lock().unwrap_or_else(PoisonError::into_inner);
+        f(&mut local)
+    }
+
+    /// Inserts a key-value pair into the calling thread's shard.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if the key was new to this thread's shard.
+    /// * `false` if an existing key of this shard was updated.
+    pub fn insert(&self, key: &[u8], value: &[u8]) -> bool {
+        self.with_local(|local| local.insert(key, value))
+    }
+
+    /// Like [`with_local`](ThreadLocalByteBox::with_local), but returns `None` instead of
+    /// blocking when the shard or the shard registry is locked by another thread, for
+    /// example while [`merge_all`](ThreadLocalByteBox::merge_all) reads this thread's shard.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::sharded::ThreadLocalByteBox;
+    ///
+    /// let shards = ThreadLocalByteBox::new();
+    /// assert_eq!(shards.try_with_local(|local| local.insert(b"key", b"value")), Some(true));
+    /// ```
+    pub fn try_with_local<R>(&self, f: impl FnOnce(&mut ByteBox) -> R) -> Option<R> {
+        let shard = self.try_local()?;
+        let mut local = match shard.try_lock() {
+            Ok(local) => local,
+            Err(TryLockError::Poisoned(poisoned)) => poisoned.into_inner(),
+            Err(TryLockError::WouldBlock) => return None,
+        };
+        Some(f(&mut local))
+    }
+
+    /// Inserts into the calling thread's shard unless that would block.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(bool)` with the result of [`ByteBox::insert`] if the insert happened.
+    /// * `None` if the shard was locked by another thread; nothing was inserted.
+    pub fn try_insert(&self, key: &[u8], value: &[u8]) -> Option<bool> {
+        self.try_with_local(|local| local.insert(key, value))
+    }
+
+    /// Reads a key of the calling thread's shard unless that would block.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Some(Vec<u8>))` with a copy of the value if the key is in this thread's shard.
+    /// * `Some(None)` if it is not.
+    /// * `None` if the shard was locked by another thread.
+    pub fn try_get(&self, key: &[u8]) -> Option<Option<Vec<u8>>> {
+        self.try_with_local(|local| local.get(key).map(<[u8]>::to_vec))
+    }
+
+    /// Consolidates all shards into a single `ByteBox`.
+    ///
+    /// When several threads hold the same key, one of their values is kept; which one is
+    /// unspecified. Use [`aggregate`](ThreadLocalByteBox::aggregate) to combine them instead.
+    pub fn merge_all(&self) -> ByteBox {
+        let mut merged = ByteBox::new();
+        for shard in self.snapshot() {
+            let shard = shard.lock().unwrap_or_else(PoisonError::into_inner);
+            for (key, value) in shard.iter() {
+                merged.insert(key, value);
+            }
+        }
+        merged
+    }
+
+    /// Consolidates all shards, combining values of keys present in several shards with `f`.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - Receives the key, the value accumulated so far and the next shard's value,
+    ///   and returns the combined value.
+    pub fn aggregate<F>(&self, mut f: F) -> ByteBox
+    where
+        F: FnMut(&[u8], &[u8], &[u8]) -> Vec<u8>,
+    {
+        let mut merged = ByteBox::new();
+        for shard in self.snapshot() {
+            let shard = shard.lock().unwrap_or_else(PoisonError::into_inner);
+            for (key, value) in shard.iter() {
+                let combined = merged.get(key).map(|acc| f(key, acc, value));
+                match combined {
+                    Some(combined) => merged.insert(key, &combined),
+                    None => merged.insert(key, value),
+                };
+            }
+        }
+        merged
+    }
+
+    /// Clears every shard, keeping their allocations for the next period.
+    pub fn clear(&self) {
+        for shard in self.snapshot() {
+            shard.lock().unwrap_or_else(PoisonError::into_inner).clear();
+        }
+    }
+
+    /// Returns the calling thread's shard, registering a new one on first use.
+    fn local(&self) -> Arc<Mutex<ByteBox>> {
+        let id = thread::current().id();
+        if let Some(shard) = self
+            .shards
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&id)
+        {
+            return Arc::clone(shard);
+        }
+
+        let mut shards = self.shards.write().unwrap_or_else(PoisonError::into_inner);
+        Arc::clone(shards.entry(id).or_default())
+    }
+
+    /// Returns the calling thread's shard without blocking on the registry.
+    fn try_local(&self) -> Option<Arc<Mutex<ByteBox>>> {
+        let id = thread::current().id();
+        match self.shards.try_read() {
+            Ok(shards) => {
+                if let Some(shard) = shards.get(&id) {
+                    return Some(Arc::clone(shard));
+                }
+            }
+            Err(TryLockError::Poisoned(poisoned)) => {
+                if let Some(shard) = poisoned.into_inner().get(&id) {
+                    return Some(Arc::clone(shard));
+                }
+            }
+            Err(TryLockError::WouldBlock) => return None,
+        }
+
+        let mut shards = match self.shards.try_write() {
+            Ok(shards) => shards,
+            Err(TryLockError::Poisoned(poisoned)) => poisoned.into_inner(),
+            Err(TryLockError::WouldBlock) => return None,
+        };
+        Some(Arc::clone(shards.entry(id).or_default()))
+    }
+
+    /// Clones the shard handles so the registry lock is not held while shards are read.
+    fn snapshot(&self) -> Vec<Arc<Mutex<ByteBox>>> {
+        self.shards
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .values()
+            .cloned()
+            .collect()
+    }
+}
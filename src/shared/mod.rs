@@ -0,0 +1,126 @@
+//! A `ByteBox` variant storing `bytes::Bytes` values, enabled by the `bytes` feature.
+use super::*;
+
+use bytes::Bytes;
+
+/// A `ByteBox` variant whose values are reference-counted [`Bytes`].
+///
+/// `ByteBox` owns every value as a `Vec<u8>`, so handing one to an I/O layer means
+/// copying it. `SharedByteBox` keeps the `Bytes` it is given: [`SharedByteBox::insert_bytes`]
+/// stores a `Bytes` or a frozen `BytesMut` without copying, and
+/// [`SharedByteBox::get_bytes`] returns a handle to the same Arc-shared storage, ready for
+/// tokio or hyper write paths.
+///
+/// # Examples
+///
+/// ```rust
+/// use bytes::{Bytes, BytesMut};
+/// use bytesbox::shared::SharedByteBox;
+///
+/// let mut table = SharedByteBox::new();
+/// let body = Bytes::from_static(b"<html></html>");
+/// table.insert_bytes(b"index.html", body.clone());
+///
+/// let mut buf = BytesMut::new();
+/// buf.extend_from_slice(b"built in place");
+/// table.insert_bytes(b"note", buf);
+///
+/// let handle = table.get_bytes(b"index.html").unwrap();
+/// assert_eq!(handle.as_ptr(), body.as_ptr());
+/// assert_eq!(table.get(b"note"), Some(&b"built in place"[..]));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SharedByteBox {
+    keys: ByteBox,
+    values: Vec<Option<Bytes>>,
+    free: Vec<usize>,
+}
+
+impl SharedByteBox {
+    /// Creates a new, empty `SharedByteBox`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of key-value pairs stored.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Returns `true` if no key-value pair is stored.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Inserts a key with a shared value, without copying the value.
+    ///
+    /// Accepts `Bytes`, `BytesMut` (which is frozen) or anything else convertible into
+    /// `Bytes`.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if a new key-value pair was inserted.
+    /// * `false` if an existing key was updated.
+    pub fn insert_bytes<V: Into<Bytes>>(&mut self, key: &[u8], value: V) -> bool {
+        let value = value.into();
+        if let Some(id) = self.keys.get(key).map(Self::decode_id) {
+            self.values[id] = Some(value);
+            return false;
+        }
+
+        let id = match self.free.pop() {
+            Some(id) => {
+                self.values[id] = Some(value);
+                id
+            }
+            None => {
+                self.values.push(Some(value));
+                self.values.len() - 1
+            }
+        };
+        self.keys.insert(key, &id.to_le_bytes())
+    }
+
+    /// Inserts a key with a copy of `value`.
+    pub fn insert(&mut self, key: &[u8], value: &[u8]) -> bool {
+        self.insert_bytes(key, Bytes::copy_from_slice(value))
+    }
+
+    /// Retrieves the value associated with the given key as a byte slice.
+    pub fn get(&self, key: &[u8]) -> Option<&[u8]> {
+        self.slot(key).map(|value| &value[..])
+    }
+
+    /// Retrieves a handle to the value associated with the given key.
+    ///
+    /// The handle shares the stored allocation; only a reference count is incremented.
+    pub fn get_bytes(&self, key: &[u8]) -> Option<Bytes> {
+        self.slot(key).cloned()
+    }
+
+    /// Removes the key, returning its value without copying it.
+    pub fn remove(&mut self, key: &[u8]) -> Option<Bytes> {
+        let id = Self::decode_id(&self.keys.remove(key)?);
+        self.free.push(id);
+        self.values[id].take()
+    }
+
+    /// Returns an iterator over the keys and the values.
+    pub fn iter(&self) -> impl Iterator<Item = (&[u8], &Bytes)> + '_ {
+        self.keys.iter().filter_map(|(key, id)| {
+            let value = self.values[Self::decode_id(id)].as_ref()?;
+            Some((key, value))
+        })
+    }
+
+    fn slot(&self, key: &[u8]) -> Option<&Bytes> {
+        let id = Self::decode_id(self.keys.get(key)?);
+        self.values[id].as_ref()
+    }
+
+    fn decode_id(bytes: &[u8]) -> usize {
+        let mut id = [0u8; std::mem::size_of::<usize>()];
+        id.copy_from_slice(bytes);
+        usize::from_le_bytes(id)
+    }
+}
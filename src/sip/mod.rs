@@ -0,0 +1,106 @@
+//! SipHash-1-3, the hash behind values that must not change between builds.
+//!
+//! The standard library's `DefaultHasher` is also SipHash-1-3 today, but its algorithm
+//! is explicitly unspecified and may change in any Rust release. Hashes that are
+//! compared across processes or persisted go through this implementation instead.
+
+/// A streaming SipHash-1-3 hasher with explicit keys.
+///
+/// Every length written through [`SipHasher13::write_len`] is encoded as a little-endian
+/// `u64`, so the output does not depend on the platform's word size or byte order.
+#[derive(Debug, Clone)]
+pub(crate) struct SipHasher13 {
+    v0: u64,
+    v1: u64,
+    v2: u64,
+    v3: u64,
+    /// Bytes not yet forming a full 8-byte word, little-endian.
+    tail: u64,
+    tail_len: usize,
+    length: u64,
+}
+
+impl SipHasher13 {
+    /// Creates a hasher keyed with `k0` and `k1`.
+    pub(crate) fn new_with_keys(k0: u64, k1: u64) -> Self {
+        SipHasher13 {
+            v0: k0 ^ 0x736f_6d65_7073_6575,
+            v1: k1 ^ 0x646f_7261_6e64_6f6d,
+            v2: k0 ^ 0x6c79_6765_6e65_7261,
+            v3: k1 ^ 0x7465_6462_7974_6573,
+            tail: 0,
+            tail_len: 0,
+            length: 0,
+        }
+    }
+
+    /// Feeds `bytes` into the hash.
+    pub(crate) fn write(&mut self, mut bytes: &[u8]) {
+        self.length = self.length.wrapping_add(bytes.len() as u64);
+
+        if self.tail_len > 0 {
+            let fill = (8 - self.tail_len).min(bytes.len());
+            self.tail |= read_le(&bytes[..fill]) << (8 * self.tail_len);
+            self.tail_len += fill;
+            bytes = &bytes[fill..];
+            if self.tail_len < 8 {
+                return;
+            }
+            self.compress(self.tail);
+            self.tail = 0;
+            self.tail_len = 0;
+        }
+
+        let mut words = bytes.chunks_exact(8);
+        for word in &mut words {
+            self.compress(read_le(word));
+        }
+        let rest = words.remainder();
+        self.tail = read_le(rest);
+        self.tail_len = rest.len();
+    }
+
+    /// Feeds a length, encoded as a little-endian `u64`.
+    pub(crate) fn write_len(&mut self, len: usize) {
+        self.write(&(len as u64).to_le_bytes());
+    }
+
+    /// Returns the hash of everything written so far.
+    pub(crate) fn finish(&self) -> u64 {
+        let mut state = self.clone();
+        let last = ((self.length & 0xff) << 56) | self.tail;
+        state.compress(last);
+        state.v2 ^= 0xff;
+        for _ in 0..3 {
+            state.round();
+        }
+        state.v0 ^ state.v1 ^ state.v2 ^ state.v3
+    }
+
+    fn compress(&mut self, word: u64) {
+        self.v3 ^= word;
+        self.round();
+        self.v0 ^= word;
+    }
+
+    fn round(&mut self) {
+        self.v0 = self.v0.wrapping_add(self.v1);
+        self.v1 = self.v1.rotate_left(13) ^ self.v0;
+        self.v0 = self.v0.rotate_left(32);
+        self.v2 = self.v2.wrapping_add(self.v3);
+        self.v3 = self.v3.rotate_left(16) ^ self.v2;
+        self.v0 = self.v0.wrapping_add(self.v3);
+        self.v3 = self.v3.rotate_left(21) ^ self.v0;
+        self.v2 = self.v2.wrapping_add(self.v1);
+        self.v1 = self.v1.rotate_left(17) ^ self.v2;
+        self.v2 = self.v2.rotate_left(32);
+    }
+}
+
+/// Reads up to 8 bytes as a little-endian integer.
+fn read_le(bytes: &[u8]) -> u64 {
+    bytes
+        .iter()
+        .rev()
+        .fold(0, |word, &byte| (word << 8) | u64::from(byte))
+}
@@ -0,0 +1,391 @@
+//! Writing tables to and reading them from a versioned, length-prefixed format.
+use super::*;
+
+use std::io::{self, Read, Write};
+
+/// Bytes every snapshot starts with; the trailing digit is the format version.
+pub const SNAPSHOT_MAGIC: &[u8; 8] = b"BYTEBOX1";
+
+/// Tag of the section holding the entries of a [`SnapshotFormat::V2`] snapshot.
+pub const SECTION_ENTRIES: u64 = 1;
+
+/// Bit set in the tag of a [`SnapshotFormat::V2`] section that readers must understand.
+///
+/// Readers skip unknown sections without this bit and refuse snapshots with an unknown
+/// section that has it.
+pub const SECTION_CRITICAL: u64 = 1 << 63;
+
+/// Versions of the snapshot format.
+///
+/// * `V1` is [`SNAPSHOT_MAGIC`], the number of entries as a little-endian `u64`, then for
+///   every entry the key length (`u64` LE), the key, the value length (`u64` LE) and the
+///   value.
+/// * `V2` is `BYTEBOX2` followed by sections, each made of a tag (`u64` LE), a payload
+///   length (`u64` LE) and the payload. The [`SECTION_ENTRIES`] section holds the `V1`
+///   body. Sections written by newer versions are skipped by older readers unless their
+///   tag has [`SECTION_CRITICAL`] set.
+///
+/// [`SnapshotReader`] reads every version; an unknown version is an `InvalidData` error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapshotFormat {
+    /// The original, single-section format.
+    V1,
+    /// The sectioned format with forward-compatible skipping of unknown sections.
+    V2,
+}
+
+impl SnapshotFormat {
+    /// The newest format this version of the crate writes.
+    pub const LATEST: SnapshotFormat = SnapshotFormat::V2;
+
+    /// Returns the magic bytes a snapshot in this format starts with.
+    pub fn magic(self) -> &'static [u8; 8] {
+        match self {
+            SnapshotFormat::V1 => SNAPSHOT_MAGIC,
+            SnapshotFormat::V2 => b"BYTEBOX2",
+        }
+    }
+}
+
+/// How [`ByteBox::import`] combines a snapshot with the entries already in the table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportStrategy {
+    /// Discard the current entries; the table ends up holding the accepted snapshot entries.
+    Replace,
+    /// Add snapshot entries whose key is absent, keeping live values for the others.
+    MergePreferExisting,
+    /// Add every snapshot entry, overwriting live values for keys present in both.
+    MergePreferImported,
+}
+
+/// Counts of what [`ByteBox::import`] did with the snapshot entries.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ImportReport {
+    /// Entries written to the table.
+    pub imported: usize,
+    /// Entries skipped because the key was already present under `MergePreferExisting`.
+    pub kept_existing: usize,
+    /// Entries refused by the validation callback.
+    pub rejected: usize,
+}
+
+/// Reads the entries of a snapshot one at a time, without building a table.
+///
+/// Snapshots are written by [`ByteBox::write_snapshot`] or
+/// [`ByteBox::write_snapshot_as`]; every [`SnapshotFormat`] is accepted.
+///
+/// # Examples
+///
+/// ```rust
+/// use bytesbox::snapshot::SnapshotReader;
+/// use bytesbox::ByteBox;
+///
+/// let mut bytebox = ByteBox::new();
+/// bytebox.insert(b"key", b"value");
+/// let mut file = Vec::new();
+/// bytebox.write_snapshot(&mut file).unwrap();
+///
+/// let reader = SnapshotReader::new(&file[..]).unwrap();
+/// assert_eq!(reader.len(), 1);
+/// let entries: Vec<_> = reader.collect::<Result<_, _>>().unwrap();
+/// assert_eq!(entries, vec![(b"key".to_vec(), b"value".to_vec())]);
+/// ```
+#[derive(Debug)]
+pub struct SnapshotReader<R> {
+    reader: R,
+    format: SnapshotFormat,
+    len: u64,
+    remaining: u64,
+}
+
+impl<R: Read> SnapshotReader<R> {
+    /// Checks the snapshot header and prepares to read its entries.
+    ///
+    /// For a [`SnapshotFormat::V2`] snapshot, sections in front of the entries are skipped
+    /// if they are not critical.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidData` if the magic bytes do not match, the version is unknown or a
+    /// critical section is not understood, or the underlying I/O error.
+    pub fn new(mut reader: R) -> io::Result<Self> {
+        let mut magic = [0u8; 8];
+        reader.read_exact(&mut magic)?;
+        let format = if &magic == SnapshotFormat::V1.magic() {
+            SnapshotFormat::V1
+        } else if &magic == SnapshotFormat::V2.magic() {
+            SnapshotFormat::V2
+        } else if magic.starts_with(b"BYTEBOX") {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("unsupported snapshot version {}", magic[7] as char),
+            ));
+        } else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "not a ByteBox snapshot",
+            ));
+        };
+
+        if format == SnapshotFormat::V2 {
+            skip_to_entries(&mut reader)?;
+        }
+        let len = read_u64(&mut reader)?;
+        Ok(SnapshotReader {
+            reader,
+            format,
+            len,
+            remaining: len,
+        })
+    }
+
+    /// Returns the format the snapshot was written in.
+    pub fn format(&self) -> SnapshotFormat {
+        self.format
+    }
+
+    /// Returns the number of entries declared by the snapshot header.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Returns `true` if the snapshot declares no entries.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn read_entry(&mut self) -> io::Result<(Vec<u8>, Vec<u8>)> {
+        let key = read_chunk(&mut self.reader)?;
+        let value = read_chunk(&mut self.reader)?;
+        Ok((key, value))
+    }
+}
+
+impl<R: Read> Iterator for SnapshotReader<R> {
+    type Item = io::Result<(Vec<u8>, Vec<u8>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let entry = self.read_entry();
+        if entry.is_err() {
+            // A truncated or corrupt stream cannot be resynchronized.
+            self.remaining = 0;
+        }
+        Some(entry)
+    }
+}
+
+/// Skips the sections in front of the entries section of a `V2` snapshot.
+fn skip_to_entries<R: Read>(reader: &mut R) -> io::Result<()> {
+    loop {
+        let tag = read_u64(reader)?;
+        let len = read_u64(reader)?;
+        if tag == SECTION_ENTRIES {
+            return Ok(());
+        }
+        if tag & SECTION_CRITICAL != 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("unsupported critical snapshot section {:#x}", tag),
+            ));
+        }
+        if io::copy(&mut reader.by_ref().take(len), &mut io::sink())? != len {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "snapshot section is truncated",
+            ));
+        }
+    }
+}
+
+pub(crate) fn read_u64<R: Read>(reader: &mut R) -> io::Result<u64> {
+    let mut bytes = [0u8; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+/// Reads a length-prefixed chunk without trusting the length for the allocation.
+pub(crate) fn read_chunk<R: Read>(reader: &mut R) -> io::Result<Vec<u8>> {
+    let len = read_u64(reader)?;
+    let mut chunk = Vec::new();
+    reader.by_ref().take(len).read_to_end(&mut chunk)?;
+    if chunk.len() as u64 != len {
+        return Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "snapshot entry is truncated",
+        ));
+    }
+    Ok(chunk)
+}
+
+pub(crate) fn write_chunk<W: Write>(writer: &mut W, chunk: &[u8]) -> io::Result<()> {
+    writer.write_all(&(chunk.len() as u64).to_le_bytes())?;
+    writer.write_all(chunk)
+}
+
+impl ByteBox {
+    /// Writes every entry to `writer` in the snapshot format read by [`SnapshotReader`].
+    ///
+    /// Entries are written in iteration order. The writer is not buffered by this method;
+    /// wrap files in a `BufWriter`.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - Where the snapshot is written.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut bytebox = ByteBox::new();
+    /// bytebox.insert(b"key", b"value");
+    ///
+    /// let mut file = Vec::new();
+    /// bytebox.write_snapshot(&mut file).unwrap();
+    /// let restored = ByteBox::read_snapshot(&file[..]).unwrap();
+    /// assert_eq!(restored.get(b"key"), Some(&b"value"[..]));
+    /// ```
+    pub fn write_snapshot<W: Write>(&self, writer: W) -> io::Result<()> {
+        self.write_snapshot_as(writer, SnapshotFormat::V1)
+    }
+
+    /// Writes every entry to `writer` in the given snapshot format.
+    ///
+    /// [`ByteBox::write_snapshot`] writes [`SnapshotFormat::V1`], which every reader
+    /// understands; choose a newer format once all readers have been upgraded.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - Where the snapshot is written.
+    /// * `format` - The format version to write.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::snapshot::{SnapshotFormat, SnapshotReader};
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut bytebox = ByteBox::new();
+    /// bytebox.insert(b"key", b"value");
+    ///
+    /// let mut file = Vec::new();
+    /// bytebox.write_snapshot_as(&mut file, SnapshotFormat::V2).unwrap();
+    /// assert_eq!(SnapshotReader::new(&file[..]).unwrap().format(), SnapshotFormat::V2);
+    /// assert_eq!(ByteBox::read_snapshot(&file[..]).unwrap().get(b"key"), Some(&b"value"[..]));
+    /// ```
+    pub fn write_snapshot_as<W: Write>(
+        &self,
+        mut writer: W,
+        format: SnapshotFormat,
+    ) -> io::Result<()> {
+        writer.write_all(format.magic())?;
+        if format == SnapshotFormat::V2 {
+            let body_len = self.iter().fold(8u64, |len, (key, value)| {
+                len + 16 + key.len() as u64 + value.len() as u64
+            });
+            writer.write_all(&SECTION_ENTRIES.to_le_bytes())?;
+            writer.write_all(&body_len.to_le_bytes())?;
+        }
+        writer.write_all(&(self.len as u64).to_le_bytes())?;
+        for (key, value) in self.iter() {
+            write_chunk(&mut writer, key)?;
+            write_chunk(&mut writer, value)?;
+        }
+        writer.flush()
+    }
+
+    /// Builds a table from a snapshot written by [`ByteBox::write_snapshot`] or
+    /// [`ByteBox::write_snapshot_as`], in any [`SnapshotFormat`].
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - Where the snapshot is read from.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidData` for a foreign file, `UnexpectedEof` for a truncated one, or the
+    /// underlying I/O error.
+    pub fn read_snapshot<R: Read>(reader: R) -> io::Result<ByteBox> {
+        let mut byte_box = ByteBox::new();
+        for entry in SnapshotReader::new(reader)? {
+            let (key, value) = entry?;
+            byte_box.insert(&key, &value);
+        }
+        Ok(byte_box)
+    }
+
+    /// Loads a snapshot into this table, validating every entry and resolving conflicts
+    /// with live entries according to `strategy`.
+    ///
+    /// The whole snapshot is read before the table is touched, so an I/O error or a
+    /// corrupt snapshot leaves the table unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - Where the snapshot is read from.
+    /// * `strategy` - How snapshot entries are combined with the current ones.
+    /// * `validate` - Called with every snapshot entry; entries for which it returns
+    ///   `false` are skipped.
+    ///
+    /// # Returns
+    ///
+    /// An [`ImportReport`] counting imported, kept and rejected entries.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::snapshot::ImportStrategy;
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut stale = ByteBox::new();
+    /// stale.insert(b"session", b"old");
+    /// stale.insert(b"config", b"v1");
+    /// let mut file = Vec::new();
+    /// stale.write_snapshot(&mut file).unwrap();
+    ///
+    /// let mut live = ByteBox::new();
+    /// live.insert(b"session", b"fresh");
+    /// let report = live
+    ///     .import(&file[..], ImportStrategy::MergePreferExisting, |_, _| true)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(report.imported, 1);
+    /// assert_eq!(report.kept_existing, 1);
+    /// assert_eq!(live.get(b"session"), Some(&b"fresh"[..]));
+    /// assert_eq!(live.get(b"config"), Some(&b"v1"[..]));
+    /// ```
+    pub fn import<R, F>(
+        &mut self,
+        reader: R,
+        strategy: ImportStrategy,
+        mut validate: F,
+    ) -> io::Result<ImportReport>
+    where
+        R: Read,
+        F: FnMut(&[u8], &[u8]) -> bool,
+    {
+        let entries = SnapshotReader::new(reader)?.collect::<io::Result<Vec<_>>>()?;
+
+        let mut report = ImportReport::default();
+        if strategy == ImportStrategy::Replace {
+            self.clear();
+        }
+        for (key, value) in entries {
+            if !validate(&key, &value) {
+                report.rejected += 1;
+                continue;
+            }
+            if strategy == ImportStrategy::MergePreferExisting && self.lookup(&key).is_some() {
+                report.kept_existing += 1;
+                continue;
+            }
+            self.insert(&key, &value);
+            report.imported += 1;
+        }
+        Ok(report)
+    }
+}
@@ -0,0 +1,244 @@
+//! Read-only perfect-hash tables built at compile time.
+
+/// Upper bound on the displacements tried for a single bucket while building the table.
+const MAX_DISPLACEMENT: u64 = 1 << 16;
+
+/// A read-only, perfect-hash table built entirely at compile time.
+///
+/// Keys are spread with a hash-and-displace scheme: every key first lands in a bucket,
+/// and each bucket stores the displacement that sends all of its keys to distinct slots.
+/// A lookup therefore costs two hashes and a single key comparison, and the whole
+/// table is embedded in the binary without any runtime construction.
+///
+/// Tables are normally declared with the [`static_bytebox!`](crate::static_bytebox)
+/// macro, which computes the const parameters for you.
+///
+/// # Examples
+///
+/// ```rust
+/// use bytesbox::static_bytebox;
+///
+/// static_bytebox! {
+///     static REASONS = {
+///         b"200" => b"OK",
+///         b"404" => b"Not Found",
+///         b"500" => b"Internal Server Error",
+///     };
+/// }
+///
+/// assert_eq!(REASONS.get(b"404"), Some(&b"Not Found"[..]));
+/// assert_eq!(REASONS.get(b"418"), None);
+/// assert_eq!(REASONS.len(), 3);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct StaticByteBox<const N: usize, const S: usize> {
+    entries: [(&'static [u8], &'static [u8]); N],
+    displacements: [u64; N],
+    slots: [usize; S],
+}
+
+impl<const N: usize, const S: usize> StaticByteBox<N, S> {
+    /// Builds the table from its entries; meant to be evaluated in a `const` or `static`.
+    ///
+    /// `S` is the number of slots and must be at least `N`; [`slot_count`] gives the
+    /// value used by the macro.
+    ///
+    /// # Panics
+    ///
+    /// Panics (at compile time when used in a const context) if a key appears twice,
+    /// if `S < N`, or if no displacement can be found for a bucket.
+    pub const fn new(entries: [(&'static [u8], &'static [u8]); N]) -> Self {
+        if S < N {
+            panic!("StaticByteBox: slot count must be at least the number of entries");
+        }
+
+        let mut i = 0;
+        while i < N {
+            let mut j = i + 1;
+            while j < N {
+                if bytes_eq(entries[i].0, entries[j].0) {
+                    panic!("StaticByteBox: duplicate key");
+                }
+                j += 1;
+            }
+            i += 1;
+        }
+
+        let mut sizes = [0usize; N];
+        i = 0;
+        while i < N {
+            sizes[bucket_of(entries[i].0, N)] += 1;
+            i += 1;
+        }
+
+        let mut displacements = [0u64; N];
+        // Slots hold `entry index + 1`, zero meaning empty.
+        let mut slots = [0usize; S];
+
+        // Place the largest buckets first, while most slots are still free.
+        let mut size = N;
+        while size > 0 {
+            let mut bucket = 0;
+            while bucket < N {
+                if sizes[bucket] == size {
+                    let mut displacement = 1;
+                    loop {
+                        let mut placed = true;
+                        let mut k = 0;
+                        while k < N {
+                            if bucket_of(entries[k].0, N) == bucket {
+                                let slot = slot_of(entries[k].0, displacement, S);
+                                if slots[slot] != 0 {
+                                    placed = false;
+                                    break;
+                                }
+                                slots[slot] = k + 1;
+                            }
+                            k += 1;
+                        }
+                        if placed {
+                            displacements[bucket] = displacement;
+                            break;
+                        }
+
+                        // Undo the partial placement of this bucket and try the next displacement.
+                        let mut slot = 0;
+                        while slot < S {
+                            if slots[slot] != 0
+                                && bucket_of(entries[slots[slot] - 1].0, N) == bucket
+                            {
+                                slots[slot] = 0;
+                            }
+                            slot += 1;
+                        }
+                        displacement += 1;
+                        if displacement > MAX_DISPLACEMENT {
+                            panic!("StaticByteBox: could not find a perfect hash for the keys");
+                        }
+                    }
+                }
+                bucket += 1;
+            }
+            size -= 1;
+        }
+
+        StaticByteBox {
+            entries,
+            displacements,
+            slots,
+        }
+    }
+
+    /// Returns the number of key-value pairs in the table.
+    pub const fn len(&self) -> usize {
+        N
+    }
+
+    /// Returns `true` if the table holds no entries.
+    pub const fn is_empty(&self) -> bool {
+        N == 0
+    }
+
+    /// Retrieves the value associated with the given key.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(&'static [u8])` containing the value if the key exists.
+    /// * `None` if the key is not part of the table.
+    pub fn get(&self, key: &[u8]) -> Option<&'static [u8]> {
+        if N == 0 {
+            return None;
+        }
+        let displacement = self.displacements[bucket_of(key, N)];
+        match self.slots[slot_of(key, displacement, S)] {
+            0 => None,
+            index => {
+                let (candidate, value) = self.entries[index - 1];
+                (candidate == key).then_some(value)
+            }
+        }
+    }
+
+    /// Returns `true` if the table contains the given key.
+    pub fn contains_key(&self, key: &[u8]) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Returns an iterator over the entries, in declaration order.
+    pub fn iter(&self) -> impl Iterator<Item = (&'static [u8], &'static [u8])> + '_ {
+        self.entries.iter().copied()
+    }
+}
+
+/// Returns the number of slots the [`static_bytebox!`](crate::static_bytebox) macro
+/// allocates for `entries` keys.
+pub const fn slot_count(entries: usize) -> usize {
+    if entries == 0 {
+        1
+    } else {
+        entries * 2
+    }
+}
+
+/// FNV-1a over the key, with the seed folded into the offset basis.
+const fn fnv1a(key: &[u8], seed: u64) -> u64 {
+    let mut hash = 0xcbf2_9ce4_8422_2325 ^ seed.wrapping_mul(0x9e37_79b9_7f4a_7c15);
+    let mut i = 0;
+    while i < key.len() {
+        hash ^= key[i] as u64;
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+        i += 1;
+    }
+    hash
+}
+
+const fn bucket_of(key: &[u8], buckets: usize) -> usize {
+    (fnv1a(key, 0) % buckets as u64) as usize
+}
+
+const fn slot_of(key: &[u8], displacement: u64, slots: usize) -> usize {
+    (fnv1a(key, displacement) % slots as u64) as usize
+}
+
+const fn bytes_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Declares a [`StaticByteBox`](crate::static_table::StaticByteBox) built at compile time.
+///
+/// The table is a `static` item; its const parameters are derived from the entries.
+///
+/// # Examples
+///
+/// ```rust
+/// use bytesbox::static_bytebox;
+///
+/// static_bytebox! {
+///     pub static METHODS = {
+///         b"GET" => b"safe",
+///         b"POST" => b"unsafe",
+///     };
+/// }
+///
+/// assert_eq!(METHODS.get(b"GET"), Some(&b"safe"[..]));
+/// ```
+#[macro_export]
+macro_rules! static_bytebox {
+    ($(#[$meta:meta])* $vis:vis static $name:ident = { $($key:expr => $value:expr),* $(,)? };) => {
+        $(#[$meta])*
+        $vis static $name: $crate::static_table::StaticByteBox<
+            { <[&str]>::len(&[$(stringify!($key)),*]) },
+            { $crate::static_table::slot_count(<[&str]>::len(&[$(stringify!($key)),*])) },
+        > = $crate::static_table::StaticByteBox::new([$(($key as &[u8], $value as &[u8])),*]);
+    };
+}
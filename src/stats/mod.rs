@@ -0,0 +1,41 @@
+//! Size and memory statistics of a table.
+
+/// Memory and occupancy figures of a table.
+///
+/// This struct is created by the `stats` method of [`ByteBox`](crate::ByteBox) and of
+/// the other table types of this crate.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// Number of key-value pairs stored.
+    pub len: usize,
+    /// Number of allocated cells.
+    pub allocation: usize,
+    /// Number of cells holding at least one entry.
+    pub occupied_cells: usize,
+    /// Length of the longest collision chain.
+    pub longest_chain: usize,
+    /// Total length of all keys, as seen by callers.
+    pub key_bytes: usize,
+    /// Bytes actually used to store the keys; lower than `key_bytes` when keys are compressed.
+    pub stored_key_bytes: usize,
+    /// Total length of all values, as seen by callers.
+    pub value_bytes: usize,
+    /// Bytes actually used to store the values; lower than `value_bytes` when values are
+    /// shared.
+    pub stored_value_bytes: usize,
+    /// Key and value bytes of entries still shared with a copy made by
+    /// [`ByteBox::clone_cheap`](crate::ByteBox::clone_cheap).
+    pub shared_bytes: usize,
+}
+
+impl Stats {
+    /// Returns how many key bytes are saved by compression.
+    pub fn key_savings(&self) -> usize {
+        self.key_bytes.saturating_sub(self.stored_key_bytes)
+    }
+
+    /// Returns how many value bytes are saved by sharing.
+    pub fn value_savings(&self) -> usize {
+        self.value_bytes.saturating_sub(self.stored_value_bytes)
+    }
+}
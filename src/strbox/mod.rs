@@ -0,0 +1,153 @@
+//! A `ByteBox` facade with `&str` keys and values.
+use super::*;
+
+/// A `ByteBox` facade with `&str` keys and values.
+///
+/// Textual tables, such as configuration, can use string literals instead of byte
+/// literals, and read values back as `&str` without converting them. Every key and value
+/// is UTF-8 by construction, so the table can be handed to byte-oriented code through
+/// [`StrBox::as_byte_box`] or [`StrBox::into_inner`] at no cost; the opposite direction
+/// checks the table once.
+///
+/// # Examples
+///
+/// ```rust
+/// use bytesbox::strbox::StrBox;
+///
+/// let mut config = StrBox::new();
+/// config.insert("listen", "0.0.0.0:8080");
+/// config.insert("log.level", "info");
+///
+/// assert_eq!(config.get("log.level"), Some("info"));
+/// assert_eq!(config.remove("listen"), Some("0.0.0.0:8080".to_string()));
+///
+/// let bytes = config.into_inner();
+/// assert_eq!(bytes.get(b"log.level"), Some(&b"info"[..]));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct StrBox {
+    inner: ByteBox,
+}
+
+impl StrBox {
+    /// Creates a new, empty `StrBox`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new `StrBox` with `size` preallocated cells; see [`ByteBox::prealloc`].
+    pub fn prealloc(size: usize) -> Self {
+        StrBox {
+            inner: ByteBox::prealloc(size),
+        }
+    }
+
+    /// Returns the underlying table.
+    pub fn as_byte_box(&self) -> &ByteBox {
+        &self.inner
+    }
+
+    /// Unwraps the underlying table.
+    pub fn into_inner(self) -> ByteBox {
+        self.inner
+    }
+
+    /// Returns the number of key-value pairs stored.
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns `true` if no key-value pair is stored.
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Inserts a key-value pair, replacing the value of an existing key.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key.
+    /// * `value` - The value.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if a new key-value pair was inserted.
+    /// * `false` if an existing key was updated.
+    pub fn insert(&mut self, key: &str, value: &str) -> bool {
+        self.inner.insert(key.as_bytes(), value.as_bytes())
+    }
+
+    /// Retrieves the value associated with the given key.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(&str)` containing the value if the key exists.
+    /// * `None` if the key does not exist.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.inner
+            .get(key.as_bytes())
+            .map(|value| std::str::from_utf8(value).expect("values are UTF-8 on insert"))
+    }
+
+    /// Returns `true` if the table contains the given key.
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.inner.contains_key(key.as_bytes())
+    }
+
+    /// Removes the key, returning its value.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(String)` containing the removed value if the key existed.
+    /// * `None` if the key was not found.
+    pub fn remove(&mut self, key: &str) -> Option<String> {
+        self.inner
+            .remove(key.as_bytes())
+            .map(|value| String::from_utf8(value).expect("values are UTF-8 on insert"))
+    }
+
+    /// Removes all key-value pairs.
+    pub fn clear(&mut self) {
+        self.inner.clear();
+    }
+
+    /// Returns an iterator over every key with its value.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> + '_ {
+        self.inner.iter().map(|(key, value)| {
+            let key = std::str::from_utf8(key).expect("keys are UTF-8 on insert");
+            let value = std::str::from_utf8(value).expect("values are UTF-8 on insert");
+            (key, value)
+        })
+    }
+}
+
+impl TryFrom<ByteBox> for StrBox {
+    type Error = InvalidUtf8Entry;
+
+    /// Wraps a table after checking that all of its keys and values are UTF-8.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InvalidUtf8Entry`] for the first entry, in iteration order, whose key or
+    /// value is not UTF-8.
+    fn try_from(inner: ByteBox) -> Result<Self, Self::Error> {
+        for (key, value) in inner.iter() {
+            for (bytes, in_value) in [(key, false), (value, true)] {
+                if let Err(err) = std::str::from_utf8(bytes) {
+                    return Err(InvalidUtf8Entry {
+                        key: key.to_vec(),
+                        in_value,
+                        valid_up_to: err.valid_up_to(),
+                    });
+                }
+            }
+        }
+        Ok(StrBox { inner })
+    }
+}
+
+impl From<StrBox> for ByteBox {
+    fn from(strbox: StrBox) -> Self {
+        strbox.inner
+    }
+}
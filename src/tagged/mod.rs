@@ -0,0 +1,97 @@
+//! A table whose values carry a one-byte type tag.
+use super::*;
+
+/// A `ByteBox` layer where every value carries a one-byte, user-defined type tag.
+///
+/// Heterogeneous tables can record how each value is encoded (string, integer, blob...)
+/// and dispatch decoding on the tag, without embedding it in the value bytes by hand.
+/// The tag is stored in front of the value inside the underlying `ByteBox`.
+///
+/// # Examples
+///
+/// ```rust
+/// use bytesbox::tagged::TaggedByteBox;
+///
+/// const TEXT: u8 = 1;
+/// const U64: u8 = 2;
+///
+/// let mut table = TaggedByteBox::new();
+/// table.insert(b"name", TEXT, b"bytesbox");
+/// table.insert(b"hits", U64, &42u64.to_le_bytes());
+///
+/// match table.get_tagged(b"hits") {
+///     Some((U64, bytes)) => assert_eq!(u64::from_le_bytes(bytes.try_into().unwrap()), 42),
+///     _ => unreachable!(),
+/// }
+/// assert_eq!(table.get_tagged(b"name"), Some((TEXT, &b"bytesbox"[..])));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TaggedByteBox {
+    inner: ByteBox,
+}
+
+impl TaggedByteBox {
+    /// Creates a new, empty `TaggedByteBox`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of key-value pairs stored.
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns `true` if no key-value pair is stored.
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Inserts a key with a tagged value, replacing both the tag and the value if the key
+    /// already exists.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if a new key-value pair was inserted.
+    /// * `false` if an existing key was updated.
+    pub fn insert(&mut self, key: &[u8], tag: u8, value: &[u8]) -> bool {
+        let mut stored = Vec::with_capacity(value.len() + 1);
+        stored.push(tag);
+        stored.extend_from_slice(value);
+        self.inner.insert(key, &stored)
+    }
+
+    /// Retrieves the tag and the value associated with the given key.
+    pub fn get_tagged(&self, key: &[u8]) -> Option<(u8, &[u8])> {
+        self.inner.get(key).and_then(split_tag)
+    }
+
+    /// Retrieves the value associated with the given key, without its tag.
+    pub fn get(&self, key: &[u8]) -> Option<&[u8]> {
+        self.get_tagged(key).map(|(_, value)| value)
+    }
+
+    /// Retrieves the tag associated with the given key.
+    pub fn tag(&self, key: &[u8]) -> Option<u8> {
+        self.get_tagged(key).map(|(tag, _)| tag)
+    }
+
+    /// Removes the key, returning its tag and value.
+    pub fn remove(&mut self, key: &[u8]) -> Option<(u8, Vec<u8>)> {
+        let mut stored = self.inner.remove(key)?;
+        let tag = stored.remove(0);
+        Some((tag, stored))
+    }
+
+    /// Returns an iterator over every key with its tag and value.
+    pub fn iter(&self) -> impl Iterator<Item = (&[u8], u8, &[u8])> + '_ {
+        self.inner.iter().filter_map(|(key, stored)| {
+            let (tag, value) = split_tag(stored)?;
+            Some((key, tag, value))
+        })
+    }
+}
+
+fn split_tag(stored: &[u8]) -> Option<(u8, &[u8])> {
+    let (&tag, value) = stored.split_first()?;
+    Some((tag, value))
+}
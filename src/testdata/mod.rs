@@ -0,0 +1,69 @@
+//! Canonical snapshot vectors, enabled by the `testdata` feature.
+//!
+//! Services that read or write the [snapshot format](crate::snapshot::SnapshotFormat)
+//! with their own code can check it against these vectors in their integration tests.
+//! Every vector encodes [`FIXTURE_ENTRIES`], in that order. Entry order inside a snapshot
+//! is otherwise unspecified, so a reader must not depend on it and a writer's output
+//! should be compared entry by entry rather than byte for byte.
+use super::*;
+
+/// The entries encoded by every snapshot vector, covering an empty key and an empty value.
+pub const FIXTURE_ENTRIES: &[(&[u8], &[u8])] = &[
+    (b"key", b"value"),
+    (b"", b"empty key"),
+    (b"empty value", b""),
+];
+
+/// [`FIXTURE_ENTRIES`] as a [`SnapshotFormat::V1`](crate::snapshot::SnapshotFormat::V1)
+/// snapshot.
+pub const SNAPSHOT_V1: &[u8] = b"BYTEBOX1\
+\x03\0\0\0\0\0\0\0\
+\x03\0\0\0\0\0\0\0key\x05\0\0\0\0\0\0\0value\
+\0\0\0\0\0\0\0\0\x09\0\0\0\0\0\0\0empty key\
+\x0b\0\0\0\0\0\0\0empty value\0\0\0\0\0\0\0\0";
+
+/// [`FIXTURE_ENTRIES`] as a [`SnapshotFormat::V2`](crate::snapshot::SnapshotFormat::V2)
+/// snapshot holding only the entries section.
+pub const SNAPSHOT_V2: &[u8] = b"BYTEBOX2\
+\x01\0\0\0\0\0\0\0\x54\0\0\0\0\0\0\0\
+\x03\0\0\0\0\0\0\0\
+\x03\0\0\0\0\0\0\0key\x05\0\0\0\0\0\0\0value\
+\0\0\0\0\0\0\0\0\x09\0\0\0\0\0\0\0empty key\
+\x0b\0\0\0\0\0\0\0empty value\0\0\0\0\0\0\0\0";
+
+/// [`SNAPSHOT_V2`] preceded by a non-critical section (tag 2) that readers must skip.
+pub const SNAPSHOT_V2_SKIPPABLE_SECTION: &[u8] = b"BYTEBOX2\
+\x02\0\0\0\0\0\0\0\x04\0\0\0\0\0\0\0meta\
+\x01\0\0\0\0\0\0\0\x54\0\0\0\0\0\0\0\
+\x03\0\0\0\0\0\0\0\
+\x03\0\0\0\0\0\0\0key\x05\0\0\0\0\0\0\0value\
+\0\0\0\0\0\0\0\0\x09\0\0\0\0\0\0\0empty key\
+\x0b\0\0\0\0\0\0\0empty value\0\0\0\0\0\0\0\0";
+
+/// [`SNAPSHOT_V2`] preceded by an unknown critical section, which readers must refuse.
+pub const SNAPSHOT_V2_CRITICAL_SECTION: &[u8] = b"BYTEBOX2\
+\x02\0\0\0\0\0\0\x80\x04\0\0\0\0\0\0\0meta\
+\x01\0\0\0\0\0\0\0\x54\0\0\0\0\0\0\0\
+\x03\0\0\0\0\0\0\0\
+\x03\0\0\0\0\0\0\0key\x05\0\0\0\0\0\0\0value\
+\0\0\0\0\0\0\0\0\x09\0\0\0\0\0\0\0empty key\
+\x0b\0\0\0\0\0\0\0empty value\0\0\0\0\0\0\0\0";
+
+/// Returns a table holding [`FIXTURE_ENTRIES`].
+///
+/// # Examples
+///
+/// ```rust
+/// use bytesbox::testdata::{self, SNAPSHOT_V1};
+/// use bytesbox::ByteBox;
+///
+/// let read = ByteBox::read_snapshot(SNAPSHOT_V1).unwrap();
+/// assert_eq!(read.content_hash(), testdata::fixture().content_hash());
+/// ```
+pub fn fixture() -> ByteBox {
+    let mut byte_box = ByteBox::new();
+    for (key, value) in FIXTURE_ENTRIES {
+        byte_box.insert(key, value);
+    }
+    byte_box
+}
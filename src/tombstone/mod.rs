@@ -0,0 +1,189 @@
+//! Soft removal: entries hidden from the table but kept for a while as tombstones.
+use super::*;
+
+use crate::map::ByteMap;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// A soft-removed value and when it was removed.
+#[derive(Debug, Clone)]
+struct Tombstone {
+    value: Vec<u8>,
+    removed_at: Instant,
+}
+
+/// The tombstones of a `ByteBox` and how long they are kept.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TombstoneLog {
+    entries: ByteMap<Tombstone>,
+    retention: Option<Duration>,
+    /// Soft removals in the order they happened, kept while a retention window is set so
+    /// that expired tombstones are found oldest first. Entries whose tombstone was
+    /// dropped or replaced since are skipped when their turn comes.
+    expiry: VecDeque<(Vec<u8>, Instant)>,
+}
+
+impl TombstoneLog {
+    /// Drops the tombstone of `key`, if any; cheap when there are no tombstones.
+    pub(crate) fn forget(&mut self, key: &[u8]) {
+        if !self.entries.is_empty() {
+            self.entries.remove(key);
+        }
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.entries.clear();
+        self.expiry.clear();
+    }
+
+    fn insert(&mut self, key: &[u8], value: Vec<u8>) {
+        let removed_at = Instant::now();
+        if self.retention.is_some() {
+            self.expiry.push_back((key.to_vec(), removed_at));
+        }
+        self.entries.insert(key, Tombstone { value, removed_at });
+    }
+
+    fn set_retention(&mut self, retention: Option<Duration>) {
+        self.retention = retention;
+        self.expiry.clear();
+        if retention.is_some() {
+            let mut order: Vec<(Vec<u8>, Instant)> = self
+                .entries
+                .iter()
+                .map(|(key, tombstone)| (key.to_vec(), tombstone.removed_at))
+                .collect();
+            order.sort_by_key(|(_, removed_at)| *removed_at);
+            self.expiry.extend(order);
+        }
+    }
+
+    fn is_expired(&self, tombstone: &Tombstone, now: Instant) -> bool {
+        self.retention
+            .is_some_and(|retention| now.duration_since(tombstone.removed_at) >= retention)
+    }
+
+    /// Drops the tombstones older than the retention window.
+    fn purge_expired(&mut self) -> usize {
+        let mut unlimited = usize::MAX;
+        self.purge_expired_within(&mut unlimited)
+    }
+
+    /// Drops expired tombstones, oldest first, examining at most `budget` soft removals
+    /// and deducting them from it.
+    ///
+    /// # Returns
+    ///
+    /// The number of tombstones dropped.
+    pub(crate) fn purge_expired_within(&mut self, budget: &mut usize) -> usize {
+        let Some(retention) = self.retention else {
+            return 0;
+        };
+        let now = Instant::now();
+        let mut purged = 0;
+        while *budget > 0 {
+            match self.expiry.front() {
+                Some((_, removed_at)) if now.duration_since(*removed_at) >= retention => {}
+                _ => break,
+            }
+            let Some((key, removed_at)) = self.expiry.pop_front() else {
+                break;
+            };
+            *budget -= 1;
+            let current = self.entries.get(&key);
+            if current.is_some_and(|tombstone| tombstone.removed_at == removed_at) {
+                self.entries.remove(&key);
+                purged += 1;
+            }
+        }
+        purged
+    }
+}
+
+impl ByteBox {
+    /// Removes `key` from the table but keeps its value as a tombstone.
+    ///
+    /// The key becomes invisible to `get`, iteration and every other method, exactly as
+    /// after [`ByteBox::remove`], and is listed by [`ByteBox::tombstones`] so that the
+    /// deletion can be propagated. [`ByteBox::restore`] brings it back until the tombstone
+    /// is dropped by [`ByteBox::compact`], by the retention window, or by inserting the key
+    /// again.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the key was present.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut bytebox = ByteBox::new();
+    /// bytebox.insert(b"user:1", b"ada");
+    ///
+    /// assert!(bytebox.soft_remove(b"user:1"));
+    /// assert_eq!(bytebox.get(b"user:1"), None);
+    /// assert_eq!(bytebox.tombstones().count(), 1);
+    ///
+    /// assert!(bytebox.restore(b"user:1"));
+    /// assert_eq!(bytebox.get(b"user:1"), Some(&b"ada"[..]));
+    /// ```
+    pub fn soft_remove(&mut self, key: &[u8]) -> bool {
+        self.tombstones.purge_expired();
+        match self.remove(key) {
+            Some(value) => {
+                self.tombstones.insert(key, value);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Puts a soft-removed key back with its value.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the key had a live tombstone and was restored; `false` if it was never
+    /// soft-removed, its tombstone expired or was compacted, or it was inserted again since.
+    pub fn restore(&mut self, key: &[u8]) -> bool {
+        self.tombstones.purge_expired();
+        match self.tombstones.entries.remove(key) {
+            Some(tombstone) => {
+                self.insert(key, &tombstone.value);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the soft-removed keys still within the retention window, with the time
+    /// elapsed since their removal.
+    pub fn tombstones(&self) -> impl Iterator<Item = (&[u8], Duration)> + '_ {
+        let now = Instant::now();
+        self.tombstones
+            .entries
+            .iter()
+            .filter(move |(_, tombstone)| !self.tombstones.is_expired(tombstone, now))
+            .map(move |(key, tombstone)| (key, now.duration_since(tombstone.removed_at)))
+    }
+
+    /// Sets how long tombstones are kept; `None`, the default, keeps them until
+    /// [`ByteBox::compact`].
+    ///
+    /// Expired tombstones are ignored at once and freed by the next soft removal, restore,
+    /// [`ByteBox::maintain`] call or compaction.
+    pub fn set_tombstone_retention(&mut self, retention: Option<Duration>) {
+        self.tombstones.set_retention(retention);
+    }
+
+    /// Drops every tombstone, making soft removals final.
+    ///
+    /// # Returns
+    ///
+    /// The number of tombstones dropped.
+    pub fn compact(&mut self) -> usize {
+        let dropped = self.tombstones.entries.len();
+        self.tombstones.clear();
+        dropped
+    }
+}
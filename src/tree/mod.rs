@@ -0,0 +1,309 @@
+//! An ordered counterpart of `ByteBox`.
+use super::*;
+
+use std::collections::{btree_map, BTreeMap};
+use std::ops::{Bound, RangeBounds};
+
+/// A byte-keyed map with the API of `ByteBox`, storing its entries in key order.
+///
+/// Entries live in a B-tree, so iteration is sorted by key, byte by byte, and
+/// [`ByteTree::range`] scans a key range without visiting the rest of the map. Lookups
+/// cost `O(log n)` key comparisons instead of the hashing of a `ByteBox`; prefer the hash
+/// table when ordered scans are not needed.
+///
+/// # Examples
+///
+/// ```rust
+/// use bytesbox::tree::ByteTree;
+///
+/// let mut tree = ByteTree::new();
+/// tree.insert(b"b:2", b"two");
+/// tree.insert(b"a:1", b"one");
+/// tree.insert(b"b:1", b"one");
+///
+/// let keys: Vec<&[u8]> = tree.iter().map(|(key, _)| key).collect();
+/// assert_eq!(keys, vec![&b"a:1"[..], b"b:1", b"b:2"]);
+///
+/// let b: Vec<&[u8]> = tree.range(b"b:"..b"c:").map(|(key, _)| key).collect();
+/// assert_eq!(b, vec![&b"b:1"[..], b"b:2"]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ByteTree {
+    inner: BTreeMap<Vec<u8>, Vec<u8>>,
+}
+
+impl ByteTree {
+    /// Creates a new, empty `ByteTree`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of key-value pairs stored.
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns `true` if no key-value pair is stored.
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Inserts a key-value pair, replacing the value of an existing key.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - A byte slice representing the key.
+    /// * `value` - A byte slice representing the value.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if a new key-value pair was inserted.
+    /// * `false` if an existing key was updated.
+    pub fn insert(&mut self, key: &[u8], value: &[u8]) -> bool {
+        if let Some(stored) = self.inner.get_mut(key) {
+            stored.clear();
+            stored.extend_from_slice(value);
+            return false;
+        }
+        self.inner.insert(key.to_vec(), value.to_vec());
+        true
+    }
+
+    /// Retrieves the value associated with the given key.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(&[u8])` containing the value if the key exists.
+    /// * `None` if the key does not exist.
+    pub fn get(&self, key: &[u8]) -> Option<&[u8]> {
+        self.inner.get(key).map(Vec::as_slice)
+    }
+
+    /// Returns `true` if the map contains the given key.
+    pub fn contains_key(&self, key: &[u8]) -> bool {
+        self.inner.contains_key(key)
+    }
+
+    /// Removes the key, returning its value.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Vec<u8>)` containing the removed value if the key existed.
+    /// * `None` if the key was not found.
+    pub fn remove(&mut self, key: &[u8]) -> Option<Vec<u8>> {
+        self.inner.remove(key)
+    }
+
+    /// Removes all key-value pairs.
+    pub fn clear(&mut self) {
+        self.inner.clear();
+    }
+
+    /// Returns an iterator over every key with its value, in key order.
+    pub fn iter(&self) -> ByteTreeIter<'_> {
+        ByteTreeIter {
+            inner: self.inner.range::<[u8], _>(..),
+        }
+    }
+
+    /// Returns an iterator over the entries whose keys fall within `range`, in key order.
+    ///
+    /// Keys compare lexicographically, byte by byte, so `b"a"..b"b"` covers every key
+    /// starting with `a`. The iterator runs in both directions.
+    ///
+    /// # Arguments
+    ///
+    /// * `range` - A range of borrowed byte strings, such as `start..end` or `start..`;
+    ///   use [`ByteTree::iter`] for the whole map.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the range is greater than its end, or if they are equal
+    /// and both excluded.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::tree::ByteTree;
+    ///
+    /// let mut tree = ByteTree::new();
+    /// for day in ["2024-05-31", "2024-06-01", "2024-06-02", "2024-07-01"] {
+    ///     tree.insert(day.as_bytes(), b"log");
+    /// }
+    ///
+    /// assert_eq!(tree.range(b"2024-06"..b"2024-07").count(), 2);
+    /// assert_eq!(tree.range(&b"2024-06-02"[..]..).count(), 2);
+    /// ```
+    pub fn range<'k, K, R>(&self, range: R) -> ByteTreeIter<'_>
+    where
+        K: AsRef<[u8]> + ?Sized + 'k,
+        R: RangeBounds<&'k K>,
+    {
+        let bounds = (as_bytes(range.start_bound()), as_bytes(range.end_bound()));
+        ByteTreeIter {
+            inner: self.inner.range::<[u8], _>(bounds),
+        }
+    }
+
+    /// Returns the entry with the greatest key less than or equal to `key`.
+    ///
+    /// # Returns
+    ///
+    /// * `Some((&[u8], &[u8]))` containing the nearest key at or below `key` and its value.
+    /// * `None` if every key is greater than `key`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::tree::ByteTree;
+    ///
+    /// let mut config = ByteTree::new();
+    /// config.insert(b"v0001", b"timeout=10");
+    /// config.insert(b"v0005", b"timeout=30");
+    ///
+    /// assert_eq!(config.floor(b"v0003"), Some((&b"v0001"[..], &b"timeout=10"[..])));
+    /// assert_eq!(config.floor(b"v0005").unwrap().0, b"v0005");
+    /// assert_eq!(config.floor(b"v0000"), None);
+    /// ```
+    pub fn floor(&self, key: &[u8]) -> Option<(&[u8], &[u8])> {
+        self.inner
+            .range::<[u8], _>((Bound::Unbounded, Bound::Included(key)))
+            .next_back()
+            .map(|(key, value)| (key.as_slice(), value.as_slice()))
+    }
+
+    /// Returns the entry with the smallest key greater than or equal to `key`.
+    ///
+    /// # Returns
+    ///
+    /// * `Some((&[u8], &[u8]))` containing the nearest key at or above `key` and its value.
+    /// * `None` if every key is less than `key`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::tree::ByteTree;
+    ///
+    /// let mut ring = ByteTree::new();
+    /// ring.insert(&[0x40], b"node-a");
+    /// ring.insert(&[0xc0], b"node-b");
+    ///
+    /// assert_eq!(ring.ceiling(&[0x41]), Some((&[0xc0][..], &b"node-b"[..])));
+    /// assert_eq!(ring.ceiling(&[0xc1]), None);
+    /// ```
+    pub fn ceiling(&self, key: &[u8]) -> Option<(&[u8], &[u8])> {
+        self.inner
+            .range::<[u8], _>((Bound::Included(key), Bound::Unbounded))
+            .next()
+            .map(|(key, value)| (key.as_slice(), value.as_slice()))
+    }
+
+    /// Removes every entry whose key falls within `range`.
+    ///
+    /// The map is split at both ends of the range and the entries outside it are joined
+    /// back, so no key is searched for individually. Cutting off the start or the end of
+    /// the map, as retention of time-ordered keys does, costs `O(log n)` plus dropping
+    /// the removed entries. An empty or inverted range removes nothing.
+    ///
+    /// # Arguments
+    ///
+    /// * `range` - A range of borrowed byte strings, as accepted by [`ByteTree::range`].
+    ///
+    /// # Returns
+    ///
+    /// The number of removed entries.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::tree::ByteTree;
+    ///
+    /// let mut tree = ByteTree::new();
+    /// for ts in ["2024-05-31T23:00", "2024-06-01T00:00", "2024-06-01T01:00", "2024-06-02T00:00"] {
+    ///     tree.insert(ts.as_bytes(), b"sample");
+    /// }
+    ///
+    /// assert_eq!(tree.remove_range(..&b"2024-06-01"[..]), 1);
+    /// assert_eq!(tree.remove_range(b"2024-06-01T01"..b"2024-06-02T00"), 1);
+    /// assert_eq!(tree.len(), 2);
+    /// ```
+    pub fn remove_range<'k, K, R>(&mut self, range: R) -> usize
+    where
+        K: AsRef<[u8]> + ?Sized + 'k,
+        R: RangeBounds<&'k K>,
+    {
+        let mut removed = match as_bytes(range.start_bound()) {
+            Bound::Unbounded => std::mem::take(&mut self.inner),
+            Bound::Included(start) => self.inner.split_off(start),
+            Bound::Excluded(start) => {
+                let mut removed = self.inner.split_off(start);
+                if let Some((key, value)) = removed.remove_entry(start) {
+                    self.inner.insert(key, value);
+                }
+                removed
+            }
+        };
+        let mut kept = match as_bytes(range.end_bound()) {
+            Bound::Unbounded => BTreeMap::new(),
+            Bound::Excluded(end) => removed.split_off(end),
+            Bound::Included(end) => {
+                let mut kept = removed.split_off(end);
+                if let Some((key, value)) = kept.remove_entry(end) {
+                    removed.insert(key, value);
+                }
+                kept
+            }
+        };
+        self.inner.append(&mut kept);
+        removed.len()
+    }
+}
+
+/// An iterator over entries of a [`ByteTree`], in key order.
+///
+/// This struct is created by the [`ByteTree::iter`] and [`ByteTree::range`] methods.
+#[derive(Debug, Clone)]
+pub struct ByteTreeIter<'a> {
+    inner: btree_map::Range<'a, Vec<u8>, Vec<u8>>,
+}
+
+impl<'a> Iterator for ByteTreeIter<'a> {
+    type Item = (&'a [u8], &'a [u8]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner
+            .next()
+            .map(|(key, value)| (key.as_slice(), value.as_slice()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for ByteTreeIter<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner
+            .next_back()
+            .map(|(key, value)| (key.as_slice(), value.as_slice()))
+    }
+}
+
+fn as_bytes<'k, K: AsRef<[u8]> + ?Sized>(bound: Bound<&&'k K>) -> Bound<&'k [u8]> {
+    match bound {
+        Bound::Included(key) => Bound::Included((*key).as_ref()),
+        Bound::Excluded(key) => Bound::Excluded((*key).as_ref()),
+        Bound::Unbounded => Bound::Unbounded,
+    }
+}
+
+impl From<&ByteBox> for ByteTree {
+    /// Copies the entries of a `ByteBox` into key order.
+    fn from(byte_box: &ByteBox) -> Self {
+        let mut tree = ByteTree::new();
+        for (key, value) in byte_box.iter() {
+            tree.insert(key, value);
+        }
+        tree
+    }
+}
@@ -0,0 +1,121 @@
+//! A `ByteBox` layer restricted to UTF-8 keys.
+use super::*;
+
+/// A `ByteBox` layer that only accepts UTF-8 keys.
+///
+/// Tables holding textual configuration can use it to reject binary garbage at the
+/// insert that lets it into the key space, instead of discovering it when the keys are
+/// printed or exported. Values stay arbitrary bytes.
+///
+/// # Examples
+///
+/// ```rust
+/// use bytesbox::utf8::Utf8KeyBox;
+///
+/// let mut config = Utf8KeyBox::new();
+/// config.insert_str("listen", b"0.0.0.0:8080");
+/// assert!(config.insert(b"log.level", b"info").is_ok());
+///
+/// let err = config.insert(b"bad\xff", b"x").unwrap_err();
+/// assert_eq!(err.valid_up_to, 3);
+/// assert_eq!(config.len(), 2);
+///
+/// let mut keys: Vec<&str> = config.iter().map(|(key, _)| key).collect();
+/// keys.sort();
+/// assert_eq!(keys, vec!["listen", "log.level"]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Utf8KeyBox {
+    inner: ByteBox,
+}
+
+impl Utf8KeyBox {
+    /// Creates a new, empty `Utf8KeyBox`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Wraps an existing table after checking that all of its keys are UTF-8.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InvalidUtf8Key`] for the first key, in iteration order, that is not UTF-8.
+    pub fn from_byte_box(inner: ByteBox) -> Result<Self, InvalidUtf8Key> {
+        for (key, _) in inner.iter() {
+            check_key(key)?;
+        }
+        Ok(Utf8KeyBox { inner })
+    }
+
+    /// Returns the underlying table.
+    pub fn as_byte_box(&self) -> &ByteBox {
+        &self.inner
+    }
+
+    /// Unwraps the underlying table.
+    pub fn into_inner(self) -> ByteBox {
+        self.inner
+    }
+
+    /// Returns the number of key-value pairs stored.
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns `true` if no key-value pair is stored.
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Inserts a key-value pair after checking that `key` is UTF-8.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(true)` if a new key-value pair was inserted.
+    /// * `Ok(false)` if an existing key was updated.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InvalidUtf8Key`] if `key` is not UTF-8; the table is left unchanged.
+    pub fn insert(&mut self, key: &[u8], value: &[u8]) -> Result<bool, InvalidUtf8Key> {
+        check_key(key)?;
+        Ok(self.inner.insert(key, value))
+    }
+
+    /// Inserts a key-value pair whose key is already known to be UTF-8.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if a new key-value pair was inserted.
+    /// * `false` if an existing key was updated.
+    pub fn insert_str(&mut self, key: &str, value: &[u8]) -> bool {
+        self.inner.insert(key.as_bytes(), value)
+    }
+
+    /// Retrieves the value associated with the given key.
+    pub fn get(&self, key: &str) -> Option<&[u8]> {
+        self.inner.get(key.as_bytes())
+    }
+
+    /// Removes the key, returning its value.
+    pub fn remove(&mut self, key: &str) -> Option<Vec<u8>> {
+        self.inner.remove(key.as_bytes())
+    }
+
+    /// Returns an iterator over every key, as a `&str`, with its value.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &[u8])> + '_ {
+        self.inner.iter().map(|(key, value)| {
+            let key = std::str::from_utf8(key).expect("keys are checked on insert");
+            (key, value)
+        })
+    }
+}
+
+fn check_key(key: &[u8]) -> Result<(), InvalidUtf8Key> {
+    std::str::from_utf8(key)
+        .map(|_| ())
+        .map_err(|err| InvalidUtf8Key {
+            key: key.to_vec(),
+            valid_up_to: err.valid_up_to(),
+        })
+}
@@ -0,0 +1,84 @@
+//! Read-only borrowed views of a table.
+use super::*;
+
+/// A borrowed, read-only view of a `ByteBox`.
+///
+/// The view exposes lookups, iteration and statistics only, so it can be handed to
+/// plugins or callbacks that must not modify the table. It is `Copy` and costs no more
+/// than a shared reference.
+///
+/// This struct is created by the [`ByteBox::view`] method.
+///
+/// # Examples
+///
+/// ```rust
+/// use bytesbox::view::ByteBoxView;
+/// use bytesbox::ByteBox;
+///
+/// fn plugin(table: ByteBoxView<'_>) -> usize {
+///     table.iter().filter(|(key, _)| key.starts_with(b"user:")).count()
+/// }
+///
+/// let mut bytebox = ByteBox::new();
+/// bytebox.insert(b"user:1", b"alice");
+/// bytebox.insert(b"config", b"on");
+/// assert_eq!(plugin(bytebox.view()), 1);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct ByteBoxView<'a> {
+    byte_box: &'a ByteBox,
+}
+
+impl<'a> ByteBoxView<'a> {
+    /// Retrieves the value associated with the given key.
+    pub fn get(&self, key: &[u8]) -> Option<&'a [u8]> {
+        self.byte_box.get(key)
+    }
+
+    /// Returns `true` if the table holds the given key.
+    pub fn contains_key(&self, key: &[u8]) -> bool {
+        self.byte_box.contains_key(key)
+    }
+
+    /// Returns the number of key-value pairs stored.
+    pub fn len(&self) -> usize {
+        self.byte_box.len()
+    }
+
+    /// Returns `true` if no key-value pair is stored.
+    pub fn is_empty(&self) -> bool {
+        self.byte_box.is_empty()
+    }
+
+    /// Returns an iterator over the key-value pairs.
+    pub fn iter(&self) -> ByteBoxIterator<'a> {
+        self.byte_box.iter()
+    }
+
+    /// Returns memory and occupancy figures for the table.
+    pub fn stats(&self) -> Stats {
+        self.byte_box.stats()
+    }
+}
+
+impl<'a> From<&'a ByteBox> for ByteBoxView<'a> {
+    fn from(byte_box: &'a ByteBox) -> Self {
+        ByteBoxView { byte_box }
+    }
+}
+
+impl<'a> IntoIterator for ByteBoxView<'a> {
+    type Item = (&'a [u8], &'a [u8]);
+    type IntoIter = ByteBoxIterator<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl ByteBox {
+    /// Returns a read-only view of the table for code that must not modify it.
+    pub fn view(&self) -> ByteBoxView<'_> {
+        ByteBoxView::from(self)
+    }
+}
@@ -0,0 +1,66 @@
+//! JavaScript bindings for `ByteBox`, enabled by the `wasm` feature.
+//!
+//! Keys and values cross the boundary as `Uint8Array`s.
+use super::*;
+
+use wasm_bindgen::prelude::*;
+
+/// A `ByteBox` exposed to JavaScript through `wasm-bindgen`.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Default)]
+pub struct JsByteBox {
+    inner: ByteBox,
+}
+
+#[wasm_bindgen]
+impl JsByteBox {
+    /// Creates a new, empty table.
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> JsByteBox {
+        JsByteBox::default()
+    }
+
+    /// Inserts a key-value pair, returning `true` if the key was new.
+    pub fn insert(&mut self, key: &[u8], value: &[u8]) -> bool {
+        self.inner.insert(key, value)
+    }
+
+    /// Returns a copy of the value stored for `key`, or `undefined` when it is missing.
+    pub fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+        self.inner.get(key).map(<[u8]>::to_vec)
+    }
+
+    /// Removes `key`, returning its value or `undefined` when it was missing.
+    pub fn remove(&mut self, key: &[u8]) -> Option<Vec<u8>> {
+        self.inner.remove(key)
+    }
+
+    /// Removes every entry.
+    pub fn clear(&mut self) {
+        self.inner.clear();
+    }
+
+    /// Returns the number of key-value pairs stored.
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns the plain-text visualization of the table.
+    #[wasm_bindgen(js_name = renderTable)]
+    pub fn render_table(&self) -> String {
+        self.inner.render_table()
+    }
+}
+
+impl From<ByteBox> for JsByteBox {
+    fn from(inner: ByteBox) -> Self {
+        JsByteBox { inner }
+    }
+}
+
+impl From<JsByteBox> for ByteBox {
+    fn from(table: JsByteBox) -> Self {
+        table.inner
+    }
+}
@@ -0,0 +1,139 @@
+//! Callbacks fired when the size of a table crosses a level.
+use super::*;
+
+use std::sync::Arc;
+
+/// The size figure a watermark watches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatermarkMetric {
+    /// The number of entries, as returned by [`ByteBox::len`].
+    Entries,
+    /// The total length of keys and values, as returned by [`ByteBox::content_bytes`].
+    Bytes,
+}
+
+/// Passed to a watermark callback when its level is crossed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WatermarkEvent {
+    /// The figure that crossed the level.
+    pub metric: WatermarkMetric,
+    /// The level the watermark was registered with.
+    pub level: usize,
+    /// The value of the figure after the mutation.
+    pub current: usize,
+    /// `true` if the figure rose to the level or above, `false` if it fell back below.
+    pub rising: bool,
+}
+
+/// A registered watermark and whether the table is currently at or above its level.
+#[derive(Clone)]
+pub(crate) struct Watermark {
+    metric: WatermarkMetric,
+    level: usize,
+    above: bool,
+    callback: Arc<dyn Fn(WatermarkEvent) + Send + Sync>,
+}
+
+impl ByteBox {
+    /// Returns the total length of all keys and values stored.
+    ///
+    /// The figure is kept up to date by every mutation, so reading it is free.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::ByteBox;
+    ///
+    /// let mut bytebox = ByteBox::new();
+    /// bytebox.insert(b"key", b"value");
+    /// assert_eq!(bytebox.content_bytes(), 8);
+    /// ```
+    pub fn content_bytes(&self) -> usize {
+        self.content_bytes
+    }
+
+    /// Registers `callback` to run when `metric` crosses `level`.
+    ///
+    /// The callback runs synchronously inside the mutating call that moved the figure to
+    /// `level` or above, and again when a later call moves it back below, so an
+    /// application can start shedding load or flushing before a hard limit is hit and stop
+    /// once it has recovered. Registering several levels (say 80% and 95% of a budget)
+    /// gives increasingly urgent signals. A table already at or above `level` does not
+    /// fire until it has fallen below it once. Clones of the table share the callbacks.
+    ///
+    /// # Arguments
+    ///
+    /// * `metric` - The figure to watch.
+    /// * `level` - The value at which the watermark is reached.
+    /// * `callback` - Receives a [`WatermarkEvent`] on every crossing.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytesbox::watermark::WatermarkMetric;
+    /// use bytesbox::ByteBox;
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    /// use std::sync::Arc;
+    ///
+    /// let max_entries = 10;
+    /// let shedding = Arc::new(AtomicBool::new(false));
+    ///
+    /// let mut bytebox = ByteBox::new();
+    /// let flag = Arc::clone(&shedding);
+    /// bytebox.add_watermark(WatermarkMetric::Entries, max_entries * 8 / 10, move |event| {
+    ///     flag.store(event.rising, Ordering::Relaxed);
+    /// });
+    ///
+    /// for i in 0..8u8 {
+    ///     bytebox.insert(&[i], b"value");
+    /// }
+    /// assert!(shedding.load(Ordering::Relaxed));
+    ///
+    /// bytebox.remove(&[0]);
+    /// assert!(!shedding.load(Ordering::Relaxed));
+    /// ```
+    pub fn add_watermark<F>(&mut self, metric: WatermarkMetric, level: usize, callback: F)
+    where
+        F: Fn(WatermarkEvent) + Send + Sync + 'static,
+    {
+        let above = self.watermark_figure(metric) >= level;
+        self.watermarks.push(Watermark {
+            metric,
+            level,
+            above,
+            callback: Arc::new(callback),
+        });
+    }
+
+    /// Removes every registered watermark.
+    pub fn clear_watermarks(&mut self) {
+        self.watermarks.clear();
+    }
+
+    /// Fires the callbacks of the watermarks whose level was crossed since the last check.
+    pub(crate) fn check_watermarks(&mut self) {
+        for index in 0..self.watermarks.len() {
+            let watermark = &self.watermarks[index];
+            let current = self.watermark_figure(watermark.metric);
+            let above = current >= watermark.level;
+            if above == watermark.above {
+                continue;
+            }
+            self.watermarks[index].above = above;
+            let watermark = &self.watermarks[index];
+            (watermark.callback)(WatermarkEvent {
+                metric: watermark.metric,
+                level: watermark.level,
+                current,
+                rising: above,
+            });
+        }
+    }
+
+    fn watermark_figure(&self, metric: WatermarkMetric) -> usize {
+        match metric {
+            WatermarkMetric::Entries => self.len,
+            WatermarkMetric::Bytes => self.content_bytes,
+        }
+    }
+}
@@ -0,0 +1,195 @@
+//! Synthetic workloads for benchmarks and examples, enabled by the `workload` feature.
+//!
+//! Generators are fully deterministic for a given seed, so every machine runs the same
+//! keys and values. `cargo run --release --example workload --features workload` times
+//! inserts and lookups on such a workload; the timings themselves vary between machines.
+
+/// A small, fast pseudo-random generator (SplitMix64); not suitable for cryptography.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    /// Creates a generator from a seed; equal seeds produce equal sequences.
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    /// Returns the next pseudo-random `u64`.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a pseudo-random float in `[0, 1)`.
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Returns a pseudo-random integer in `[0, bound)`; `bound` must not be zero.
+    pub fn below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
+}
+
+/// How keys are picked from the key space.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum KeyDistribution {
+    /// Every key is equally likely.
+    Uniform,
+    /// Key of rank `k` is picked with a probability proportional to `1 / k^exponent`;
+    /// an exponent around `1.0` models typical cache and web traffic.
+    Zipf { exponent: f64 },
+}
+
+/// How long generated values are.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueSize {
+    /// Every value has exactly this many bytes.
+    Fixed(usize),
+    /// Value lengths are spread evenly over `min..=max` bytes.
+    Uniform { min: usize, max: usize },
+}
+
+/// Description of a synthetic key-value workload.
+///
+/// # Examples
+///
+/// ```rust
+/// use bytesbox::workload::{KeyDistribution, ValueSize, Workload};
+/// use bytesbox::ByteBox;
+///
+/// let workload = Workload {
+///     key_space: 1_000,
+///     keys: KeyDistribution::Zipf { exponent: 1.0 },
+///     values: ValueSize::Uniform { min: 8, max: 64 },
+///     seed: 42,
+/// };
+///
+/// let mut bytebox = ByteBox::new();
+/// for (key, value) in workload.pairs().take(10_000) {
+///     bytebox.insert(&key, &value);
+/// }
+/// assert!(bytebox.len() <= 1_000);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Workload {
+    /// Number of distinct keys that can be generated.
+    pub key_space: usize,
+    /// Distribution used to pick keys.
+    pub keys: KeyDistribution,
+    /// Distribution of value lengths.
+    pub values: ValueSize,
+    /// Seed making the workload reproducible.
+    pub seed: u64,
+}
+
+impl Workload {
+    /// Returns the key of the given rank, as produced by the generators.
+    pub fn key(rank: usize) -> Vec<u8> {
+        format!("key:{:08}", rank).into_bytes()
+    }
+
+    /// Returns an endless iterator over keys drawn from the key distribution.
+    pub fn keys(&self) -> Keys {
+        Keys {
+            rng: Rng::new(self.seed),
+            sampler: Sampler::new(self.key_space.max(1), self.keys),
+        }
+    }
+
+    /// Returns an endless iterator over key-value pairs.
+    pub fn pairs(&self) -> Pairs {
+        Pairs {
+            keys: self.keys(),
+            // A distinct stream so value sizes do not correlate with key picks.
+            rng: Rng::new(self.seed ^ 0x5851_f42d_4c95_7f2d),
+            values: self.values,
+        }
+    }
+}
+
+/// Draws key ranks, precomputing the cumulative distribution for Zipf workloads.
+#[derive(Debug, Clone)]
+enum Sampler {
+    Uniform(usize),
+    Cumulative(Vec<f64>),
+}
+
+impl Sampler {
+    fn new(key_space: usize, distribution: KeyDistribution) -> Self {
+        match distribution {
+            KeyDistribution::Uniform => Sampler::Uniform(key_space),
+            KeyDistribution::Zipf { exponent } => {
+                let mut total = 0.0;
+                let mut cumulative: Vec<f64> = (1..=key_space)
+                    .map(|rank| {
+                        total += 1.0 / (rank as f64).powf(exponent);
+                        total
+                    })
+                    .collect();
+                for weight in &mut cumulative {
+                    *weight /= total;
+                }
+                Sampler::Cumulative(cumulative)
+            }
+        }
+    }
+
+    fn sample(&self, rng: &mut Rng) -> usize {
+        match self {
+            Sampler::Uniform(key_space) => rng.below(*key_space),
+            Sampler::Cumulative(cumulative) => {
+                let point = rng.next_f64();
+                cumulative
+                    .partition_point(|&weight| weight < point)
+                    .min(cumulative.len() - 1)
+            }
+        }
+    }
+}
+
+/// An endless iterator over generated keys.
+///
+/// This struct is created by the [`Workload::keys`] method.
+#[derive(Debug, Clone)]
+pub struct Keys {
+    rng: Rng,
+    sampler: Sampler,
+}
+
+impl Iterator for Keys {
+    type Item = Vec<u8>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(Workload::key(self.sampler.sample(&mut self.rng)))
+    }
+}
+
+/// An endless iterator over generated key-value pairs.
+///
+/// This struct is created by the [`Workload::pairs`] method.
+#[derive(Debug, Clone)]
+pub struct Pairs {
+    keys: Keys,
+    rng: Rng,
+    values: ValueSize,
+}
+
+impl Iterator for Pairs {
+    type Item = (Vec<u8>, Vec<u8>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let key = self.keys.next()?;
+        let len = match self.values {
+            ValueSize::Fixed(len) => len,
+            ValueSize::Uniform { min, max } => min + self.rng.below(max.saturating_sub(min) + 1),
+        };
+        let value = (0..len).map(|_| self.rng.next_u64() as u8).collect();
+        Some((key, value))
+    }
+}
@@ -0,0 +1,71 @@
+use bytesbox::analysis::Suggestion;
+use bytesbox::ByteBox;
+
+#[test]
+fn analyze_suggests_shrink() {
+    let mut byte_box = ByteBox::prealloc(4096);
+    byte_box.insert(b"key1", b"value1");
+    byte_box.insert(b"key2", b"value2");
+
+    let report = byte_box.analyze();
+    assert_eq!(report.len, 2);
+    assert_eq!(report.occupied_cells, 2);
+    assert!(report
+        .suggestions
+        .contains(&Suggestion::ShrinkCapacity { recommended: 16 }));
+}
+
+#[test]
+fn analyze_suggests_grow() {
+    let mut byte_box = ByteBox::prealloc(4);
+    byte_box.insert(b"key1", b"value1");
+    byte_box.insert(b"key2", b"value2");
+    byte_box.insert(b"key3", b"value3");
+
+    let report = byte_box.analyze();
+    assert!(matches!(
+        report.suggestions.first(),
+        Some(Suggestion::GrowCapacity { .. })
+    ));
+    assert!(report.to_string().contains("load factor threshold reached"));
+}
+
+#[test]
+fn analyze_flags_key_length_distribution() {
+    let mut tiny = ByteBox::prealloc(64);
+    for i in 0..32u16 {
+        tiny.insert(&i.to_le_bytes(), b"telemetry");
+    }
+    let report = tiny.analyze();
+    assert_eq!(report.median_key_len, 2);
+    assert_eq!(report.short_keys, 1.0);
+    assert!(report
+        .suggestions
+        .contains(&Suggestion::GroupShortKeys { share: 1.0 }));
+    assert!(report
+        .to_string()
+        .contains("100% of keys are shorter than 8 bytes"));
+
+    let mut long = ByteBox::prealloc(64);
+    for i in 0..32u32 {
+        let mut key = vec![b'k'; 300];
+        key.extend_from_slice(&i.to_le_bytes());
+        long.insert(&key, b"v");
+    }
+    let report = long.analyze();
+    assert_eq!(report.long_keys, 1.0);
+    assert!(report
+        .suggestions
+        .contains(&Suggestion::ShortenLongKeys { share: 1.0 }));
+
+    let mut mixed = ByteBox::prealloc(64);
+    for i in 0..32u32 {
+        mixed.insert(format!("user:{:04}", i).as_bytes(), b"v");
+    }
+    let report = mixed.analyze();
+    assert_eq!(report.short_keys, 0.0);
+    assert!(!report.suggestions.iter().any(|suggestion| matches!(
+        suggestion,
+        Suggestion::GroupShortKeys { .. } | Suggestion::ShortenLongKeys { .. }
+    )));
+}
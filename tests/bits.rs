@@ -0,0 +1,23 @@
+use bytesbox::ByteBox;
+
+#[test]
+fn bitmap_values() {
+    let mut flags = ByteBox::new();
+    assert!(!flags.test_bit(b"user", 0));
+    assert!(!flags.clear_bit(b"user", 0));
+    assert_eq!(flags.get(b"user"), None);
+
+    for index in [0, 7, 8, 63] {
+        assert!(!flags.set_bit(b"user", index));
+        assert!(flags.set_bit(b"user", index));
+    }
+    assert_eq!(flags.get(b"user").unwrap().len(), 8);
+    assert!((0..64)
+        .filter(|&i| flags.test_bit(b"user", i))
+        .eq([0, 7, 8, 63]));
+
+    assert!(flags.clear_bit(b"user", 7));
+    assert!(!flags.test_bit(b"user", 7));
+    assert_eq!(flags.get(b"user").unwrap()[0], 0b0000_0001);
+    assert_eq!(flags.len(), 1);
+}
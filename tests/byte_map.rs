@@ -0,0 +1,30 @@
+use bytesbox::map::ByteMap;
+
+#[test]
+fn byte_map_stores_structured_values() {
+    let mut map: ByteMap<Vec<String>> = ByteMap::new();
+    assert_eq!(map.allocation(), 0);
+    assert_eq!(map.get(b"missing"), None);
+
+    for i in 0..500u32 {
+        assert!(map.insert(&i.to_be_bytes(), vec![i.to_string()]).is_none());
+    }
+    assert_eq!(map.len(), 500);
+    assert!(map.allocation() >= 500);
+
+    map.get_mut(&7u32.to_be_bytes())
+        .unwrap()
+        .push("seven".into());
+    assert_eq!(
+        map.insert(&7u32.to_be_bytes(), Vec::new()),
+        Some(vec!["7".to_string(), "seven".to_string()])
+    );
+    assert_eq!(map.remove(&8u32.to_be_bytes()), Some(vec!["8".to_string()]));
+    assert!(!map.contains_key(&8u32.to_be_bytes()));
+    assert_eq!(map.iter().count(), 499);
+    assert_eq!((&map).into_iter().filter(|(_, v)| v.is_empty()).count(), 1);
+
+    map.clear();
+    assert!(map.is_empty());
+    assert_eq!(map.iter().next(), None);
+}
@@ -0,0 +1,39 @@
+use bytesbox::tree::ByteTree;
+use bytesbox::ByteBox;
+
+#[test]
+fn byte_tree_keeps_keys_ordered() {
+    let mut byte_box = ByteBox::new();
+    for i in (0..100u32).rev() {
+        byte_box.insert(&i.to_be_bytes(), &i.to_le_bytes());
+    }
+    let mut tree = ByteTree::from(&byte_box);
+    assert_eq!(tree.len(), 100);
+    let keys: Vec<u32> = tree
+        .iter()
+        .map(|(key, _)| u32::from_be_bytes(key.try_into().unwrap()))
+        .collect();
+    assert_eq!(keys, (0..100).collect::<Vec<_>>());
+
+    assert!(tree.insert(b"", b"first"));
+    assert!(!tree.insert(b"", b"root"));
+    assert_eq!(tree.iter().next(), Some((&b""[..], &b"root"[..])));
+    assert_eq!(
+        tree.remove(&5u32.to_be_bytes()),
+        Some(5u32.to_le_bytes().to_vec())
+    );
+    assert!(!tree.contains_key(&5u32.to_be_bytes()));
+
+    let start = 3u32.to_be_bytes();
+    let end = 8u32.to_be_bytes();
+    let keys: Vec<u32> = tree
+        .range(&start..=&end)
+        .rev()
+        .map(|(key, _)| u32::from_be_bytes(key.try_into().unwrap()))
+        .collect();
+    assert_eq!(keys, vec![8, 7, 6, 4, 3]);
+    assert_eq!(tree.range(&b""[..]..).count(), tree.len());
+
+    tree.clear();
+    assert!(tree.is_empty());
+}
@@ -0,0 +1,13 @@
+use bytesbox::ByteBox;
+
+#[test]
+fn chain_lengths() {
+    let mut byte_box = ByteBox::prealloc(8);
+    for i in 0..5 {
+        byte_box.insert(format!("key{}", i).as_bytes(), b"value");
+    }
+
+    let lengths: Vec<usize> = byte_box.chain_lengths().collect();
+    assert_eq!(lengths.len(), byte_box.allocation());
+    assert_eq!(lengths.iter().sum::<usize>(), byte_box.len());
+}
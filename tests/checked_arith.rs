@@ -0,0 +1,45 @@
+use bytesbox::error::NumericError;
+use bytesbox::ByteBox;
+
+#[test]
+fn token_bucket() {
+    let mut buckets = ByteBox::new();
+    buckets.insert(b"client", &10u64.to_le_bytes());
+
+    for remaining in (0..10u64).rev() {
+        assert_eq!(buckets.checked_sub(b"client", 1u64), Ok(remaining));
+    }
+    assert_eq!(
+        buckets.checked_sub(b"client", 1u64),
+        Err(NumericError::Overflow)
+    );
+    assert_eq!(buckets.checked_add(b"client", 5u64), Ok(5));
+    assert_eq!(
+        buckets.checked_mul(b"client", u64::MAX),
+        Err(NumericError::Overflow)
+    );
+    assert_eq!(buckets.get(b"client"), Some(&5u64.to_le_bytes()[..]));
+}
+
+#[test]
+fn checked_arith_errors() {
+    let mut table = ByteBox::new();
+    table.insert(b"short", &[1, 2]);
+    assert_eq!(
+        table.checked_add(b"short", 1u32),
+        Err(NumericError::WidthMismatch {
+            expected: 4,
+            found: 2
+        })
+    );
+    assert_eq!(table.checked_add(b"short", 1u16), Ok(0x0202));
+    assert_eq!(
+        table.checked_add(b"missing", 1i8),
+        Err(NumericError::MissingKey)
+    );
+    table.insert(b"signed", &i32::MIN.to_le_bytes());
+    assert_eq!(
+        table.checked_mul(b"signed", -1i32),
+        Err(NumericError::Overflow)
+    );
+}
@@ -0,0 +1,74 @@
+use bytesbox::ByteBox;
+
+fn filled(entries: u32) -> ByteBox {
+    let mut byte_box = ByteBox::prealloc(4096);
+    for i in 0..entries {
+        byte_box.insert(&i.to_le_bytes(), &[i as u8; 32]);
+    }
+    byte_box
+}
+
+#[test]
+fn clone_cheap_shares_entries_until_written() {
+    let mut live = filled(1_000);
+    let total = live.stats().key_bytes + live.stats().value_bytes;
+    assert_eq!(live.stats().shared_bytes, 0);
+
+    let snapshot = live.clone_cheap();
+    assert_eq!(live.stats().shared_bytes, total);
+    assert_eq!(snapshot.stats().shared_bytes, total);
+
+    // Writes copy only what they touch, and never show through the other table.
+    live.insert(&0u32.to_le_bytes(), b"changed");
+    live.remove(&1u32.to_le_bytes());
+    live.insert(b"new", b"entry");
+    assert!(live.stats().shared_bytes > total - 3 * 36);
+    assert_eq!(snapshot.get(&0u32.to_le_bytes()), Some(&[0u8; 32][..]));
+    assert_eq!(snapshot.get(&1u32.to_le_bytes()), Some(&[1u8; 32][..]));
+    assert_eq!(snapshot.get(b"new"), None);
+    assert_eq!(snapshot.len(), 1_000);
+    assert_eq!(live.get(&0u32.to_le_bytes()), Some(&b"changed"[..]));
+    assert_eq!(live.len(), 1_000);
+
+    let mut edited = snapshot.clone_cheap();
+    edited
+        .replace_in_place(&2u32.to_le_bytes(), &[9u8; 32])
+        .unwrap();
+    assert_eq!(snapshot.get(&2u32.to_le_bytes()), Some(&[2u8; 32][..]));
+    assert_eq!(snapshot.content_hash(), filled(1_000).content_hash());
+
+    // Once the other side is gone, nothing is shared any more.
+    drop(edited);
+    drop(snapshot);
+    assert_eq!(live.stats().shared_bytes, 0);
+}
+
+#[test]
+fn clone_cheap_survives_relinking_writes() {
+    let mut live = filled(100);
+    let snapshot = live.clone_cheap();
+
+    live.retain(|key, _| key[0] % 2 == 0);
+    live.clear();
+    assert!(live.is_empty());
+    assert_eq!(snapshot.len(), 100);
+    assert_eq!(snapshot.iter().count(), 100);
+    assert_eq!(snapshot.content_hash(), filled(100).content_hash());
+
+    let mut resized = snapshot.clone_cheap();
+    for i in 100..5_000u32 {
+        resized.insert(&i.to_le_bytes(), b"grow");
+    }
+    assert_eq!(snapshot.len(), 100);
+    assert_eq!(snapshot.get(&5u32.to_le_bytes()), Some(&[5u8; 32][..]));
+    assert_eq!(resized.stats().shared_bytes, 0);
+}
+
+#[test]
+fn clone_copies_every_entry() {
+    let live = filled(100);
+    let copy = live.clone();
+    assert_eq!(copy.content_hash(), live.content_hash());
+    assert_eq!(live.stats().shared_bytes, 0);
+    assert_eq!(copy.stats().shared_bytes, 0);
+}
@@ -0,0 +1,37 @@
+use bytesbox::collation::{ascii_case_insensitive, natural};
+use bytesbox::ByteBox;
+
+#[test]
+fn sorted_iteration_follows_the_collation() {
+    let mut byte_box = ByteBox::new();
+    for key in ["b10", "B2", "a", "b2", "A", "b002", "b"] {
+        byte_box.insert(key.as_bytes(), b"");
+    }
+    let keys = |iter: std::vec::IntoIter<(&[u8], &[u8])>| -> Vec<String> {
+        iter.map(|(key, _)| String::from_utf8_lossy(key).into_owned())
+            .collect()
+    };
+
+    assert_eq!(
+        keys(byte_box.iter_sorted()),
+        ["A", "B2", "a", "b", "b002", "b10", "b2"]
+    );
+    assert_eq!(
+        keys(byte_box.iter_sorted_by(ascii_case_insensitive)),
+        ["A", "a", "b", "b002", "b10", "B2", "b2"]
+    );
+    assert_eq!(
+        keys(byte_box.iter_sorted_by(natural)),
+        ["A", "B2", "a", "b", "b002", "b2", "b10"]
+    );
+    assert_eq!(
+        keys(
+            byte_box
+                .iter_sorted_by(natural)
+                .rev()
+                .collect::<Vec<_>>()
+                .into_iter()
+        ),
+        ["b10", "b2", "b002", "b", "a", "B2", "A"]
+    );
+}
@@ -0,0 +1,63 @@
+use bytesbox::policy::DuplicatePolicy;
+use bytesbox::ByteBox;
+
+fn rebuilt(byte_box: &ByteBox) -> u64 {
+    let mut fresh = ByteBox::new();
+    for (key, value) in byte_box.iter() {
+        fresh.insert(key, value);
+    }
+    fresh.content_hash()
+}
+
+#[test]
+fn content_hash_tracks_every_mutation() {
+    let mut byte_box = ByteBox::new();
+    assert_eq!(byte_box.content_hash(), 0);
+
+    for i in 0..200u32 {
+        byte_box.insert(&i.to_le_bytes(), &[i as u8; 3]);
+    }
+    assert_eq!(byte_box.content_hash(), rebuilt(&byte_box));
+
+    byte_box.insert(&5u32.to_le_bytes(), b"overwritten");
+    byte_box.remove(&6u32.to_le_bytes());
+    byte_box
+        .replace_in_place(&7u32.to_le_bytes(), b"in place")
+        .unwrap();
+    byte_box
+        .insert_with_policy(&8u32.to_le_bytes(), b"+", DuplicatePolicy::Append(b","))
+        .unwrap();
+    byte_box
+        .swap(&9u32.to_le_bytes(), &10u32.to_le_bytes())
+        .unwrap();
+    byte_box.set_bit(b"bits", 12);
+    byte_box.clear_bit(b"bits", 12);
+    byte_box.retain(|key, value| {
+        value.push(key[0]);
+        key[0] % 3 != 0
+    });
+    byte_box.extract_if(|key, _| key[0] % 5 == 0).for_each(drop);
+    byte_box.rehash_with_seed(42);
+    assert_eq!(byte_box.content_hash(), rebuilt(&byte_box));
+
+    byte_box.clear();
+    assert_eq!(byte_box.content_hash(), 0);
+}
+
+#[test]
+fn content_hash_is_stable_across_builds() {
+    let mut byte_box = ByteBox::new();
+    byte_box.insert(b"", b"");
+    assert_eq!(byte_box.content_hash(), 0x76be_999e_3e25_b2a0);
+
+    let mut byte_box = ByteBox::prealloc(4);
+    byte_box.rehash_with_seed(99);
+    byte_box.insert(b"user:42", b"{\"name\":\"ada\"}");
+    byte_box.insert(b"user:7", b"{\"name\":\"grace\"}");
+    assert_eq!(byte_box.content_hash(), 0x1d55_9b5a_3f3b_7225);
+
+    // Values longer than a chunk add one term per 64 bytes.
+    let mut byte_box = ByteBox::new();
+    byte_box.insert(b"k", &(0..200u8).collect::<Vec<_>>());
+    assert_eq!(byte_box.content_hash(), 0x03c5_65ce_75bd_db19);
+}
@@ -0,0 +1,28 @@
+use bytesbox::debug::DEBUG_SAMPLE;
+use bytesbox::ByteBox;
+
+#[test]
+fn debug_is_bounded() {
+    let mut byte_box = ByteBox::new();
+    for i in 0..1000 {
+        byte_box.insert(format!("key{:04}", i).as_bytes(), b"value");
+    }
+
+    let summary = format!("{:?}", byte_box);
+    assert!(summary.starts_with("ByteBox { len: 1000, alloc: 2048,"));
+    assert_eq!(summary.matches("\"value\"").count(), DEBUG_SAMPLE);
+    assert!(summary.ends_with(".. }"));
+
+    let full = format!("{:?}", byte_box.debug_full());
+    assert_eq!(full.matches("\"value\"").count(), 1000);
+}
+
+#[test]
+fn debug_small_table() {
+    let mut byte_box = ByteBox::new();
+    byte_box.insert(b"key", b"value");
+    assert_eq!(
+        format!("{:?}", byte_box),
+        "ByteBox { len: 1, alloc: 16, load_factor: 0.0625, sample: {\"key\": \"value\"} }"
+    );
+}
@@ -0,0 +1,54 @@
+use bytesbox::dedup::DedupByteBox;
+
+#[test]
+fn dedup_shares_values() {
+    let mut table = DedupByteBox::new();
+
+    table.insert(b"index.html", b"text/html");
+    table.insert(b"about.html", b"text/html");
+    table.insert(b"logo.png", b"image/png");
+
+    assert_eq!(table.len(), 3);
+    assert_eq!(table.distinct_values(), 2);
+    assert_eq!(table.ref_count(b"text/html"), 2);
+    assert_eq!(table.get(b"index.html"), Some(&b"text/html"[..]));
+
+    table.insert(b"about.html", b"image/png");
+    assert_eq!(table.ref_count(b"text/html"), 1);
+    assert_eq!(table.ref_count(b"image/png"), 2);
+
+    assert_eq!(table.remove(b"index.html"), Some(b"text/html".to_vec()));
+    assert_eq!(table.distinct_values(), 1);
+    assert_eq!(table.get(b"index.html"), None);
+}
+
+#[test]
+fn dedup_stores_each_distinct_value_once() {
+    let mime_types: [&[u8]; 3] = [b"text/html", b"image/png", b"application/json"];
+    let mut table = DedupByteBox::new();
+    for i in 0..10_000usize {
+        table.insert(
+            format!("file{}.ext", i).as_bytes(),
+            mime_types[i % mime_types.len()],
+        );
+    }
+
+    let stats = table.stats();
+    assert_eq!(stats.len, 10_000);
+    assert_eq!(table.distinct_values(), 3);
+    assert_eq!(stats.value_bytes, 3334 * 9 + 3333 * 9 + 3333 * 16);
+    assert_eq!(stats.stored_value_bytes, 9 + 9 + 16);
+    assert_eq!(stats.value_savings(), stats.value_bytes - 34);
+
+    // A clone keeps its own counts.
+    let mut copy = table.clone();
+    copy.remove(b"file0.ext");
+    assert_eq!(copy.ref_count(b"text/html"), 3333);
+    assert_eq!(table.ref_count(b"text/html"), 3334);
+
+    for i in 0..10_000usize {
+        table.remove(format!("file{}.ext", i).as_bytes());
+    }
+    assert_eq!(table.distinct_values(), 0);
+    assert_eq!(table.stats().stored_value_bytes, 0);
+}
@@ -0,0 +1,60 @@
+use bytesbox::ByteBox;
+
+#[test]
+fn defragment_keeps_contents_and_reports_reclaimed_bytes() {
+    let mut byte_box = ByteBox::new();
+    assert_eq!(byte_box.defragment().reclaimed_bytes, 0);
+
+    for i in 0..2_000u32 {
+        byte_box.insert(&i.to_le_bytes(), &[7u8; 64]);
+    }
+    for i in 0..2_000u32 {
+        if i % 4 == 0 {
+            byte_box
+                .replace_in_place(&i.to_le_bytes(), &i.to_be_bytes())
+                .unwrap();
+        } else {
+            byte_box.remove(&i.to_le_bytes());
+        }
+    }
+    let expected = byte_box.content_hash();
+    let pooled = byte_box.pool_stats().pooled;
+    assert!(pooled > 0);
+
+    let report = byte_box.defragment();
+    assert_eq!(report.shrunk_to, Some(1024));
+    assert_eq!(byte_box.allocation(), 1024);
+    assert!(report.reclaimed_bytes >= 500 * 60 + pooled * 64);
+    assert_eq!(byte_box.pool_stats().pooled, 0);
+    assert_eq!(byte_box.len(), 500);
+    assert_eq!(byte_box.content_hash(), expected);
+    assert_eq!(
+        byte_box.get(&8u32.to_le_bytes()),
+        Some(&8u32.to_be_bytes()[..])
+    );
+
+    let again = byte_box.defragment();
+    assert_eq!((again.reclaimed_bytes, again.shrunk_to), (0, None));
+}
+
+#[test]
+fn defragment_keeps_iteration_order() {
+    let mut byte_box = ByteBox::prealloc(16);
+    for i in 0..10u32 {
+        byte_box.insert(&i.to_le_bytes(), &[0u8; 64]);
+    }
+    for i in 0..10u32 {
+        byte_box
+            .replace_in_place(&i.to_le_bytes(), &i.to_be_bytes())
+            .unwrap();
+    }
+    let keys = |byte_box: &ByteBox| -> Vec<Vec<u8>> {
+        byte_box.iter().map(|(key, _)| key.to_vec()).collect()
+    };
+    let before = keys(&byte_box);
+
+    let report = byte_box.defragment();
+    assert_eq!(report.shrunk_to, None);
+    assert!(report.reclaimed_bytes >= 10 * 60);
+    assert_eq!(keys(&byte_box), before);
+}
@@ -0,0 +1,49 @@
+use bytesbox::delimited::DelimitedFormat;
+use bytesbox::ByteBox;
+use std::io::ErrorKind;
+
+#[test]
+fn delimited_round_trips_raw_bytes() {
+    let mut byte_box = ByteBox::new();
+    byte_box.insert(b"plain", b"value");
+    byte_box.insert(b"with,comma", b"multi\nline\r\nvalue");
+    byte_box.insert(b"\"quoted\"", b"");
+    byte_box.insert(b"\xff\xfe", b"\x00binary\x01");
+
+    let mut csv = Vec::new();
+    byte_box
+        .write_delimited(&mut csv, DelimitedFormat::CSV)
+        .unwrap();
+    let restored = ByteBox::from_delimited(&csv[..], DelimitedFormat::CSV).unwrap();
+    assert_eq!(restored.content_hash(), byte_box.content_hash());
+
+    let mut tsv = Vec::new();
+    assert_eq!(
+        byte_box
+            .write_delimited(&mut tsv, b'\t')
+            .unwrap_err()
+            .kind(),
+        ErrorKind::InvalidInput
+    );
+    byte_box.remove(b"with,comma");
+    tsv.clear();
+    byte_box
+        .write_delimited(&mut tsv, DelimitedFormat::TSV)
+        .unwrap();
+    let restored = ByteBox::from_delimited(&tsv[..], b'\t').unwrap();
+    assert_eq!(restored.content_hash(), byte_box.content_hash());
+}
+
+#[test]
+fn malformed_records_name_their_line() {
+    let error = ByteBox::from_delimited(&b"a\t1\n\nno delimiter\n"[..], b'\t').unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+    assert!(error.to_string().starts_with("line 3"));
+
+    let error =
+        ByteBox::from_delimited(&b"k,\"open\nstill open"[..], DelimitedFormat::CSV).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+
+    let error = ByteBox::from_delimited(&b"\"k\"x,v\n"[..], DelimitedFormat::CSV).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+}
@@ -0,0 +1,48 @@
+use bytesbox::disk::DiskByteBox;
+use bytesbox::ByteBox;
+use std::io::Cursor;
+
+#[test]
+fn disk_table_lookups_read_single_blocks() {
+    let mut byte_box = ByteBox::new();
+    for i in 0..5_000u32 {
+        byte_box.insert(format!("key:{:05}", i).as_bytes(), &i.to_le_bytes());
+    }
+    byte_box.insert(b"", b"empty key");
+
+    let mut file = Vec::new();
+    byte_box.write_disk_table(&mut file).unwrap();
+    let mut disk = DiskByteBox::from_reader(Cursor::new(file)).unwrap();
+    assert_eq!(disk.len(), 5_001);
+    assert!(disk.block_count() > 1);
+
+    for i in (0..5_000u32).step_by(37) {
+        let key = format!("key:{:05}", i);
+        assert_eq!(
+            disk.get(key.as_bytes()).unwrap(),
+            Some(i.to_le_bytes().to_vec())
+        );
+    }
+    assert_eq!(disk.get(b"").unwrap(), Some(b"empty key".to_vec()));
+    assert_eq!(disk.get(b"key:00000x").unwrap(), None);
+    assert_eq!(disk.get(b"zzz").unwrap(), None);
+
+    let mut empty = Vec::new();
+    ByteBox::new().write_disk_table(&mut empty).unwrap();
+    let mut empty = DiskByteBox::from_reader(Cursor::new(empty)).unwrap();
+    assert!(empty.is_empty());
+    assert_eq!(empty.get(b"anything").unwrap(), None);
+}
+
+#[test]
+fn corrupt_disk_tables_are_rejected() {
+    let mut byte_box = ByteBox::new();
+    byte_box.insert(b"key", b"value");
+    let mut file = Vec::new();
+    byte_box.write_disk_table(&mut file).unwrap();
+
+    let mut truncated = file.clone();
+    truncated.truncate(file.len() - 1);
+    assert!(DiskByteBox::from_reader(Cursor::new(truncated)).is_err());
+    assert!(DiskByteBox::from_reader(Cursor::new(b"BYTEBOX1".to_vec())).is_err());
+}
@@ -0,0 +1,24 @@
+use bytesbox::ByteBox;
+
+#[test]
+fn display_is_sorted_and_truncates_with_precision() {
+    let mut forward = ByteBox::prealloc(4);
+    let mut backward = ByteBox::new();
+    for i in 0..50u8 {
+        forward.insert(&[b'k', i], &[b'v'; 3]);
+        backward.insert(&[b'k', 49 - i], &[b'v'; 3]);
+    }
+    assert_eq!(forward.to_string(), backward.to_string());
+    assert!(forward
+        .to_string()
+        .starts_with(r#"{"k\0": "vvv", "k\u{1}": "vvv""#));
+
+    let mut table = ByteBox::new();
+    assert_eq!(table.to_string(), "{}");
+    table.insert(b"big", &[b'x'; 10_000]);
+    table.insert(b"small", b"ok");
+    assert_eq!(
+        format!("{:.4}", table),
+        r#"{"big": "xxxx"… (10000 bytes), "small": "ok"}"#
+    );
+}
@@ -0,0 +1,47 @@
+use bytesbox::headers::HeaderEncoding;
+use bytesbox::ByteBox;
+
+#[test]
+fn empty_keys_and_values_are_ordinary_entries() {
+    let mut byte_box = ByteBox::new();
+    assert!(byte_box.insert(b"", b""));
+    assert!(byte_box.insert(b"x-empty", b""));
+    assert!(!byte_box.insert(b"", b""));
+
+    assert_eq!(byte_box.len(), 2);
+    assert!(byte_box.contains_empty_key());
+    assert_eq!(byte_box.get(b""), Some(&b""[..]));
+    assert_eq!(byte_box.get(b"x-empty"), Some(&b""[..]));
+    assert_eq!(byte_box.get(b"x-missing"), None);
+    assert!(!byte_box.contains_key(b"x-missing"));
+
+    let mut buf = Vec::new();
+    assert_eq!(byte_box.get_to_vec(b"x-empty", &mut buf), Some(0));
+    assert_eq!(byte_box.get_to_vec(b"x-missing", &mut buf), None);
+
+    for i in 0..100u32 {
+        byte_box.insert(&i.to_le_bytes(), b"");
+    }
+    assert!(byte_box.contains_empty_key());
+    assert_eq!(byte_box.remove(b""), Some(Vec::new()));
+    assert!(!byte_box.contains_empty_key());
+    assert_eq!(byte_box.remove(b""), None);
+}
+
+#[test]
+fn empty_values_survive_header_blocks_and_snapshots() {
+    let mut headers = ByteBox::new();
+    headers.insert(b"x-empty", b"");
+    headers.insert(b"", b"anonymous");
+
+    let block = headers.to_header_block(HeaderEncoding::Literal);
+    let decoded = ByteBox::from_header_block(&block).unwrap();
+    assert_eq!(decoded.get(b"x-empty"), Some(&b""[..]));
+    assert_eq!(decoded.get(b""), Some(&b"anonymous"[..]));
+
+    let mut file = Vec::new();
+    headers.write_snapshot(&mut file).unwrap();
+    let restored = ByteBox::read_snapshot(&file[..]).unwrap();
+    assert_eq!(restored.get(b"x-empty"), Some(&b""[..]));
+    assert!(restored.contains_empty_key());
+}
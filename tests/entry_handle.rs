@@ -0,0 +1,63 @@
+use bytesbox::error::StaleHandle;
+use bytesbox::ByteBox;
+
+#[test]
+fn entry_handle_survives_value_writes_only() {
+    let mut byte_box = ByteBox::new();
+    for i in 0..100u32 {
+        byte_box.insert(&i.to_le_bytes(), b"0");
+    }
+    let handles: Vec<_> = (0..100u32)
+        .map(|i| byte_box.get_handle(&i.to_le_bytes()).unwrap())
+        .collect();
+
+    for (i, handle) in handles.iter().enumerate() {
+        byte_box.write(*handle, &(i as u32).to_be_bytes()).unwrap();
+    }
+    for (i, handle) in handles.iter().enumerate() {
+        assert_eq!(byte_box.read(*handle), Ok(&(i as u32).to_be_bytes()[..]));
+        assert_eq!(
+            byte_box.get(&(i as u32).to_le_bytes()),
+            Some(&(i as u32).to_be_bytes()[..])
+        );
+    }
+
+    let fresh = byte_box.insert_handle(b"new", b"value");
+    assert_eq!(byte_box.read(fresh), Ok(&b"value"[..]));
+    assert!(matches!(byte_box.read(handles[0]), Err(StaleHandle { .. })));
+    assert!(byte_box.write(handles[1], b"lost").is_err());
+    assert_eq!(
+        byte_box.get(&1u32.to_le_bytes()),
+        Some(&1u32.to_be_bytes()[..])
+    );
+}
+
+#[test]
+fn entry_handle_after_extract_if_keeping_everything() {
+    let mut byte_box = ByteBox::prealloc(64);
+    let keys: Vec<Vec<u8>> = (0u32..)
+        .map(|i| format!("key{}", i).into_bytes())
+        .filter(|key| ByteBox::cell_index(key, 64) == ByteBox::cell_index(b"key0", 64))
+        .take(3)
+        .collect();
+    for key in &keys {
+        byte_box.insert(key, key);
+    }
+    let handle = byte_box.get_handle(&keys[0]).unwrap();
+
+    assert_eq!(byte_box.extract_if(|_, _| false).count(), 0);
+    assert_eq!(byte_box.read(handle), Ok(&keys[0][..]));
+    byte_box.write(handle, b"X").unwrap();
+    assert_eq!(byte_box.get(&keys[0]), Some(&b"X"[..]));
+    assert_eq!(byte_box.get(&keys[2]), Some(&keys[2][..]));
+}
+
+#[test]
+fn entry_handle_writes_release_the_old_value_buffer() {
+    let mut byte_box = ByteBox::new();
+    byte_box.insert(b"blob", &[0u8; 4096]);
+    let handle = byte_box.get_handle(b"blob").unwrap();
+    byte_box.write(handle, b"tiny").unwrap();
+    // The 4 KiB buffer is gone, so a 64-byte value no longer fits in place.
+    assert_eq!(byte_box.replace_in_place(b"blob", &[1u8; 64]), Ok(true));
+}
@@ -0,0 +1,18 @@
+use bytesbox::ByteBox;
+
+#[test]
+fn export_raw() {
+    let mut byte_box = ByteBox::prealloc(4);
+    byte_box.insert(b"content-type", b"text/html");
+    byte_box.insert(b"content-length", b"");
+    byte_box.insert(b"host", b"example.com");
+
+    let raw = byte_box.export_raw();
+    assert_eq!(raw.len(), 3);
+    assert_eq!(raw.offsets.len(), 7);
+    assert_eq!(raw.offsets.last(), Some(&(raw.bytes.len() as u64)));
+
+    for (key, value) in raw.iter() {
+        assert_eq!(byte_box.get(key), Some(value));
+    }
+}
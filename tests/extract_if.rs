@@ -0,0 +1,76 @@
+use bytesbox::ByteBox;
+
+#[test]
+fn extract_if() {
+    let mut byte_box = ByteBox::prealloc(1); // Small capacity to force collisions
+
+    for i in 0..10 {
+        byte_box.insert(format!("key{}", i).as_bytes(), &[i]);
+    }
+
+    let mut extracted: Vec<(Vec<u8>, Vec<u8>)> =
+        byte_box.extract_if(|_, value| value[0] % 2 == 0).collect();
+    extracted.sort();
+
+    assert_eq!(extracted.len(), 5);
+    assert_eq!(extracted[0], (b"key0".to_vec(), vec![0]));
+    assert_eq!(byte_box.len(), 5);
+    for i in 0..10u8 {
+        let value = byte_box.get(format!("key{}", i).as_bytes());
+        assert_eq!(value.is_some(), i % 2 == 1);
+    }
+}
+
+#[test]
+fn extract_if_dropped_early() {
+    let mut byte_box = ByteBox::prealloc(1);
+
+    for i in 0..10 {
+        byte_box.insert(format!("key{}", i).as_bytes(), b"value");
+    }
+
+    let first = byte_box.extract_if(|_, _| true).next();
+    assert!(first.is_some());
+    assert_eq!(byte_box.len(), 9);
+    assert_eq!(byte_box.iter().count(), 9);
+}
+
+#[test]
+fn extract_if_keeps_iteration_order() {
+    let mut byte_box = ByteBox::prealloc(4);
+    for i in 0..40u8 {
+        byte_box.insert(format!("key{}", i).as_bytes(), &[i]);
+    }
+    let keys = |byte_box: &ByteBox| -> Vec<Vec<u8>> {
+        byte_box.iter().map(|(key, _)| key.to_vec()).collect()
+    };
+    let before = keys(&byte_box);
+
+    assert_eq!(byte_box.extract_if(|_, _| false).count(), 0);
+    assert_eq!(keys(&byte_box), before);
+
+    // Stopping mid-chain keeps the rest of the chain in place too.
+    let (first, _) = byte_box
+        .extract_if(|_, value| value[0] % 3 == 0)
+        .next()
+        .unwrap();
+    let expected: Vec<Vec<u8>> = before
+        .iter()
+        .filter(|key| **key != first)
+        .cloned()
+        .collect();
+    assert_eq!(keys(&byte_box), expected);
+
+    byte_box
+        .extract_if(|_, value| value[0] % 3 == 0)
+        .for_each(drop);
+    let expected: Vec<Vec<u8>> = before
+        .iter()
+        .filter(|key| {
+            let i: u8 = std::str::from_utf8(&key[3..]).unwrap().parse().unwrap();
+            i % 3 != 0
+        })
+        .cloned()
+        .collect();
+    assert_eq!(keys(&byte_box), expected);
+}
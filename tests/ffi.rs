@@ -0,0 +1,33 @@
+#![cfg(feature = "ffi")]
+use bytesbox::ffi::*;
+use std::ptr;
+
+#[test]
+fn ffi_round_trip() {
+    unsafe {
+        let table = bytebox_new();
+        assert!(bytebox_insert(
+            table,
+            b"key".as_ptr(),
+            3,
+            b"value".as_ptr(),
+            5
+        ));
+        assert!(bytebox_insert(table, b"empty".as_ptr(), 5, ptr::null(), 0));
+        assert_eq!(bytebox_len(table), 2);
+
+        let mut len = 0;
+        let value = bytebox_get(table, b"key".as_ptr(), 3, &mut len);
+        assert_eq!(std::slice::from_raw_parts(value, len), b"value");
+        assert!(bytebox_get(table, b"missing".as_ptr(), 7, &mut len).is_null());
+
+        let raw = bytebox_export(table);
+        let mut offsets_len = 0;
+        bytebox_raw_offsets(raw, &mut offsets_len);
+        assert_eq!(offsets_len, 5);
+        bytebox_raw_free(raw);
+
+        assert!(bytebox_remove(table, b"key".as_ptr(), 3));
+        bytebox_free(table);
+    }
+}
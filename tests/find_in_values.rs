@@ -0,0 +1,58 @@
+use bytesbox::search::find_all;
+use bytesbox::ByteBox;
+
+#[test]
+fn find_in_values_reports_every_occurrence() {
+    let mut byte_box = ByteBox::new();
+    assert_eq!(byte_box.find_in_values(b"x").count(), 0);
+
+    byte_box.insert(b"a", b"xxTOKENxxTOKEN");
+    byte_box.insert(b"b", b"TOKEN");
+    byte_box.insert(b"c", b"TOKE");
+    byte_box.insert(b"d", b"");
+    byte_box.insert(b"e", b"TTTOKENN");
+
+    let mut hits: Vec<(Vec<u8>, usize)> = byte_box
+        .find_in_values(b"TOKEN")
+        .map(|(key, offset)| (key.to_vec(), offset))
+        .collect();
+    hits.sort();
+    assert_eq!(
+        hits,
+        vec![
+            (b"a".to_vec(), 2),
+            (b"a".to_vec(), 9),
+            (b"b".to_vec(), 0),
+            (b"e".to_vec(), 2),
+        ]
+    );
+    assert_eq!(byte_box.find_in_values(b"").count(), 0);
+    assert_eq!(byte_box.find_in_values(b"TOKENS").count(), 0);
+}
+
+#[test]
+fn find_all_matches_a_naive_scan_on_repetitive_input() {
+    let haystack: Vec<u8> = (0..2000)
+        .map(|i| if i % 97 == 96 { b'b' } else { b'a' })
+        .collect();
+    for needle in [
+        &b"a"[..],
+        b"aa",
+        b"aaab",
+        b"aabaa",
+        b"abaab",
+        b"ab",
+        b"b",
+        b"ba",
+    ] {
+        let naive: Vec<usize> = haystack
+            .windows(needle.len())
+            .enumerate()
+            .filter(|(_, window)| window == &needle)
+            .map(|(start, _)| start)
+            .collect();
+        assert_eq!(find_all(&haystack, needle).collect::<Vec<_>>(), naive);
+    }
+    assert_eq!(find_all(b"aaaaa", b"aa").collect::<Vec<_>>(), [0, 1, 2, 3]);
+    assert_eq!(find_all(b"aab", b"aaab").count(), 0);
+}
@@ -0,0 +1,24 @@
+use bytesbox::tree::ByteTree;
+
+#[test]
+fn floor_and_ceiling_find_the_nearest_keys() {
+    let mut tree = ByteTree::new();
+    assert_eq!(tree.floor(b"m"), None);
+    assert_eq!(tree.ceiling(b"m"), None);
+
+    for key in [&b"b"[..], b"d", b"dd", b"f"] {
+        tree.insert(key, key);
+    }
+    let key = |entry: Option<(&[u8], &[u8])>| entry.map(|(key, _)| key.to_vec());
+
+    assert_eq!(key(tree.floor(b"a")), None);
+    assert_eq!(key(tree.floor(b"b")), Some(b"b".to_vec()));
+    assert_eq!(key(tree.floor(b"d\0")), Some(b"d".to_vec()));
+    assert_eq!(key(tree.floor(b"e")), Some(b"dd".to_vec()));
+    assert_eq!(key(tree.floor(b"z")), Some(b"f".to_vec()));
+
+    assert_eq!(key(tree.ceiling(b"")), Some(b"b".to_vec()));
+    assert_eq!(key(tree.ceiling(b"d\0")), Some(b"dd".to_vec()));
+    assert_eq!(key(tree.ceiling(b"f")), Some(b"f".to_vec()));
+    assert_eq!(key(tree.ceiling(b"f\0")), None);
+}
@@ -0,0 +1,28 @@
+use bytesbox::ByteBox;
+
+#[test]
+fn for_each_key_allows_mutation() {
+    let mut byte_box = ByteBox::new();
+    for i in 0..20u8 {
+        byte_box.insert(&[i], &[i]);
+    }
+
+    let mut snapshot = byte_box.keys_owned_snapshot();
+    snapshot.sort();
+    assert_eq!(snapshot, (0..20u8).map(|i| vec![i]).collect::<Vec<_>>());
+
+    let mut visited = 0;
+    byte_box.for_each_key(|table, key| {
+        visited += 1;
+        if key[0] % 2 == 0 {
+            table.remove(key);
+        } else {
+            table.insert(&[key[0], 0xff], b"copy");
+        }
+    });
+
+    assert_eq!(visited, 20);
+    assert_eq!(byte_box.len(), 20);
+    assert_eq!(byte_box.get(&[4]), None);
+    assert_eq!(byte_box.get(&[5, 0xff]), Some(&b"copy"[..]));
+}
@@ -0,0 +1,29 @@
+use bytesbox::ByteBox;
+
+#[test]
+fn freeze_and_thaw_keep_the_entries() {
+    let empty = ByteBox::new().freeze();
+    assert!(empty.is_empty());
+    assert_eq!(empty.get(b""), None);
+
+    let mut byte_box = ByteBox::new();
+    byte_box.rehash_with_seed(7);
+    for i in 0..1_000u32 {
+        byte_box.insert(&i.to_le_bytes(), &i.to_be_bytes());
+    }
+    byte_box.insert(b"", b"empty key");
+    let expected = byte_box.clone();
+
+    let frozen = byte_box.freeze();
+    assert_eq!(frozen.len(), 1_001);
+    for (key, value) in expected.iter() {
+        assert_eq!(frozen.get(key), Some(value));
+    }
+    assert_eq!(frozen.get(&1_000u32.to_le_bytes()), None);
+    assert_eq!(frozen.iter().count(), 1_001);
+
+    let thawed = frozen.thaw();
+    assert_eq!(thawed.seed(), 7);
+    assert_eq!(thawed.allocation(), expected.allocation());
+    assert_eq!(thawed.content_hash(), expected.content_hash());
+}
@@ -0,0 +1,35 @@
+use bytesbox::ByteBox;
+
+#[test]
+fn read_decoder_strips_envelopes_and_reports_failures() {
+    let mut byte_box = ByteBox::new();
+    byte_box.insert(b"ok", b"[payload]");
+    byte_box.insert(b"bad", b"payload");
+
+    let mut buf = b"stale".to_vec();
+    assert_eq!(byte_box.get_decoded(b"ok", &mut buf).unwrap(), Some(9));
+    assert_eq!(buf, b"[payload]");
+
+    byte_box.set_read_decoder(|stored, out| {
+        let inner = stored
+            .strip_prefix(b"[")
+            .and_then(|rest| rest.strip_suffix(b"]"))
+            .ok_or("not enveloped")?;
+        out.extend_from_slice(inner);
+        Ok(())
+    });
+    let copy = byte_box.clone();
+    assert_eq!(copy.get_decoded(b"ok", &mut buf).unwrap(), Some(7));
+    assert_eq!(buf, b"payload");
+    assert_eq!(copy.get_decoded(b"missing", &mut buf).unwrap(), None);
+
+    let error = byte_box.get_decoded(b"bad", &mut buf).unwrap_err();
+    assert_eq!(error.key, b"bad");
+    assert_eq!(
+        error.to_string(),
+        "cannot decode value of key \"bad\": not enveloped"
+    );
+
+    byte_box.clear_read_decoder();
+    assert_eq!(byte_box.get_decoded(b"bad", &mut buf).unwrap(), Some(7));
+}
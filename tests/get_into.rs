@@ -0,0 +1,18 @@
+use bytesbox::ByteBox;
+
+#[test]
+fn get_into() {
+    let mut byte_box = ByteBox::new();
+    byte_box.insert(b"key", b"value");
+
+    let mut buf = [0u8; 3];
+    assert_eq!(byte_box.get_into(b"key", &mut buf), Some(5));
+    assert_eq!(&buf, b"val");
+    assert_eq!(byte_box.get_into(b"missing", &mut buf), None);
+
+    let mut vec = b"previous contents".to_vec();
+    let capacity = vec.capacity();
+    assert_eq!(byte_box.get_to_vec(b"key", &mut vec), Some(5));
+    assert_eq!(vec, b"value");
+    assert_eq!(vec.capacity(), capacity);
+}
@@ -0,0 +1,18 @@
+use bytesbox::ByteBox;
+
+#[test]
+fn get_many_matches_individual_gets() {
+    let mut byte_box = ByteBox::new();
+    assert_eq!(byte_box.get_many(&[b"a"]), vec![None]);
+
+    for i in 0..1_000u32 {
+        byte_box.insert(&i.to_le_bytes(), &i.to_be_bytes());
+    }
+    let owned: Vec<[u8; 4]> = (0..1_200u32).rev().map(u32::to_le_bytes).collect();
+    let mut keys: Vec<&[u8]> = owned.iter().map(|key| &key[..]).collect();
+    keys.push(&owned[300]);
+
+    let expected: Vec<_> = keys.iter().map(|key| byte_box.get(key)).collect();
+    assert_eq!(byte_box.get_many(&keys), expected);
+    assert!(byte_box.get_many(&[]).is_empty());
+}
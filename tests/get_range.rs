@@ -0,0 +1,33 @@
+use bytesbox::error::RangeError;
+use bytesbox::ByteBox;
+use std::ops::Bound;
+
+#[test]
+fn get_range() {
+    let mut byte_box = ByteBox::new();
+    let payload: Vec<u8> = (0..64).collect();
+    byte_box.insert(b"payload", &payload);
+
+    assert_eq!(byte_box.get_range(b"payload", ..16), Some(&payload[..16]));
+    assert_eq!(
+        byte_box.get_range(b"payload", 60..=63),
+        Some(&payload[60..])
+    );
+    assert_eq!(byte_box.get_range(b"payload", 64..), Some(&[][..]));
+    assert_eq!(
+        byte_box.get_range(b"payload", (Bound::Included(8), Bound::Excluded(4))),
+        None
+    );
+    assert_eq!(
+        byte_box.try_get_range(b"payload", 60..70),
+        Err(RangeError::OutOfBounds {
+            start: 60,
+            end: 70,
+            len: 64
+        })
+    );
+    assert_eq!(
+        byte_box.try_get_range(b"missing", ..),
+        Err(RangeError::MissingKey)
+    );
+}
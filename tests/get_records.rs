@@ -0,0 +1,23 @@
+use bytesbox::ByteBox;
+
+#[test]
+fn get_records_borrows_fixed_width_records() {
+    let mut byte_box = ByteBox::new();
+    let mut buffer = Vec::new();
+    for ts in 0..4u32 {
+        buffer.extend_from_slice(&ts.to_le_bytes());
+        buffer.extend_from_slice(&[ts as u8 * 10, 0]);
+    }
+    buffer.push(0xff);
+    byte_box.insert(b"buffer", &buffer);
+
+    let records = byte_box.get_records(b"buffer", 6).unwrap();
+    assert_eq!(records.len(), 4);
+    assert_eq!(records.remainder(), &[0xff]);
+    let newest = records.clone().next_back().unwrap();
+    assert_eq!(newest, &[3, 0, 0, 0, 30, 0]);
+    let levels: Vec<u8> = records.map(|record| record[4]).collect();
+    assert_eq!(levels, vec![0, 10, 20, 30]);
+
+    assert_eq!(byte_box.get_records(b"missing", 6).map(|r| r.len()), None);
+}
@@ -0,0 +1,27 @@
+use bytesbox::ByteBox;
+
+#[test]
+fn stored_hashes_resolve_across_resizes_but_not_reseeding() {
+    let mut byte_box = ByteBox::new();
+    let keys: Vec<Vec<u8>> = (0..500u32)
+        .map(|i| format!("key{}", i).into_bytes())
+        .collect();
+    let mut index = Vec::new();
+    for key in &keys {
+        byte_box.insert(key, key);
+        index.push(byte_box.hash_of(key));
+    }
+    for (key, &hash) in keys.iter().zip(&index) {
+        assert_eq!(byte_box.get_by_hash_and_key(hash, key), Some(&key[..]));
+    }
+    assert_eq!(byte_box.get_by_hash_and_key(index[0], b"missing"), None);
+
+    byte_box.rehash_with_seed(99);
+    assert_ne!(byte_box.hash_of(&keys[0]), index[0]);
+    let hash = byte_box.hash_of(&keys[0]);
+    assert_eq!(
+        byte_box.get_by_hash_and_key(hash, &keys[0]),
+        Some(&keys[0][..])
+    );
+    assert_eq!(ByteBox::new().get_by_hash_and_key(hash, &keys[0]), None);
+}
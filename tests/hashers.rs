@@ -0,0 +1,18 @@
+use bytesbox::hashers::HasherChoice;
+use bytesbox::ByteBox;
+
+#[test]
+fn evaluate_hashers() {
+    let keys: Vec<Vec<u8>> = (0..4096u32)
+        .map(|i| format!("session:{:08}", i).into_bytes())
+        .collect();
+
+    let results = ByteBox::evaluate_hashers(&keys);
+    assert_eq!(results.len(), HasherChoice::available().len());
+    for result in &results {
+        assert_eq!(result.keys, 4096);
+        assert_eq!(result.allocation, 8192);
+        assert!(result.longest_chain >= 1);
+        assert!(result.chi_squared_ratio < 2.0, "{}", result);
+    }
+}
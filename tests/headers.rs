@@ -0,0 +1,40 @@
+use bytesbox::error::HeaderBlockError;
+use bytesbox::headers::HeaderEncoding;
+use bytesbox::ByteBox;
+
+#[test]
+fn header_block_roundtrip() {
+    let mut headers = ByteBox::new();
+    headers.insert(b":method", b"GET");
+    headers.insert(b"user-agent", b"bytesbox");
+    headers.insert(b"x-request-id", b"42");
+    headers.insert(b"empty", b"");
+
+    let literal = headers.to_header_block(HeaderEncoding::Literal);
+    let compact = headers.to_header_block(HeaderEncoding::StaticTable);
+    assert!(compact.len() < literal.len());
+
+    for block in [literal, compact] {
+        let decoded = ByteBox::from_header_block(&block).unwrap();
+        assert_eq!(decoded.len(), 4);
+        for (name, value) in headers.iter() {
+            assert_eq!(decoded.get(name), Some(value));
+        }
+    }
+}
+
+#[test]
+fn header_block_errors() {
+    assert_eq!(
+        ByteBox::from_header_block(b"\x02\0\0\0\x05GE").unwrap_err(),
+        HeaderBlockError::Truncated { offset: 1 }
+    );
+    assert_eq!(
+        ByteBox::from_header_block(b"\x02\0\0\0\x03GET\xc8").unwrap_err(),
+        HeaderBlockError::UnknownIndex {
+            index: 200,
+            offset: 8
+        }
+    );
+    assert!(ByteBox::from_header_block(b"").unwrap().is_empty());
+}
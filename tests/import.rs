@@ -0,0 +1,76 @@
+use bytesbox::snapshot::{ImportReport, ImportStrategy};
+use bytesbox::ByteBox;
+
+fn snapshot() -> Vec<u8> {
+    let mut stale = ByteBox::new();
+    stale.insert(b"shared", b"stale");
+    stale.insert(b"only-snapshot", b"1");
+    stale.insert(b"bad", b"");
+    let mut file = Vec::new();
+    stale.write_snapshot(&mut file).unwrap();
+    file
+}
+
+fn live() -> ByteBox {
+    let mut live = ByteBox::new();
+    live.insert(b"shared", b"fresh");
+    live.insert(b"only-live", b"2");
+    live
+}
+
+#[test]
+fn import_strategies() {
+    let non_empty = |_: &[u8], value: &[u8]| !value.is_empty();
+
+    let mut table = live();
+    let report = table
+        .import(&snapshot()[..], ImportStrategy::Replace, non_empty)
+        .unwrap();
+    assert_eq!(
+        report,
+        ImportReport {
+            imported: 2,
+            kept_existing: 0,
+            rejected: 1
+        }
+    );
+    assert_eq!(table.len(), 2);
+    assert_eq!(table.get(b"shared"), Some(&b"stale"[..]));
+    assert_eq!(table.get(b"only-live"), None);
+
+    let mut table = live();
+    table
+        .import(
+            &snapshot()[..],
+            ImportStrategy::MergePreferExisting,
+            non_empty,
+        )
+        .unwrap();
+    assert_eq!(table.len(), 3);
+    assert_eq!(table.get(b"shared"), Some(&b"fresh"[..]));
+
+    let mut table = live();
+    table
+        .import(
+            &snapshot()[..],
+            ImportStrategy::MergePreferImported,
+            non_empty,
+        )
+        .unwrap();
+    assert_eq!(table.len(), 3);
+    assert_eq!(table.get(b"shared"), Some(&b"stale"[..]));
+    assert_eq!(table.get(b"bad"), None);
+}
+
+#[test]
+fn import_failure_leaves_table_untouched() {
+    let mut file = snapshot();
+    file.truncate(file.len() - 1);
+
+    let mut table = live();
+    assert!(table
+        .import(&file[..], ImportStrategy::Replace, |_, _| true)
+        .is_err());
+    assert_eq!(table.len(), 2);
+    assert_eq!(table.get(b"shared"), Some(&b"fresh"[..]));
+}
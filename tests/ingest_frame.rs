@@ -0,0 +1,34 @@
+use bytesbox::error::FrameError;
+use bytesbox::frame::write_frame;
+use bytesbox::ByteBox;
+
+#[test]
+fn ingest_frame_inserts_pairs_or_nothing() {
+    let mut byte_box = ByteBox::new();
+    assert_eq!(byte_box.ingest_frame(&write_frame(&[])), Ok(0));
+    assert_eq!(
+        byte_box.ingest_frame(b""),
+        Err(FrameError::Truncated { offset: 0 })
+    );
+
+    let frame = write_frame(&[(b"a", b"1"), (b"", b""), (b"a", b"2"), (b"b", b"22")]);
+    assert_eq!(byte_box.ingest_frame(&frame), Ok(4));
+    assert_eq!(byte_box.len(), 3);
+    assert_eq!(byte_box.get(b"a"), Some(&b"2"[..]));
+    assert_eq!(byte_box.get(b""), Some(&b""[..]));
+
+    let mut fresh = ByteBox::new();
+    assert_eq!(
+        fresh.ingest_frame(&frame[..frame.len() - 1]),
+        Err(FrameError::Truncated { offset: 37 })
+    );
+    let mut padded = frame.clone();
+    padded.push(0);
+    assert_eq!(
+        fresh.ingest_frame(&padded),
+        Err(FrameError::TrailingBytes {
+            offset: frame.len()
+        })
+    );
+    assert!(fresh.is_empty());
+}
@@ -0,0 +1,40 @@
+use bytesbox::ByteBox;
+
+#[test]
+fn insert_or_append_accumulates_values() {
+    let mut byte_box = ByteBox::new();
+    assert!(byte_box.insert_or_append(b"log", b"start", Some(b"; ")));
+    assert!(!byte_box.insert_or_append(b"log", b"step", Some(b"; ")));
+    assert!(!byte_box.insert_or_append(b"log", b"!", None));
+    assert_eq!(byte_box.get(b"log"), Some(&b"start; step!"[..]));
+
+    // The separator is written for an existing empty value, never for a new key.
+    byte_box.insert(b"empty", b"");
+    assert!(!byte_box.insert_or_append(b"empty", b"x", Some(b",")));
+    assert_eq!(byte_box.get(b"empty"), Some(&b",x"[..]));
+
+    let mut expected = ByteBox::new();
+    expected.insert(b"log", b"start; step!");
+    expected.insert(b"empty", b",x");
+    assert_eq!(byte_box.content_hash(), expected.content_hash());
+    assert_eq!(byte_box.content_bytes(), expected.content_bytes());
+}
+
+#[test]
+fn insert_or_append_keeps_content_hash_across_chunks() {
+    let mut byte_box = ByteBox::new();
+    let mut expected = Vec::new();
+    for i in 0..100u8 {
+        let bytes = vec![i; usize::from(i % 70)];
+        byte_box.insert_or_append(b"log", &bytes, Some(b"|"));
+        if i > 0 {
+            expected.push(b'|');
+        }
+        expected.extend_from_slice(&bytes);
+
+        let mut fresh = ByteBox::new();
+        fresh.insert(b"log", &expected);
+        assert_eq!(byte_box.content_hash(), fresh.content_hash());
+    }
+    assert_eq!(byte_box.get(b"log"), Some(&expected[..]));
+}
@@ -0,0 +1,45 @@
+use bytesbox::error::DuplicateKey;
+use bytesbox::policy::DuplicatePolicy;
+use bytesbox::ByteBox;
+
+#[test]
+fn insert_with_policy() {
+    let mut byte_box = ByteBox::new();
+    assert_eq!(
+        byte_box.insert_with_policy(b"key", b"a", DuplicatePolicy::Error),
+        Ok(true)
+    );
+    assert_eq!(
+        byte_box.insert_with_policy(b"key", b"b", DuplicatePolicy::Error),
+        Err(DuplicateKey {
+            key: b"key".to_vec()
+        })
+    );
+    assert_eq!(
+        byte_box.insert_with_policy(b"key", b"c", DuplicatePolicy::Keep),
+        Ok(false)
+    );
+    assert_eq!(byte_box.get(b"key"), Some(&b"a"[..]));
+
+    byte_box
+        .insert_with_policy(b"key", b"d", DuplicatePolicy::Append(b";"))
+        .unwrap();
+    assert_eq!(byte_box.get(b"key"), Some(&b"a;d"[..]));
+
+    byte_box
+        .insert_with_policy(b"key", b"e", DuplicatePolicy::Overwrite)
+        .unwrap();
+    assert_eq!(byte_box.get(b"key"), Some(&b"e"[..]));
+    assert_eq!(byte_box.len(), 1);
+}
+
+#[test]
+fn overwrite_policy_releases_the_old_value_buffer() {
+    let mut byte_box = ByteBox::new();
+    byte_box.insert(b"blob", &[0u8; 4096]);
+    byte_box
+        .insert_with_policy(b"blob", b"tiny", DuplicatePolicy::Overwrite)
+        .unwrap();
+    // The 4 KiB buffer is gone, so a 64-byte value no longer fits in place.
+    assert_eq!(byte_box.replace_in_place(b"blob", &[1u8; 64]), Ok(true));
+}
@@ -0,0 +1,41 @@
+use bytesbox::outcome::InsertOutcome;
+use bytesbox::ByteBox;
+
+#[test]
+fn insert_report_counts_writes_overwrites_and_resizes() {
+    let mut byte_box = ByteBox::new();
+    let (mut inserted, mut resized) = (0, 0);
+    for i in 0..1_000u32 {
+        match byte_box.insert_report(&i.to_le_bytes(), b"v") {
+            InsertOutcome::Inserted => inserted += 1,
+            InsertOutcome::Resized => resized += 1,
+            InsertOutcome::Updated { .. } => unreachable!(),
+        }
+    }
+    assert_eq!(inserted + resized, 1_000);
+    // 0 -> 16 cells, then doubling up to 2048.
+    assert_eq!(resized, 8);
+    assert_eq!(byte_box.allocation(), 2048);
+
+    let allocation = byte_box.allocation();
+    for i in 0..1_000u32 {
+        assert_eq!(
+            byte_box.insert_report(&i.to_le_bytes(), b"longer"),
+            InsertOutcome::Updated { old_len: 1 }
+        );
+    }
+    assert_eq!(byte_box.allocation(), allocation);
+    assert_eq!(byte_box.get(&5u32.to_le_bytes()), Some(&b"longer"[..]));
+}
+
+#[test]
+fn updates_release_the_old_value_buffer() {
+    let mut byte_box = ByteBox::new();
+    byte_box.insert(b"blob", &[0u8; 4096]);
+    assert_eq!(
+        byte_box.insert_report(b"blob", b"tiny"),
+        InsertOutcome::Updated { old_len: 4096 }
+    );
+    // The 4 KiB buffer is gone, so a 64-byte value no longer fits in place.
+    assert_eq!(byte_box.replace_in_place(b"blob", &[1u8; 64]), Ok(true));
+}
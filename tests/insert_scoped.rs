@@ -0,0 +1,23 @@
+use bytesbox::ByteBox;
+
+#[test]
+fn scoped_inserts_are_undone_on_drop() {
+    let mut byte_box = ByteBox::new();
+    byte_box.insert(b"existing", b"original");
+    let hash = byte_box.content_hash();
+
+    {
+        let mut guard = byte_box.insert_scoped(b"existing", b"override");
+        guard.insert(b"existing", b"changed again");
+        let mut nested = guard.insert_scoped(b"fresh", b"temporary");
+        assert_eq!(nested.previous(), None);
+        assert_eq!(nested.get(b"fresh"), Some(&b"temporary"[..]));
+        nested.insert(b"unrelated", b"kept");
+    }
+
+    assert_eq!(byte_box.get(b"existing"), Some(&b"original"[..]));
+    assert_eq!(byte_box.get(b"fresh"), None);
+    assert_eq!(byte_box.get(b"unrelated"), Some(&b"kept"[..]));
+    byte_box.remove(b"unrelated");
+    assert_eq!(byte_box.content_hash(), hash);
+}
@@ -0,0 +1,22 @@
+use bytesbox::ByteBox;
+
+#[test]
+fn iter_by_value_size() {
+    let mut byte_box = ByteBox::new();
+    for i in 0..50usize {
+        byte_box.insert(&i.to_le_bytes(), &vec![0u8; (i * 37) % 101]);
+    }
+
+    let descending: Vec<usize> = byte_box
+        .iter_by_value_size(true)
+        .map(|(_, value)| value.len())
+        .collect();
+    assert_eq!(descending.len(), 50);
+    assert!(descending.windows(2).all(|pair| pair[0] >= pair[1]));
+
+    let ascending: Vec<usize> = byte_box
+        .iter_by_value_size(false)
+        .map(|(_, value)| value.len())
+        .collect();
+    assert!(ascending.windows(2).all(|pair| pair[0] <= pair[1]));
+}
@@ -0,0 +1,25 @@
+use bytesbox::ByteBox;
+
+#[test]
+fn chunks_cover_every_entry_once() {
+    let mut byte_box = ByteBox::new();
+    assert_eq!(byte_box.iter_chunks(3).count(), 0);
+
+    for i in 0..100u32 {
+        byte_box.insert(&i.to_le_bytes(), b"v");
+    }
+    let chunks: Vec<_> = byte_box.iter_chunks(32).collect();
+    assert_eq!(
+        chunks.iter().map(Vec::len).collect::<Vec<_>>(),
+        vec![32, 32, 32, 4]
+    );
+    let flattened: Vec<_> = chunks.into_iter().flatten().collect();
+    assert_eq!(flattened, byte_box.iter().collect::<Vec<_>>());
+    assert_eq!(byte_box.iter_chunks(100).count(), 1);
+}
+
+#[test]
+#[should_panic(expected = "non-zero")]
+fn zero_sized_chunks_panic() {
+    ByteBox::new().iter_chunks(0);
+}
@@ -0,0 +1,46 @@
+use bytesbox::pattern::glob_match;
+use bytesbox::ByteBox;
+
+#[test]
+fn glob_patterns() {
+    let cases: &[(&[u8], &[u8], bool)] = &[
+        (b"", b"", true),
+        (b"", b"a", false),
+        (b"*", b"", true),
+        (b"*", b"anything", true),
+        (b"a*b*c", b"axxbyyc", true),
+        (b"a*b*c", b"axxbyy", false),
+        (b"*a", b"banana", true),
+        (b"*ana", b"banana", true),
+        (b"?", b"", false),
+        (b"??", b"\xff\x00", true),
+        (b"user:*:session", b"user::session", true),
+        (b"user:*:session", b"user:1:session:x", false),
+        (br"\?", b"?", true),
+        (br"\?", b"x", false),
+        (br"a\", b"a", false),
+        (b"***x", b"x", true),
+    ];
+    for &(pattern, key, expected) in cases {
+        assert_eq!(
+            glob_match(pattern, key),
+            expected,
+            "{:?} against {:?}",
+            String::from_utf8_lossy(pattern),
+            String::from_utf8_lossy(key)
+        );
+    }
+}
+
+#[test]
+fn iter_match_filters_keys() {
+    let mut byte_box = ByteBox::new();
+    for user in 0..20u8 {
+        byte_box.insert(format!("user:{}:session", user).as_bytes(), &[user]);
+        byte_box.insert(format!("user:{}:profile", user).as_bytes(), &[user]);
+    }
+    assert_eq!(byte_box.iter_match(b"user:*:session").count(), 20);
+    assert_eq!(byte_box.iter_match(b"user:?:*").count(), 20);
+    assert_eq!(byte_box.iter_match(b"user:1?:profile").count(), 10);
+    assert_eq!(byte_box.iter_match(b"admin:*").count(), 0);
+}
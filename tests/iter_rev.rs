@@ -0,0 +1,36 @@
+use bytesbox::ByteBox;
+
+#[test]
+fn stable_iter_walks_from_both_ends() {
+    let mut byte_box = ByteBox::prealloc(4);
+    for i in 0..20 {
+        byte_box.insert(format!("key{}", i).as_bytes(), b"value");
+    }
+
+    let forward: Vec<_> = byte_box.iter_stable().collect();
+    let mut backward: Vec<_> = byte_box.iter_stable().rev().collect();
+    backward.reverse();
+    assert_eq!(forward, backward);
+
+    // Alternating ends meet in the middle, even inside a single chain.
+    let mut iter = byte_box.iter_stable();
+    let mut front = Vec::new();
+    let mut back = Vec::new();
+    while let Some(item) = iter.next() {
+        front.push(item);
+        match iter.next_back() {
+            Some(item) => back.push(item),
+            None => break,
+        }
+    }
+    back.reverse();
+    front.extend(back);
+    assert_eq!(front, forward);
+
+    // A clone resumes from the same position.
+    let mut iter = byte_box.iter();
+    iter.next();
+    let rest: Vec<_> = iter.clone().collect();
+    assert_eq!(rest, iter.collect::<Vec<_>>());
+    assert_eq!(rest.len(), 19);
+}
@@ -0,0 +1,22 @@
+use bytesbox::ByteBox;
+use std::collections::HashSet;
+
+#[test]
+fn iteration_semantics() {
+    let mut byte_box = ByteBox::prealloc(4);
+    for i in 0..50 {
+        byte_box.insert(format!("key{}", i).as_bytes(), b"value");
+    }
+
+    // Each entry exactly once.
+    let keys: Vec<&[u8]> = byte_box.iter().map(|(key, _)| key).collect();
+    let unique: HashSet<&[u8]> = keys.iter().copied().collect();
+    assert_eq!(keys.len(), 50);
+    assert_eq!(unique.len(), 50);
+
+    // Stable while unchanged, and consistent between both iterators.
+    let first: Vec<_> = byte_box.iter_stable().collect();
+    let second: Vec<_> = byte_box.iter_stable().collect();
+    assert_eq!(first, second);
+    assert_eq!(first, byte_box.iter().collect::<Vec<_>>());
+}
@@ -0,0 +1,35 @@
+use bytesbox::lazy::LazyByteBox;
+use std::cell::Cell;
+use std::rc::Rc;
+
+#[test]
+fn lazy_values_render_once_and_only_when_read() {
+    let calls = Rc::new(Cell::new(0));
+    let mut table = LazyByteBox::new();
+    for name in [&b"a"[..], b"b", b"c"] {
+        let calls = Rc::clone(&calls);
+        let value = name.repeat(3);
+        assert!(table.insert_lazy(name, move || {
+            calls.set(calls.get() + 1);
+            value
+        }));
+    }
+    assert_eq!(table.len(), 3);
+
+    assert_eq!(table.get(b"b"), Some(&b"bbb"[..]));
+    assert_eq!(table.get(b"b"), Some(&b"bbb"[..]));
+    assert_eq!(calls.get(), 1);
+    assert!(table.is_materialized(b"b"));
+    assert!(!table.is_materialized(b"a"));
+
+    assert!(table.remove(b"a"));
+    assert!(!table.insert(b"c", b"ready"));
+    assert_eq!(table.get(b"c"), Some(&b"ready"[..]));
+    assert_eq!(calls.get(), 1);
+    assert_eq!(table.get(b"missing"), None);
+
+    assert!(!table.insert_lazy(b"b", || b"again".to_vec()));
+    let byte_box = table.into_byte_box();
+    assert_eq!(byte_box.len(), 2);
+    assert_eq!(byte_box.get(b"b"), Some(&b"again"[..]));
+}
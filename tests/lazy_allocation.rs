@@ -0,0 +1,21 @@
+use bytesbox::ByteBox;
+
+#[test]
+fn zero_capacity_tables_allocate_on_first_insert() {
+    for mut byte_box in [ByteBox::new(), ByteBox::prealloc(0), ByteBox::default()] {
+        assert_eq!(byte_box.allocation(), 0);
+        assert_eq!(byte_box.get(b"key"), None);
+        assert_eq!(byte_box.remove(b"key"), None);
+        assert!(byte_box.replace_in_place(b"key", b"v").is_err());
+        assert_eq!(byte_box.iter().count(), 0);
+        assert_eq!(byte_box.stats().len, 0);
+        byte_box.rehash_with_seed(3);
+        byte_box.clear();
+        assert_eq!(byte_box.to_string(), "{}");
+        assert_eq!(byte_box.allocation(), 0);
+
+        assert!(byte_box.insert(b"key", b"value"));
+        assert_eq!(byte_box.allocation(), 16);
+        assert_eq!(byte_box.get(b"key"), Some(&b"value"[..]));
+    }
+}
@@ -0,0 +1,60 @@
+#![cfg(loom)]
+
+//! Model tests of `ThreadLocalByteBox`, exploring every interleaving with loom.
+//!
+//! Run with `RUSTFLAGS="--cfg loom" cargo test --release --test loom`.
+
+use bytesbox::sharded::ThreadLocalByteBox;
+use loom::sync::Arc;
+use loom::thread;
+
+#[test]
+fn writes_are_visible_to_merges_after_join() {
+    loom::model(|| {
+        let shards = Arc::new(ThreadLocalByteBox::new());
+        let writers: Vec<_> = [b"a", b"b"]
+            .into_iter()
+            .map(|key| {
+                let shards = Arc::clone(&shards);
+                thread::spawn(move || {
+                    shards.insert(key, b"1");
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+
+        assert_eq!(shards.shard_count(), 2);
+        let merged = shards.merge_all();
+        assert_eq!(merged.get(b"a"), Some(&b"1"[..]));
+        assert_eq!(merged.get(b"b"), Some(&b"1"[..]));
+    });
+}
+
+#[test]
+fn concurrent_merges_see_a_prefix_of_each_shard() {
+    loom::model(|| {
+        let shards = Arc::new(ThreadLocalByteBox::new());
+        let writer = {
+            let shards = Arc::clone(&shards);
+            thread::spawn(move || {
+                shards.insert(b"first", b"1");
+                shards.insert(b"second", b"2");
+                shards.try_insert(b"third", b"3")
+            })
+        };
+
+        let merged = shards.merge_all();
+        if merged.contains_key(b"second") {
+            assert!(merged.contains_key(b"first"));
+        }
+        if merged.contains_key(b"third") {
+            assert!(merged.contains_key(b"second"));
+        }
+
+        let third = writer.join().unwrap();
+        let merged = shards.merge_all();
+        assert_eq!(merged.len(), 2 + usize::from(third.is_some()));
+    });
+}
@@ -0,0 +1,95 @@
+use bytesbox::ByteBox;
+
+#[test]
+fn maintain_shrinks_within_budget() {
+    let mut byte_box = ByteBox::prealloc(1024);
+    for i in 0..10 {
+        byte_box.insert(format!("key{}", i).as_bytes(), b"value");
+    }
+
+    let report = byte_box.maintain(64);
+    assert!(report.deferred);
+    assert_eq!(byte_box.allocation(), 1024);
+
+    let report = byte_box.maintain(2048);
+    assert_eq!(report.shrunk_to, Some(16));
+    assert_eq!(byte_box.allocation(), 16);
+    for i in 0..10 {
+        assert!(byte_box.get(format!("key{}", i).as_bytes()).is_some());
+    }
+
+    assert_eq!(byte_box.maintain(2048), Default::default());
+}
+
+#[test]
+fn maintain_resumes_a_shrink_across_calls() {
+    let mut byte_box = ByteBox::prealloc(4096);
+    let mut model = std::collections::HashMap::new();
+    for i in 0..40u32 {
+        byte_box.insert(&i.to_le_bytes(), &[i as u8; 3]);
+        model.insert(i.to_le_bytes().to_vec(), vec![i as u8; 3]);
+    }
+
+    let mut calls = 0;
+    loop {
+        let report = byte_box.maintain(100);
+        calls += 1;
+        // The table stays usable between steps, including for keys in folded cells.
+        let key = (1_000 + calls as u32).to_le_bytes();
+        byte_box.insert(&key, b"new");
+        model.insert(key.to_vec(), b"new".to_vec());
+        let gone = (calls as u32 % 40).to_le_bytes();
+        assert_eq!(byte_box.remove(&gone), model.remove(&gone[..]));
+        for (key, value) in &model {
+            assert_eq!(byte_box.get(key), Some(value.as_slice()));
+        }
+        assert_eq!(byte_box.len(), model.len());
+
+        if let Some(allocation) = report.shrunk_to {
+            assert!(!report.deferred);
+            assert_eq!(allocation, 64);
+            break;
+        }
+        assert!(report.deferred);
+        assert_eq!(byte_box.allocation(), 4096);
+    }
+    assert!(calls > 10);
+    assert_eq!(byte_box.allocation(), 64);
+}
+
+#[test]
+fn maintain_purges_expired_tombstones_and_rehashes_odd_allocations() {
+    let mut byte_box = ByteBox::prealloc(1001);
+    for i in 0..10u8 {
+        byte_box.insert(&[i], b"value");
+    }
+    byte_box.set_tombstone_retention(Some(std::time::Duration::ZERO));
+    byte_box.soft_remove(&[0]);
+    byte_box.soft_remove(&[1]);
+
+    let report = byte_box.maintain(64);
+    assert_eq!(report.purged_tombstones, 1);
+    assert!(report.deferred);
+    assert_eq!(byte_box.allocation(), 1001);
+
+    let report = byte_box.maintain(2048);
+    assert_eq!((report.shrunk_to, report.purged_tombstones), (Some(16), 0));
+    assert_eq!(byte_box.len(), 8);
+}
+
+#[test]
+fn maintain_until_progresses_past_its_deadline() {
+    let mut byte_box = ByteBox::prealloc(1024);
+    byte_box.insert(b"key", b"value");
+
+    let mut calls = 0;
+    while byte_box
+        .maintain_until(4096, std::time::Instant::now())
+        .deferred
+    {
+        calls += 1;
+        assert!(calls < 64);
+    }
+    assert_eq!(byte_box.allocation(), 16);
+    assert_eq!(byte_box.get(b"key"), Some(&b"value"[..]));
+}
@@ -0,0 +1,42 @@
+use bytesbox::ByteBox;
+
+#[test]
+fn migrate_into_moves_entries_in_bounded_steps() {
+    let mut source = ByteBox::prealloc(8);
+    for i in 0..100 {
+        source.insert(
+            format!("key{}", i).as_bytes(),
+            format!("value{}", i).as_bytes(),
+        );
+    }
+    let expected = source.content_hash();
+
+    let mut target = ByteBox::new();
+    target.rehash_with_seed(42);
+    target.insert(b"key0", b"stale");
+
+    let mut steps = 0;
+    loop {
+        let progress = source.migrate_into(&mut target, 7);
+        assert!(progress.moved <= 7);
+        assert_eq!(progress.remaining, source.len());
+        steps += 1;
+        if progress.is_complete() {
+            break;
+        }
+        // Keys written to the source mid-migration are picked up by later calls.
+        if steps == 3 {
+            source.insert(b"late", b"arrival");
+        }
+    }
+
+    assert_eq!(steps, 15);
+    assert!(source.is_empty());
+    assert_eq!(source.content_hash(), ByteBox::new().content_hash());
+    assert_eq!(target.len(), 101);
+    assert_eq!(target.get(b"key0"), Some(&b"value0"[..]));
+    assert_eq!(target.get(b"late"), Some(&b"arrival"[..]));
+    target.remove(b"late");
+    assert_eq!(target.content_hash(), expected);
+    assert_eq!(source.migrate_into(&mut target, 7).moved, 0);
+}
@@ -0,0 +1,80 @@
+use bytesbox::ByteBox;
+use proptest::prelude::*;
+use std::collections::HashMap;
+
+/// An operation applied both to a `ByteBox` and to the `HashMap` model.
+#[derive(Debug, Clone)]
+enum Op {
+    Insert(Vec<u8>, Vec<u8>),
+    Remove(Vec<u8>),
+    Get(Vec<u8>),
+    ExtractEven,
+    Clear,
+}
+
+/// Keys come from a small alphabet so updates, removals of present keys and
+/// collisions in small tables happen often.
+fn key() -> impl Strategy<Value = Vec<u8>> {
+    prop::collection::vec(0u8..4, 0..3)
+}
+
+fn op() -> impl Strategy<Value = Op> {
+    prop_oneof![
+        6 => (key(), prop::collection::vec(any::<u8>(), 0..4)).prop_map(|(k, v)| Op::Insert(k, v)),
+        3 => key().prop_map(Op::Remove),
+        3 => key().prop_map(Op::Get),
+        1 => Just(Op::ExtractEven),
+        1 => Just(Op::Clear),
+    ]
+}
+
+fn is_even(value: &[u8]) -> bool {
+    value.first().map_or(true, |byte| byte % 2 == 0)
+}
+
+proptest! {
+    #[test]
+    fn matches_hashmap_model(capacity in 1usize..8, ops in prop::collection::vec(op(), 0..200)) {
+        let mut byte_box = ByteBox::prealloc(capacity);
+        let mut model: HashMap<Vec<u8>, Vec<u8>> = HashMap::new();
+
+        for op in ops {
+            match op {
+                Op::Insert(k, v) => {
+                    let inserted = byte_box.insert(&k, &v);
+                    prop_assert_eq!(inserted, model.insert(k, v).is_none());
+                }
+                Op::Remove(k) => {
+                    prop_assert_eq!(byte_box.remove(&k), model.remove(&k));
+                }
+                Op::Get(k) => {
+                    prop_assert_eq!(byte_box.get(&k), model.get(&k).map(Vec::as_slice));
+                }
+                Op::ExtractEven => {
+                    let mut extracted: Vec<_> = byte_box.extract_if(|_, v| is_even(v)).collect();
+                    let mut expected: Vec<_> = model
+                        .iter()
+                        .filter(|(_, v)| is_even(v))
+                        .map(|(k, v)| (k.clone(), v.clone()))
+                        .collect();
+                    model.retain(|_, v| !is_even(v));
+                    extracted.sort();
+                    expected.sort();
+                    prop_assert_eq!(extracted, expected);
+                }
+                Op::Clear => {
+                    byte_box.clear();
+                    model.clear();
+                }
+            }
+
+            prop_assert_eq!(byte_box.len(), model.len());
+        }
+
+        let mut entries: Vec<_> = byte_box.iter().map(|(k, v)| (k.to_vec(), v.to_vec())).collect();
+        let mut expected: Vec<_> = model.into_iter().collect();
+        entries.sort();
+        expected.sort();
+        prop_assert_eq!(entries, expected);
+    }
+}
@@ -0,0 +1,34 @@
+use bytesbox::ByteBox;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+#[test]
+fn miss_handler_sees_only_failed_reads() {
+    let misses = Arc::new(AtomicUsize::new(0));
+    let mut byte_box = ByteBox::new();
+    let counter = Arc::clone(&misses);
+    byte_box.on_miss(move |key| {
+        assert_eq!(key, b"absent");
+        counter.fetch_add(1, Ordering::Relaxed);
+    });
+
+    // Misses on a table that has not allocated yet are reported too.
+    assert_eq!(byte_box.get(b"absent"), None);
+    byte_box.insert(b"present", b"value");
+    assert!(byte_box.get(b"present").is_some());
+    assert!(byte_box.get_range(b"absent", ..).is_none());
+    assert!(!byte_box.contains_key(b"absent"));
+    assert!(!byte_box.view().contains_key(b"absent"));
+    assert!(!byte_box.test_bit(b"absent", 3));
+    assert_eq!(misses.load(Ordering::Relaxed), 2);
+
+    assert_eq!(
+        byte_box.get_many(&[b"absent", b"present", b"absent"]),
+        vec![None, Some(&b"value"[..]), None]
+    );
+    assert_eq!(misses.load(Ordering::Relaxed), 4);
+
+    byte_box.clear_miss_handler();
+    byte_box.get(b"absent");
+    assert_eq!(misses.load(Ordering::Relaxed), 4);
+}
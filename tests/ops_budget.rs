@@ -0,0 +1,38 @@
+#![cfg(feature = "ops-budget")]
+
+use bytesbox::ByteBox;
+
+const ENTRIES: u32 = 10_000;
+
+#[test]
+fn operations_stay_within_their_budgets() {
+    let mut byte_box = ByteBox::new();
+    for i in 0..ENTRIES {
+        byte_box.insert(format!("key:{}", i).as_bytes(), &i.to_le_bytes());
+    }
+    let inserted = byte_box.op_counts();
+    assert_eq!(inserted.bytes_copied, byte_box.content_bytes() as u64);
+    // Doubling keeps growth amortized: each entry is moved fewer than two times.
+    assert!(inserted.entries_rehashed < 2 * ENTRIES as u64);
+    assert_eq!(inserted.lookups, 0);
+
+    byte_box.reset_op_counts();
+    for i in 0..ENTRIES {
+        byte_box.get(format!("key:{}", i).as_bytes());
+    }
+    let hits = byte_box.op_counts();
+    assert_eq!(hits.lookups, ENTRIES as u64);
+    // A hit compares 1 + load factor / 2 entries on average.
+    assert!(hits.probes_per_lookup() <= 1.5, "{:?}", hits);
+
+    byte_box.reset_op_counts();
+    for i in ENTRIES..2 * ENTRIES {
+        byte_box.get(format!("key:{}", i).as_bytes());
+    }
+    // A miss compares every entry of one chain, the load factor on average.
+    assert!(byte_box.op_counts().probes_per_lookup() <= 0.75);
+
+    byte_box.reset_op_counts();
+    byte_box.insert(b"key:0", b"updated");
+    assert_eq!(byte_box.op_counts().bytes_copied, 7);
+}
@@ -0,0 +1,32 @@
+use bytesbox::error::PackedValueError;
+use bytesbox::packed::{pack_values, unpack_values};
+use bytesbox::ByteBox;
+
+#[test]
+fn packed_values_round_trip() {
+    let long = vec![7u8; 300];
+    let fields: Vec<&[u8]> = vec![b"id", b"", &long, b"\x80\xff"];
+    let packed = pack_values(&fields);
+    // 300 needs a two-byte prefix: 0xac 0x02.
+    assert_eq!(&packed[4..6], &[0xac, 0x02]);
+    assert_eq!(unpack_values(&packed).unwrap(), fields);
+    assert_eq!(unpack_values(b"").unwrap(), Vec::<&[u8]>::new());
+
+    let mut byte_box = ByteBox::new();
+    assert!(byte_box.insert_packed(b"row", &fields));
+    assert_eq!(byte_box.get_unpacked(b"row").unwrap(), Some(fields));
+
+    byte_box.insert(b"bad", b"\x02id\x05abc");
+    assert_eq!(
+        byte_box.get_unpacked(b"bad"),
+        Err(PackedValueError::Truncated { offset: 3 })
+    );
+    assert_eq!(
+        unpack_values(b"\x02id\x80"),
+        Err(PackedValueError::Truncated { offset: 3 })
+    );
+    assert_eq!(
+        unpack_values(&[0xff; 10]),
+        Err(PackedValueError::LengthOverflow { offset: 0 })
+    );
+}
@@ -0,0 +1,25 @@
+#![cfg(feature = "rayon")]
+
+use bytesbox::ByteBox;
+use rayon::prelude::*;
+
+#[test]
+fn par_buckets_move_every_entry_once() {
+    let mut byte_box = ByteBox::prealloc(64);
+    for i in 0..10_000u32 {
+        byte_box.insert(&i.to_le_bytes(), &i.to_be_bytes());
+    }
+    let allocation = byte_box.allocation();
+    let expected = byte_box.content_hash();
+
+    let buckets: Vec<_> = byte_box.into_par_buckets().collect();
+    assert!(buckets.iter().all(|bucket| !bucket.is_empty()));
+    assert_eq!(buckets.iter().map(Vec::len).sum::<usize>(), 10_000);
+
+    let mut cells = vec![Vec::new(); allocation];
+    for bucket in buckets {
+        let cell = ByteBox::cell_index(&bucket[0].0, allocation);
+        cells[cell] = bucket;
+    }
+    assert_eq!(ByteBox::from_raw_parts(cells).content_hash(), expected);
+}
@@ -0,0 +1,59 @@
+use bytesbox::ByteBox;
+
+#[test]
+fn pool_reuses_entries() {
+    let mut byte_box = ByteBox::new();
+    for round in 0..10u8 {
+        for i in 0..8u8 {
+            byte_box.insert(&[b'k', i], &[round; 16]);
+        }
+        for i in 0..8u8 {
+            assert_eq!(byte_box.remove(&[b'k', i]), Some(vec![round; 16]));
+        }
+    }
+
+    let stats = byte_box.pool_stats();
+    assert_eq!(stats.recycled, 80);
+    assert_eq!(stats.reused, 72);
+    assert_eq!(stats.pooled, 8);
+    assert!(byte_box.is_empty());
+
+    byte_box.insert(b"k", b"v");
+    assert_eq!(byte_box.get(b"k"), Some(&b"v"[..]));
+}
+
+#[test]
+fn pool_limit_and_trim() {
+    let mut byte_box = ByteBox::new();
+    byte_box.set_pool_limit(2);
+    for i in 0..5u8 {
+        byte_box.insert(&[i], b"value");
+    }
+    byte_box.clear();
+    assert_eq!(byte_box.pool_stats().pooled, 2);
+    assert!(byte_box.pool_stats().pooled_bytes >= 2);
+
+    byte_box.trim_pool();
+    assert_eq!(byte_box.pool_stats().pooled, 0);
+    assert_eq!(byte_box.pool_stats().pooled_bytes, 0);
+}
+
+#[test]
+fn pool_frees_large_buffers_and_honours_the_byte_limit() {
+    let mut byte_box = ByteBox::new();
+    byte_box.insert(b"big", &vec![0u8; 1 << 20]);
+    byte_box.insert(b"small", b"value");
+    byte_box.clear();
+
+    let stats = byte_box.pool_stats();
+    assert_eq!(stats.pooled, 2);
+    assert!(stats.pooled_bytes < 1024);
+
+    byte_box.set_pool_byte_limit(0);
+    assert_eq!(byte_box.pool_stats().pooled_bytes, 0);
+    for i in 0..4u8 {
+        byte_box.insert(&[i], b"value");
+    }
+    byte_box.clear();
+    assert_eq!(byte_box.pool_stats().pooled_bytes, 0);
+}
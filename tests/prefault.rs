@@ -0,0 +1,18 @@
+use bytesbox::ByteBox;
+
+#[test]
+fn prefault_leaves_contents_unchanged() {
+    let mut byte_box = ByteBox::new();
+    assert_eq!(byte_box.prefault(), 0);
+
+    for i in 0..1_000u32 {
+        byte_box.insert(&i.to_le_bytes(), &vec![i as u8; i as usize * 10]);
+    }
+    let expected = byte_box.content_hash();
+    let handle = byte_box.get_handle(&999u32.to_le_bytes()).unwrap();
+
+    assert!(byte_box.prefault() >= byte_box.content_bytes());
+    assert_eq!(byte_box.content_hash(), expected);
+    assert_eq!(byte_box.len(), 1_000);
+    assert_eq!(byte_box.read(handle), Ok(&[231u8; 9_990][..]));
+}
@@ -0,0 +1,82 @@
+use bytesbox::prefix::PrefixByteBox;
+use std::collections::HashMap;
+
+#[test]
+fn prefix_compression() {
+    let mut table = PrefixByteBox::prealloc(2);
+    let mut model = HashMap::new();
+
+    for i in 0..200u32 {
+        let key = format!("https://example.com/static/assets/{}/file-{}.js", i % 7, i);
+        table.insert(key.as_bytes(), &i.to_le_bytes());
+        model.insert(key.into_bytes(), i.to_le_bytes().to_vec());
+    }
+    assert_eq!(table.len(), 200);
+
+    for (key, value) in &model {
+        assert_eq!(table.get(key), Some(value.as_slice()));
+    }
+    assert_eq!(table.get(b"https://example.com/static/assets/"), None);
+    assert_eq!(
+        table.get(b"https://example.com/static/assets/9/file-1.js"),
+        None
+    );
+
+    let stats = table.stats();
+    assert_eq!(stats.len, 200);
+    assert!(stats.key_savings() > stats.key_bytes / 3);
+
+    assert_eq!(
+        table.remove(b"https://example.com/static/assets/3/file-3.js"),
+        Some(3u32.to_le_bytes().to_vec())
+    );
+    assert_eq!(
+        table.get(b"https://example.com/static/assets/3/file-3.js"),
+        None
+    );
+    assert_eq!(table.iter().count(), 199);
+    for (key, value) in table.iter() {
+        assert_eq!(model.get(&key).map(Vec::as_slice), Some(value));
+    }
+}
+
+#[test]
+fn prefix_edits_keep_the_encoding_canonical() {
+    let mut table = PrefixByteBox::prealloc(1);
+    let mut model = HashMap::new();
+    let mut state = 0x2545_f491_4f6c_dd1du64;
+    for step in 0..4_000u32 {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        // Short keys over a tiny alphabet, so keys are often prefixes of each other.
+        let len = (state % 5) as usize;
+        let key: Vec<u8> = (0..len)
+            .map(|i| b'a' + (state >> (8 + 2 * i) & 3) as u8)
+            .collect();
+        if state >> 40 & 3 == 0 {
+            assert_eq!(table.remove(&key), model.remove(&key));
+        } else {
+            let value = step.to_le_bytes().to_vec();
+            assert_eq!(
+                table.insert(&key, &value),
+                model.insert(key, value).is_none()
+            );
+        }
+    }
+
+    assert_eq!(table.len(), model.len());
+    for (key, value) in &model {
+        assert_eq!(table.get(key), Some(value.as_slice()));
+    }
+    let mut rebuilt = PrefixByteBox::prealloc(table.allocation());
+    for (key, value) in table.iter() {
+        assert_eq!(model.get(&key).map(Vec::as_slice), Some(value));
+        rebuilt.insert(&key, value);
+    }
+    assert_eq!(rebuilt.allocation(), table.allocation());
+    assert_eq!(
+        rebuilt.stats().stored_key_bytes,
+        table.stats().stored_key_bytes
+    );
+}
@@ -0,0 +1,37 @@
+#![cfg(feature = "profiling")]
+
+use bytesbox::ByteBox;
+
+#[test]
+fn latency_histograms_split_hits_and_misses() {
+    let mut byte_box = ByteBox::new();
+    for i in 0..100u32 {
+        byte_box.insert(&i.to_le_bytes(), b"v");
+    }
+    byte_box.insert(&0u32.to_le_bytes(), b"w");
+    for i in 0..150u32 {
+        byte_box.get(&i.to_le_bytes());
+    }
+    byte_box.remove(&1u32.to_le_bytes());
+    byte_box.remove(b"missing");
+
+    let stats = byte_box.op_latency_stats();
+    assert_eq!(stats.insert_new.count, 100);
+    assert_eq!(stats.insert_update.count, 1);
+    assert_eq!(stats.get_hit.count, 100);
+    assert_eq!(stats.get_miss.count, 50);
+    assert_eq!((stats.remove_hit.count, stats.remove_miss.count), (1, 1));
+    assert_eq!(stats.get_hit.buckets().iter().sum::<u64>(), 100);
+    assert!(stats.get_hit.percentile(50.0) <= stats.get_hit.max);
+    assert!(stats.get_hit.mean() <= stats.get_hit.max);
+
+    byte_box.get_many(&[&2u32.to_le_bytes(), b"missing"]);
+    byte_box.contains_key(b"missing");
+    let stats = byte_box.op_latency_stats();
+    assert_eq!((stats.get_hit.count, stats.get_miss.count), (101, 51));
+
+    let copy = byte_box.clone();
+    assert_eq!(copy.op_latency_stats(), stats);
+    byte_box.reset_latency_stats();
+    assert_eq!(byte_box.op_latency_stats().get_hit.count, 0);
+}
@@ -0,0 +1,34 @@
+use bytesbox::ByteBox;
+
+#[test]
+fn from_raw_parts() {
+    let allocation = 32;
+    let mut cells = vec![Vec::new(); allocation];
+    for i in 0..20 {
+        let key = format!("key{}", i).into_bytes();
+        cells[ByteBox::cell_index(&key, allocation)].push((key, vec![i]));
+    }
+
+    let mut byte_box = ByteBox::from_raw_parts(cells);
+    assert_eq!(byte_box.len(), 20);
+    assert_eq!(byte_box.allocation(), allocation);
+    for i in 0..20 {
+        assert_eq!(byte_box.get(format!("key{}", i).as_bytes()), Some(&[i][..]));
+    }
+
+    assert!(!byte_box.insert(b"key3", b"updated"));
+    assert_eq!(byte_box.remove(b"key4"), Some(vec![4]));
+    assert_eq!(byte_box.len(), 19);
+}
+
+#[test]
+fn from_hashed_parts() {
+    let entries = (0..20u8).map(|i| {
+        let key = format!("key{}", i).into_bytes();
+        (ByteBox::cell_index(&key, 8), key, vec![i])
+    });
+
+    let byte_box = ByteBox::from_hashed_parts(8, entries);
+    assert_eq!(byte_box.len(), 20);
+    assert_eq!(byte_box.get(b"key19"), Some(&[19u8][..]));
+}
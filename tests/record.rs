@@ -0,0 +1,45 @@
+#![cfg(feature = "derive")]
+use bytesbox::error::RecordSizeMismatch;
+use bytesbox::record::ByteRecord;
+use bytesbox::ByteBox;
+
+#[derive(ByteRecord, Debug, Clone, Copy, PartialEq)]
+struct Point(i32, i32);
+
+#[derive(ByteRecord, Debug, PartialEq)]
+struct Span {
+    id: u64,
+    sampled: bool,
+    origin: Point,
+    duration: f64,
+    trace: [u8; 16],
+}
+
+#[test]
+fn derived_records_round_trip() {
+    assert_eq!(Point::SIZE, 8);
+    assert_eq!(Span::SIZE, 8 + 1 + 8 + 8 + 16);
+
+    let span = Span {
+        id: 7,
+        sampled: true,
+        origin: Point(-3, 4),
+        duration: 0.25,
+        trace: *b"0123456789abcdef",
+    };
+    let bytes = span.to_bytes();
+    assert_eq!(bytes.len(), Span::SIZE);
+    assert_eq!(&bytes[..9], &[7, 0, 0, 0, 0, 0, 0, 0, 1]);
+
+    let mut byte_box = ByteBox::new();
+    assert!(byte_box.insert_record(b"span", &span));
+    assert_eq!(byte_box.get_record::<Span>(b"span"), Ok(Some(span)));
+    assert_eq!(
+        byte_box.get_record::<Point>(b"span"),
+        Err(RecordSizeMismatch {
+            expected: 8,
+            found: 41
+        })
+    );
+    assert_eq!(byte_box.get_record::<Point>(b"missing"), Ok(None));
+}
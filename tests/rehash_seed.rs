@@ -0,0 +1,29 @@
+use bytesbox::ByteBox;
+
+#[test]
+fn rehash_with_seed_keeps_entries() {
+    let mut byte_box = ByteBox::prealloc(64);
+    for i in 0..40u8 {
+        byte_box.insert(&[i; 3], &[i]);
+    }
+    let before: Vec<usize> = byte_box.chain_lengths().collect();
+
+    byte_box.rehash_with_seed(0xdead_beef);
+    assert_eq!(byte_box.seed(), 0xdead_beef);
+    assert_eq!(byte_box.allocation(), 64);
+    assert_eq!(byte_box.len(), 40);
+    assert_ne!(byte_box.chain_lengths().collect::<Vec<_>>(), before);
+
+    for i in 0..40u8 {
+        assert_eq!(byte_box.get(&[i; 3]), Some(&[i][..]));
+    }
+    for i in 40..80u8 {
+        byte_box.insert(&[i; 3], &[i]);
+    }
+    assert_eq!(byte_box.remove(&[5; 3]), Some(vec![5]));
+    assert_eq!(byte_box.get(&[79; 3]), Some(&[79][..]));
+
+    byte_box.rehash_with_seed(0);
+    assert_eq!(byte_box.get(&[79; 3]), Some(&[79][..]));
+    assert_eq!(byte_box.len(), 79);
+}
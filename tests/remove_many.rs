@@ -0,0 +1,27 @@
+use bytesbox::ByteBox;
+
+#[test]
+fn remove_many_matches_individual_removes() {
+    let mut byte_box = ByteBox::new();
+    assert_eq!(byte_box.remove_many(&[b"a"]), 0);
+
+    for i in 0..1_000u32 {
+        byte_box.insert(&i.to_le_bytes(), &i.to_be_bytes());
+    }
+    let mut expected = byte_box.clone();
+
+    let owned: Vec<[u8; 4]> = (500..1_200u32).rev().map(u32::to_le_bytes).collect();
+    let mut keys: Vec<&[u8]> = owned.iter().map(|key| &key[..]).collect();
+    keys.push(&owned[300]);
+
+    let individually = keys
+        .iter()
+        .filter(|key| expected.remove(key).is_some())
+        .count();
+    assert_eq!(byte_box.remove_many(&keys), individually);
+    assert_eq!(individually, 500);
+    assert_eq!(byte_box.len(), 500);
+    assert_eq!(byte_box.content_hash(), expected.content_hash());
+    assert!((0..500u32).all(|i| byte_box.get(&i.to_le_bytes()).is_some()));
+    assert_eq!(byte_box.remove_many(&keys), 0);
+}
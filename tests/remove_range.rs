@@ -0,0 +1,36 @@
+use bytesbox::tree::ByteTree;
+use std::ops::Bound;
+
+fn tree() -> ByteTree {
+    let mut tree = ByteTree::new();
+    for i in 0..10u8 {
+        tree.insert(&[i], &[i]);
+    }
+    tree
+}
+
+fn keys(tree: &ByteTree) -> Vec<u8> {
+    tree.iter().map(|(key, _)| key[0]).collect()
+}
+
+#[test]
+fn remove_range_honours_every_bound() {
+    let mut cut = tree();
+    assert_eq!(cut.remove_range(&[2u8][..]..&[5u8][..]), 3);
+    assert_eq!(keys(&cut), vec![0, 1, 5, 6, 7, 8, 9]);
+    assert_eq!(cut.remove_range(&[5u8][..]..=&[6u8][..]), 2);
+    assert_eq!(cut.remove_range(&[8u8][..]..), 2);
+    assert_eq!(cut.remove_range(..=&[0u8][..]), 1);
+    assert_eq!(keys(&cut), vec![1, 7]);
+
+    let mut open = tree();
+    let bounds: (Bound<&[u8]>, Bound<&[u8]>) = (Bound::Excluded(&[3]), Bound::Excluded(&[6]));
+    assert_eq!(open.remove_range(bounds), 2);
+    assert_eq!(keys(&open), vec![0, 1, 2, 3, 6, 7, 8, 9]);
+
+    let mut inverted = tree();
+    assert_eq!(inverted.remove_range(&[7u8][..]..&[2u8][..]), 0);
+    assert_eq!(inverted, tree());
+    assert_eq!(inverted.remove_range(..&[0xffu8][..]), 10);
+    assert!(inverted.is_empty());
+}
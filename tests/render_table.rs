@@ -0,0 +1,45 @@
+use bytesbox::debug::RenderOptions;
+use bytesbox::ByteBox;
+
+#[test]
+fn render_table() {
+    let mut byte_box = ByteBox::prealloc(2);
+    byte_box.insert(b"key1", b"value1");
+
+    let table = byte_box.render_table();
+    assert!(table.contains("ByteBox, number of cell (1), allocation (2)"));
+    assert!(table.contains("  Cell 0:"));
+    assert!(table.contains("  Cell 1:"));
+    assert!(table.contains("| v |->| value1 |"));
+}
+
+#[test]
+fn render_table_with_densest_first_and_collapsed_empty_cells() {
+    let mut byte_box = ByteBox::prealloc(64);
+    for i in 0..40 {
+        byte_box.insert(format!("key{}", i).as_bytes(), b"value");
+    }
+    let lengths: Vec<usize> = byte_box.chain_lengths().collect();
+    let empty = lengths.iter().filter(|&&len| len == 0).count();
+
+    let table = byte_box.render_table_with(RenderOptions {
+        densest_first: true,
+        collapse_empty: true,
+    });
+    assert!(table.contains(&format!("  {} empty cells", empty)));
+
+    let listed: Vec<usize> = table
+        .lines()
+        .filter_map(|line| line.strip_prefix("  Cell "))
+        .map(|rest| rest.trim_end_matches(':').parse().unwrap())
+        .collect();
+    assert_eq!(listed.len(), lengths.len() - empty);
+    assert!(listed
+        .windows(2)
+        .all(|pair| lengths[pair[0]] >= lengths[pair[1]]));
+
+    assert_eq!(
+        byte_box.render_table_with(RenderOptions::default()),
+        byte_box.render_table()
+    );
+}
@@ -0,0 +1,26 @@
+use bytesbox::ByteBox;
+
+#[test]
+fn replace_in_place_keeps_buffer() {
+    let mut byte_box = ByteBox::new();
+    byte_box.insert(b"record", &[0u8; 64]);
+
+    let before = byte_box.get(b"record").unwrap().as_ptr();
+    for round in 1..=10u8 {
+        assert_eq!(
+            byte_box.replace_in_place(b"record", &[round; 64]),
+            Ok(false)
+        );
+    }
+    let after = byte_box.get(b"record").unwrap();
+    assert_eq!(after.as_ptr(), before);
+    assert_eq!(after, &[10u8; 64][..]);
+
+    assert_eq!(byte_box.replace_in_place(b"record", &[1u8; 128]), Ok(true));
+    assert_eq!(byte_box.get(b"record"), Some(&[1u8; 128][..]));
+    assert_eq!(byte_box.len(), 1);
+
+    let err = byte_box.replace_in_place(b"missing", b"value").unwrap_err();
+    assert_eq!(err.key, b"missing".to_vec());
+    assert_eq!(byte_box.get(b"missing"), None);
+}
@@ -0,0 +1,30 @@
+use bytesbox::ByteBox;
+
+#[test]
+fn retain_and_remove_prefix() {
+    let mut byte_box = ByteBox::prealloc(4);
+    for i in 0..3u8 {
+        byte_box.insert(&[b's', i], b"session");
+        byte_box.insert(&[b'c', i], b"config");
+    }
+    // Force long chains so removals hit heads, middles and tails.
+    byte_box.insert(b"keep", b"x");
+
+    assert_eq!(byte_box.remove_prefix(b"s"), 3);
+    assert_eq!(byte_box.len(), 4);
+    assert!(byte_box.iter().all(|(key, _)| !key.starts_with(b"s")));
+
+    let removed = byte_box.retain(|key, value| {
+        value.push(b'!');
+        key != b"keep"
+    });
+    assert_eq!(removed, 1);
+    assert_eq!(byte_box.len(), 3);
+    assert_eq!(byte_box.get(&[b'c', 1]), Some(&b"config!"[..]));
+    assert_eq!(byte_box.iter().count(), 3);
+
+    assert_eq!(byte_box.remove(&[b'c', 0]), Some(b"config!".to_vec()));
+    assert_eq!(byte_box.remove(&[b'c', 0]), None);
+    assert_eq!(byte_box.remove_prefix(b""), 2);
+    assert!(byte_box.is_empty());
+}
@@ -0,0 +1,51 @@
+use bytesbox::router::ByteBoxRouter;
+
+#[test]
+fn router_moves_only_reassigned_keys() {
+    let mut router = ByteBoxRouter::with_shards(4);
+    let keys: Vec<Vec<u8>> = (0..2000)
+        .map(|i| format!("key{}", i).into_bytes())
+        .collect();
+    for key in &keys {
+        router.insert(key, key);
+    }
+    let before: Vec<_> = keys.iter().map(|key| router.shard_for(key)).collect();
+
+    // A new shard only takes keys; everything else stays where it was.
+    let (added, moved) = router.add_shard();
+    let mut reassigned = 0;
+    for (key, owner) in keys.iter().zip(&before) {
+        let now = router.shard_for(key);
+        if now != *owner {
+            assert_eq!(now, Some(added));
+            reassigned += 1;
+        }
+        assert_eq!(router.get(key), Some(&key[..]));
+    }
+    assert_eq!(reassigned, moved);
+    assert!(moved > 200 && moved < 700, "moved {}", moved);
+    assert_eq!(router.shard(added).unwrap().len(), moved);
+
+    // Removing it sends its keys back to their previous owners.
+    let removed = router.remove_shard(added).unwrap();
+    assert!(removed.is_empty());
+    assert_eq!(router.len(), 2000);
+    for (key, owner) in keys.iter().zip(&before) {
+        assert_eq!(router.shard_for(key), *owner);
+        assert_eq!(router.get(key), Some(&key[..]));
+    }
+    assert!(router.remove_shard(added).is_none());
+
+    assert_eq!(router.remove(b"key7"), Some(b"key7".to_vec()));
+    assert_eq!(router.get(b"key7"), None);
+}
+
+#[test]
+fn router_assignment_is_stable_across_builds() {
+    let router = ByteBoxRouter::with_shards(4);
+    let owners: Vec<u64> = (0..8)
+        .map(|i| router.shard_for(format!("key{}", i).as_bytes()).unwrap().0)
+        .collect();
+    // Pinned: a change here reshuffles keys between the shards of deployed routers.
+    assert_eq!(owners, [0, 3, 2, 0, 0, 3, 0, 2]);
+}
@@ -0,0 +1,30 @@
+use bytesbox::segmented::{SegmentedByteBox, SEGMENT_CELLS};
+
+#[test]
+fn segmented_grows_by_splitting() {
+    let mut table = SegmentedByteBox::new();
+    for i in 0..50_000u32 {
+        assert!(table.insert(&i.to_le_bytes(), &i.to_be_bytes()));
+    }
+    assert!(!table.insert(&7u32.to_le_bytes(), b"updated"));
+
+    assert_eq!(table.len(), 50_000);
+    assert!(table.segment_count() >= 50_000 / SEGMENT_CELLS);
+    assert!(table.segment_count() <= 1 << table.global_depth());
+    assert_eq!(table.get(&7u32.to_le_bytes()), Some(&b"updated"[..]));
+    for i in (0..50_000u32).step_by(97).filter(|&i| i != 7) {
+        assert_eq!(table.get(&i.to_le_bytes()), Some(&i.to_be_bytes()[..]));
+    }
+    assert_eq!(table.iter().count(), 50_000);
+    assert!(table.stats().longest_chain < 16);
+
+    for i in 0..25_000u32 {
+        assert!(table.remove(&i.to_le_bytes()).is_some());
+    }
+    assert_eq!(table.remove(&0u32.to_le_bytes()), None);
+    assert_eq!(table.len(), 25_000);
+    assert_eq!(
+        table.get(&30_000u32.to_le_bytes()),
+        Some(&30_000u32.to_be_bytes()[..])
+    );
+}
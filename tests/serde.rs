@@ -0,0 +1,56 @@
+#![cfg(feature = "serde")]
+
+use bytesbox::ByteBox;
+use serde::{Deserialize, Deserializer};
+use serde_test::{assert_de_tokens, assert_ser_tokens, Token};
+
+/// Compares tables by content, which `assert_de_tokens` requires.
+#[derive(Debug)]
+struct Table(ByteBox);
+
+impl PartialEq for Table {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.len() == other.0.len()
+            && self
+                .0
+                .iter()
+                .all(|(key, value)| other.0.get(key) == Some(value))
+    }
+}
+
+impl<'de> Deserialize<'de> for Table {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        ByteBox::deserialize(deserializer).map(Table)
+    }
+}
+
+#[test]
+fn serde_uses_byte_strings() {
+    let mut byte_box = ByteBox::new();
+    byte_box.insert(b"key", &[0, 159, 146, 150]);
+
+    assert_ser_tokens(
+        &byte_box,
+        &[
+            Token::Map { len: Some(1) },
+            Token::Bytes(b"key"),
+            Token::Bytes(&[0, 159, 146, 150]),
+            Token::MapEnd,
+        ],
+    );
+
+    let mut expected = ByteBox::new();
+    expected.insert(b"a", b"1");
+    expected.insert(b"b", b"");
+    assert_de_tokens(
+        &Table(expected),
+        &[
+            Token::Map { len: Some(2) },
+            Token::Bytes(b"a"),
+            Token::Bytes(b"1"),
+            Token::Bytes(b"b"),
+            Token::Bytes(b""),
+            Token::MapEnd,
+        ],
+    );
+}
@@ -0,0 +1,33 @@
+use bytesbox::sharded::ThreadLocalByteBox;
+use std::sync::Arc;
+use std::thread;
+
+#[test]
+fn thread_local_merge() {
+    let shards = Arc::new(ThreadLocalByteBox::new());
+
+    let handles: Vec<_> = (0..4u8)
+        .map(|i| {
+            let shards = Arc::clone(&shards);
+            thread::spawn(move || {
+                shards.insert(format!("thread{}", i).as_bytes(), &[i]);
+                shards.insert(b"requests", &[10]);
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    assert_eq!(shards.shard_count(), 4);
+
+    let merged = shards.merge_all();
+    assert_eq!(merged.len(), 5);
+    assert_eq!(merged.get(b"thread2"), Some(&[2u8][..]));
+
+    let totals = shards.aggregate(|_, acc, value| vec![acc[0] + value[0]]);
+    assert_eq!(totals.get(b"requests"), Some(&[40u8][..]));
+
+    shards.clear();
+    assert!(shards.merge_all().is_empty());
+}
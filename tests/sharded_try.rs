@@ -0,0 +1,28 @@
+use bytesbox::sharded::ThreadLocalByteBox;
+use std::sync::{mpsc, Arc};
+use std::thread;
+
+#[test]
+fn try_accessors_fail_fast_on_a_locked_shard() {
+    let shards = Arc::new(ThreadLocalByteBox::new());
+    assert_eq!(shards.try_insert(b"key", b"value"), Some(true));
+    assert_eq!(shards.try_get(b"key"), Some(Some(b"value".to_vec())));
+    assert_eq!(shards.try_get(b"missing"), Some(None));
+
+    // Hold this thread's shard locked from within, then probe it from the same shard
+    // through another handle: the nested attempt must not block.
+    shards.with_local(|_| {
+        let (sender, receiver) = mpsc::channel();
+        let probe = Arc::clone(&shards);
+        thread::scope(|scope| {
+            scope.spawn(move || {
+                // A different thread gets its own shard and is not blocked.
+                sender.send(probe.try_insert(b"other", b"thread")).unwrap();
+            });
+        });
+        assert_eq!(receiver.recv().unwrap(), Some(true));
+        assert_eq!(shards.try_insert(b"key", b"blocked"), None);
+        assert_eq!(shards.try_get(b"key"), None);
+    });
+    assert_eq!(shards.try_get(b"key"), Some(Some(b"value".to_vec())));
+}
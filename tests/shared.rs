@@ -0,0 +1,31 @@
+#![cfg(feature = "bytes")]
+
+use bytes::{Bytes, BytesMut};
+use bytesbox::shared::SharedByteBox;
+
+#[test]
+fn shared_values_are_not_copied() {
+    let mut table = SharedByteBox::new();
+    let payload = Bytes::from(vec![7u8; 4096]);
+    assert!(table.insert_bytes(b"payload", payload.clone()));
+
+    let handle = table.get_bytes(b"payload").unwrap();
+    assert_eq!(handle.as_ptr(), payload.as_ptr());
+
+    let mut buf = BytesMut::with_capacity(16);
+    buf.extend_from_slice(b"frame");
+    let ptr = buf.as_ptr();
+    assert!(!table.insert_bytes(b"payload", buf));
+    assert_eq!(table.get(b"payload").unwrap().as_ptr(), ptr);
+    assert_eq!(handle.len(), 4096);
+
+    assert!(table.insert(b"copied", b"value"));
+    assert_eq!(table.iter().count(), 2);
+    let removed = table.remove(b"payload").unwrap();
+    assert_eq!(removed.as_ptr(), ptr);
+    assert_eq!(table.get(b"payload"), None);
+    assert_eq!(table.len(), 1);
+
+    assert!(table.insert_bytes(b"reused", Bytes::from_static(b"slot")));
+    assert_eq!(table.get(b"reused"), Some(&b"slot"[..]));
+}
@@ -0,0 +1,34 @@
+use bytesbox::snapshot::SnapshotReader;
+use bytesbox::ByteBox;
+use std::io::ErrorKind;
+
+#[test]
+fn snapshot_roundtrip() {
+    let mut byte_box = ByteBox::new();
+    for i in 0..100u32 {
+        byte_box.insert(format!("key{}", i).as_bytes(), &i.to_le_bytes());
+    }
+    byte_box.insert(b"", b"");
+
+    let mut file = Vec::new();
+    byte_box.write_snapshot(&mut file).unwrap();
+    let restored = ByteBox::read_snapshot(&file[..]).unwrap();
+    assert_eq!(restored.len(), 101);
+    for (key, value) in byte_box.iter() {
+        assert_eq!(restored.get(key), Some(value));
+    }
+}
+
+#[test]
+fn snapshot_rejects_bad_input() {
+    let err = SnapshotReader::new(&b"NOTABOX1\0\0\0\0\0\0\0\0"[..]).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+    let mut byte_box = ByteBox::new();
+    byte_box.insert(b"key", b"a long enough value");
+    let mut file = Vec::new();
+    byte_box.write_snapshot(&mut file).unwrap();
+    file.truncate(file.len() - 4);
+    let err = ByteBox::read_snapshot(&file[..]).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+}
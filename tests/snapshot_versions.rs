@@ -0,0 +1,44 @@
+use bytesbox::snapshot::{SnapshotFormat, SnapshotReader, SECTION_CRITICAL, SECTION_ENTRIES};
+use bytesbox::ByteBox;
+use std::io::ErrorKind;
+
+fn section(tag: u64, payload: &[u8]) -> Vec<u8> {
+    let mut bytes = tag.to_le_bytes().to_vec();
+    bytes.extend_from_slice(&(payload.len() as u64).to_le_bytes());
+    bytes.extend_from_slice(payload);
+    bytes
+}
+
+#[test]
+fn v2_readers_skip_unknown_sections() {
+    let mut byte_box = ByteBox::new();
+    byte_box.insert(b"key", b"value");
+    let mut v1 = Vec::new();
+    byte_box.write_snapshot(&mut v1).unwrap();
+    let mut v2 = Vec::new();
+    byte_box
+        .write_snapshot_as(&mut v2, SnapshotFormat::LATEST)
+        .unwrap();
+    assert_eq!(
+        SnapshotReader::new(&v1[..]).unwrap().format(),
+        SnapshotFormat::V1
+    );
+
+    // A future writer adding an optional section in front of the entries.
+    let mut future = b"BYTEBOX2".to_vec();
+    future.extend(section(42, b"metadata an old reader does not know"));
+    future.extend_from_slice(&v2[8..]);
+    future.extend(section(43, b"trailing section"));
+    let restored = ByteBox::read_snapshot(&future[..]).unwrap();
+    assert_eq!(restored.get(b"key"), Some(&b"value"[..]));
+    assert_eq!(restored.len(), 1);
+
+    let mut critical = b"BYTEBOX2".to_vec();
+    critical.extend(section(SECTION_CRITICAL | 7, b""));
+    critical.extend(section(SECTION_ENTRIES, &0u64.to_le_bytes()));
+    let error = SnapshotReader::new(&critical[..]).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+
+    let error = SnapshotReader::new(&b"BYTEBOX9"[..]).unwrap_err();
+    assert!(error.to_string().contains("version 9"));
+}
@@ -0,0 +1,50 @@
+use bytesbox::ByteBox;
+use std::time::Duration;
+
+#[test]
+fn soft_removed_entries_can_be_restored_until_compacted() {
+    let mut byte_box = ByteBox::new();
+    for i in 0..10u8 {
+        byte_box.insert(&[i], &[i; 4]);
+    }
+
+    assert!(byte_box.soft_remove(&[1]));
+    assert!(byte_box.soft_remove(&[2]));
+    assert!(byte_box.soft_remove(&[3]));
+    assert!(!byte_box.soft_remove(b"missing"));
+    assert_eq!(byte_box.len(), 7);
+    assert!(!byte_box.contains_key(&[1]));
+    assert_eq!(byte_box.iter().count(), 7);
+    assert_eq!(byte_box.tombstones().count(), 3);
+
+    assert!(byte_box.restore(&[1]));
+    assert_eq!(byte_box.get(&[1]), Some(&[1u8; 4][..]));
+    assert!(!byte_box.restore(&[1]));
+
+    // Inserting the key again supersedes the soft removal.
+    byte_box.insert(&[2], b"new");
+    assert!(!byte_box.restore(&[2]));
+    assert_eq!(byte_box.get(&[2]), Some(&b"new"[..]));
+
+    assert_eq!(byte_box.compact(), 1);
+    assert!(!byte_box.restore(&[3]));
+    assert_eq!(byte_box.len(), 9);
+}
+
+#[test]
+fn tombstones_expire_after_the_retention_window() {
+    let mut byte_box = ByteBox::new();
+    byte_box.insert(b"key", b"value");
+    byte_box.set_tombstone_retention(Some(Duration::ZERO));
+    assert!(byte_box.soft_remove(b"key"));
+    assert_eq!(byte_box.tombstones().count(), 0);
+    assert!(!byte_box.restore(b"key"));
+
+    byte_box.insert(b"key", b"value");
+    byte_box.set_tombstone_retention(Some(Duration::from_secs(3600)));
+    byte_box.soft_remove(b"key");
+    assert!(byte_box
+        .tombstones()
+        .all(|(key, age)| key == b"key" && age < Duration::from_secs(3600)));
+    assert!(byte_box.restore(b"key"));
+}
@@ -0,0 +1,42 @@
+use bytesbox::static_bytebox;
+
+static_bytebox! {
+    static REASONS = {
+        b"100" => b"Continue",
+        b"101" => b"Switching Protocols",
+        b"200" => b"OK",
+        b"201" => b"Created",
+        b"204" => b"No Content",
+        b"301" => b"Moved Permanently",
+        b"302" => b"Found",
+        b"304" => b"Not Modified",
+        b"400" => b"Bad Request",
+        b"401" => b"Unauthorized",
+        b"403" => b"Forbidden",
+        b"404" => b"Not Found",
+        b"405" => b"Method Not Allowed",
+        b"409" => b"Conflict",
+        b"418" => b"I'm a teapot",
+        b"429" => b"Too Many Requests",
+        b"500" => b"Internal Server Error",
+        b"502" => b"Bad Gateway",
+        b"503" => b"Service Unavailable",
+        b"504" => b"Gateway Timeout",
+    };
+}
+
+static_bytebox! {
+    static EMPTY = {};
+}
+
+#[test]
+fn static_table_lookup() {
+    assert_eq!(REASONS.len(), 20);
+    for (key, value) in REASONS.iter() {
+        assert_eq!(REASONS.get(key), Some(value));
+    }
+    assert_eq!(REASONS.get(b"999"), None);
+    assert_eq!(REASONS.get(b""), None);
+    assert!(EMPTY.is_empty());
+    assert_eq!(EMPTY.get(b"200"), None);
+}
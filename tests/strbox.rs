@@ -0,0 +1,38 @@
+use bytesbox::error::InvalidUtf8Entry;
+use bytesbox::strbox::StrBox;
+use bytesbox::ByteBox;
+
+#[test]
+fn strbox_round_trips_through_byte_box() {
+    let mut config = StrBox::prealloc(4);
+    assert!(config.insert("name", "bytesbox"));
+    assert!(!config.insert("name", "bytes·box"));
+    config.insert("", "empty key");
+    assert_eq!(config.get("name"), Some("bytes·box"));
+    assert!(config.contains_key(""));
+    assert_eq!(config.iter().count(), 2);
+
+    let mut bytes = ByteBox::from(config.clone());
+    assert_eq!(
+        StrBox::try_from(bytes.clone()).unwrap().get(""),
+        Some("empty key")
+    );
+
+    bytes.insert(b"bin", b"\xc3");
+    assert_eq!(
+        StrBox::try_from(bytes.clone()).unwrap_err(),
+        InvalidUtf8Entry {
+            key: b"bin".to_vec(),
+            in_value: true,
+            valid_up_to: 0
+        }
+    );
+    bytes.remove(b"bin");
+    bytes.insert(b"k\xff", b"v");
+    let err = StrBox::try_from(bytes).unwrap_err();
+    assert!(!err.in_value);
+    assert_eq!(err.valid_up_to, 1);
+
+    config.clear();
+    assert!(config.is_empty());
+}
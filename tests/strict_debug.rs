@@ -0,0 +1,26 @@
+#![cfg(feature = "strict-debug")]
+use bytesbox::ByteBox;
+
+#[test]
+fn mutations_keep_invariants() {
+    let mut byte_box = ByteBox::new();
+    for i in 0..200u32 {
+        byte_box.insert(&i.to_le_bytes(), &i.to_be_bytes());
+    }
+    for i in (0..200u32).step_by(3) {
+        byte_box.insert(&i.to_le_bytes(), b"updated");
+        byte_box.remove(&(i + 1).to_le_bytes());
+    }
+    byte_box.retain(|key, value| {
+        value.push(key[0]);
+        key[0] % 2 == 0
+    });
+    let extracted = byte_box.extract_if(|key, _| key[0] % 4 == 0).count();
+    assert!(extracted > 0);
+    byte_box.rehash_with_seed(99);
+    byte_box
+        .swap(&2u32.to_le_bytes(), &6u32.to_le_bytes())
+        .unwrap();
+    byte_box.clear();
+    assert!(byte_box.is_empty());
+}
@@ -0,0 +1,21 @@
+use bytesbox::error::MissingKey;
+use bytesbox::ByteBox;
+
+#[test]
+fn swap() {
+    let mut byte_box = ByteBox::prealloc(1);
+    byte_box.insert(b"active", b"v1");
+    byte_box.insert(b"staged", b"v2");
+
+    assert_eq!(byte_box.swap(b"active", b"staged"), Ok(()));
+    assert_eq!(byte_box.get(b"active"), Some(&b"v2"[..]));
+    assert_eq!(byte_box.get(b"staged"), Some(&b"v1"[..]));
+
+    assert_eq!(
+        byte_box.swap(b"missing", b"active"),
+        Err(MissingKey {
+            key: b"missing".to_vec()
+        })
+    );
+    assert_eq!(byte_box.get(b"active"), Some(&b"v2"[..]));
+}
@@ -0,0 +1,23 @@
+use bytesbox::tagged::TaggedByteBox;
+
+#[test]
+fn tagged_values() {
+    let mut table = TaggedByteBox::new();
+    assert!(table.insert(b"blob", 3, &[0xde, 0xad]));
+    assert!(table.insert(b"empty", 7, b""));
+    assert!(!table.insert(b"blob", 4, b"replaced"));
+
+    assert_eq!(table.len(), 2);
+    assert_eq!(table.get_tagged(b"blob"), Some((4, &b"replaced"[..])));
+    assert_eq!(table.get(b"empty"), Some(&b""[..]));
+    assert_eq!(table.tag(b"empty"), Some(7));
+    assert_eq!(table.get_tagged(b"missing"), None);
+
+    let mut tags: Vec<u8> = table.iter().map(|(_, tag, _)| tag).collect();
+    tags.sort();
+    assert_eq!(tags, vec![4, 7]);
+
+    assert_eq!(table.remove(b"blob"), Some((4, b"replaced".to_vec())));
+    assert_eq!(table.remove(b"blob"), None);
+    assert_eq!(table.len(), 1);
+}
@@ -0,0 +1,37 @@
+#![cfg(feature = "testdata")]
+use bytesbox::snapshot::{SnapshotFormat, SnapshotReader};
+use bytesbox::testdata::{self, FIXTURE_ENTRIES};
+use bytesbox::ByteBox;
+
+#[test]
+fn snapshot_vectors_match_the_fixture() {
+    for (vector, format) in [
+        (testdata::SNAPSHOT_V1, SnapshotFormat::V1),
+        (testdata::SNAPSHOT_V2, SnapshotFormat::V2),
+        (testdata::SNAPSHOT_V2_SKIPPABLE_SECTION, SnapshotFormat::V2),
+    ] {
+        let reader = SnapshotReader::new(vector).unwrap();
+        assert_eq!(reader.format(), format);
+        let entries: Vec<_> = reader.map(Result::unwrap).collect();
+        let expected: Vec<_> = FIXTURE_ENTRIES
+            .iter()
+            .map(|(key, value)| (key.to_vec(), value.to_vec()))
+            .collect();
+        assert_eq!(entries, expected);
+    }
+    assert!(SnapshotReader::new(testdata::SNAPSHOT_V2_CRITICAL_SECTION).is_err());
+
+    // The crate's writer produces the vectors up to entry order.
+    let fixture = testdata::fixture();
+    for (vector, format, header) in [
+        (testdata::SNAPSHOT_V1, SnapshotFormat::V1, 16),
+        (testdata::SNAPSHOT_V2, SnapshotFormat::V2, 32),
+    ] {
+        let mut written = Vec::new();
+        fixture.write_snapshot_as(&mut written, format).unwrap();
+        assert_eq!(written.len(), vector.len());
+        assert_eq!(written[..header], vector[..header]);
+        let read = ByteBox::read_snapshot(&written[..]).unwrap();
+        assert_eq!(read.content_hash(), fixture.content_hash());
+    }
+}
@@ -0,0 +1,23 @@
+use bytesbox::error::PreallocError;
+use bytesbox::{ByteBox, DEFAULT_MAX_PREALLOC};
+
+#[test]
+fn try_prealloc_rejects_absurd_sizes() {
+    let mut byte_box = ByteBox::try_prealloc(64).unwrap();
+    assert_eq!(byte_box.allocation(), 64);
+    byte_box.insert(b"key", b"value");
+    assert_eq!(byte_box.get(b"key"), Some(&b"value"[..]));
+
+    assert_eq!(ByteBox::try_prealloc(0).unwrap().allocation(), 0);
+    assert_eq!(
+        ByteBox::try_prealloc(DEFAULT_MAX_PREALLOC + 1).unwrap_err(),
+        PreallocError::ExceedsLimit {
+            requested: DEFAULT_MAX_PREALLOC + 1,
+            limit: DEFAULT_MAX_PREALLOC
+        }
+    );
+    assert!(matches!(
+        ByteBox::try_prealloc_within(usize::MAX / 2, usize::MAX),
+        Err(PreallocError::OutOfMemory { .. })
+    ));
+}
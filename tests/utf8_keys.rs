@@ -0,0 +1,30 @@
+use bytesbox::error::InvalidUtf8Key;
+use bytesbox::utf8::Utf8KeyBox;
+use bytesbox::ByteBox;
+
+#[test]
+fn utf8_key_box_rejects_binary_keys() {
+    let mut config = Utf8KeyBox::new();
+    assert_eq!(config.insert("clé".as_bytes(), b"1"), Ok(true));
+    assert_eq!(config.insert("clé".as_bytes(), b"2"), Ok(false));
+
+    let err = config.insert(b"port\xc3", b"80").unwrap_err();
+    assert_eq!(
+        err,
+        InvalidUtf8Key {
+            key: b"port\xc3".to_vec(),
+            valid_up_to: 4
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "key \"port\u{fffd}\" is not valid UTF-8 (invalid byte at offset 4)"
+    );
+    assert_eq!(config.len(), 1);
+    assert_eq!(config.get("clé"), Some(&b"2"[..]));
+
+    let mut raw = ByteBox::new();
+    raw.insert(b"ok", b"");
+    raw.insert(b"\xff", b"");
+    assert_eq!(Utf8KeyBox::from_byte_box(raw).unwrap_err().valid_up_to, 0);
+}
@@ -0,0 +1,36 @@
+use bytesbox::ByteBox;
+
+#[test]
+fn percentiles_use_nearest_rank() {
+    let mut byte_box = ByteBox::new();
+    assert_eq!(byte_box.value_size_percentiles(&[50.0, 90.0]), vec![0, 0]);
+
+    for (i, size) in [10usize, 20, 30, 40, 1000].iter().enumerate() {
+        byte_box.insert(&[i as u8], &vec![b'x'; *size]);
+    }
+    assert_eq!(
+        byte_box.value_size_percentiles(&[0.0, 20.0, 21.0, 50.0, 80.0, 100.0]),
+        vec![10, 10, 20, 30, 40, 1000]
+    );
+    assert!(byte_box.value_size_percentiles(&[]).is_empty());
+}
+
+#[test]
+#[should_panic(expected = "between 0 and 100")]
+fn percentiles_out_of_range_panic() {
+    ByteBox::new().value_size_percentiles(&[100.5]);
+}
+
+#[test]
+fn key_percentiles_use_nearest_rank() {
+    let mut byte_box = ByteBox::new();
+    assert_eq!(byte_box.key_size_percentiles(&[50.0]), vec![0]);
+
+    for size in [1usize, 2, 3, 4, 100] {
+        byte_box.insert(&vec![b'k'; size], b"v");
+    }
+    assert_eq!(
+        byte_box.key_size_percentiles(&[0.0, 20.0, 21.0, 50.0, 100.0]),
+        vec![1, 1, 2, 3, 100]
+    );
+}
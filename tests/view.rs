@@ -0,0 +1,26 @@
+use bytesbox::view::ByteBoxView;
+use bytesbox::ByteBox;
+
+fn summarize(view: ByteBoxView<'_>) -> (usize, usize) {
+    let bytes = view
+        .into_iter()
+        .map(|(key, value)| key.len() + value.len())
+        .sum();
+    (view.len(), bytes)
+}
+
+#[test]
+fn read_only_view() {
+    let mut byte_box = ByteBox::new();
+    byte_box.insert(b"key1", b"value1");
+    byte_box.insert(b"key2", b"value2");
+
+    let view = byte_box.view();
+    let value = view.get(b"key1").unwrap();
+    assert_eq!(value, b"value1");
+    assert!(view.contains_key(b"key2"));
+    assert!(!view.is_empty());
+    assert_eq!(view.stats().value_bytes, 12);
+    assert_eq!(summarize(view), (2, 20));
+    assert_eq!(summarize(ByteBoxView::from(&byte_box)), (2, 20));
+}
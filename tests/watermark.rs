@@ -0,0 +1,57 @@
+use bytesbox::watermark::{WatermarkEvent, WatermarkMetric};
+use bytesbox::ByteBox;
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn watermarks_fire_on_each_crossing() {
+    let events: Arc<Mutex<Vec<WatermarkEvent>>> = Arc::default();
+    let mut byte_box = ByteBox::new();
+    for level in [40, 60] {
+        let events = Arc::clone(&events);
+        byte_box.add_watermark(WatermarkMetric::Bytes, level, move |event| {
+            events.lock().unwrap().push(event)
+        });
+    }
+
+    // Each entry is 10 bytes: a 2 byte key and an 8 byte value.
+    for i in 0..5u8 {
+        byte_box.insert(&[b'k', i], b"payload!");
+    }
+    assert_eq!(byte_box.content_bytes(), 50);
+    let crossed: Vec<_> = events.lock().unwrap().drain(..).collect();
+    assert_eq!(
+        crossed,
+        vec![WatermarkEvent {
+            metric: WatermarkMetric::Bytes,
+            level: 40,
+            current: 40,
+            rising: true
+        }]
+    );
+
+    // Growing a value in place counts too, and staying above does not fire again.
+    byte_box.insert(b"k\x00", b"a much longer payload");
+    byte_box.insert(b"k\x01", b"another longer one");
+    let crossed: Vec<_> = events
+        .lock()
+        .unwrap()
+        .drain(..)
+        .map(|e| (e.level, e.rising))
+        .collect();
+    assert_eq!(crossed, vec![(60, true)]);
+
+    byte_box.retain(|key, _| key[1] > 2);
+    assert_eq!(byte_box.content_bytes(), 20);
+    let crossed: Vec<_> = events
+        .lock()
+        .unwrap()
+        .drain(..)
+        .map(|e| (e.level, e.rising))
+        .collect();
+    assert_eq!(crossed, vec![(40, false), (60, false)]);
+
+    byte_box.clear_watermarks();
+    byte_box.clear();
+    assert_eq!(byte_box.content_bytes(), 0);
+    assert!(events.lock().unwrap().is_empty());
+}
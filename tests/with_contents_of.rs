@@ -0,0 +1,24 @@
+use bytesbox::outcome::InsertOutcome;
+use bytesbox::ByteBox;
+
+#[test]
+fn prefill_never_resizes() {
+    for len in [0u32, 1, 11, 12, 13, 96, 97, 1_000, 3_000] {
+        let mut source = ByteBox::new();
+        for i in 0..len {
+            source.insert(&i.to_le_bytes(), &i.to_be_bytes());
+        }
+
+        let copy = ByteBox::with_contents_of(&source);
+        assert_eq!(copy.len(), source.len());
+        assert_eq!(copy.content_hash(), source.content_hash());
+
+        // Replaying the inserts into a table of the same allocation never resizes.
+        let mut replay = ByteBox::prealloc(copy.allocation());
+        for (key, value) in source.iter() {
+            assert_ne!(replay.insert_report(key, value), InsertOutcome::Resized);
+        }
+        assert_eq!(replay.allocation(), copy.allocation());
+        assert!(copy.allocation() <= source.allocation().max(16));
+    }
+}
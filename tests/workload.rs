@@ -0,0 +1,25 @@
+#![cfg(feature = "workload")]
+use bytesbox::workload::{KeyDistribution, ValueSize, Workload};
+
+#[test]
+fn zipf_workload_is_skewed_and_reproducible() {
+    let workload = Workload {
+        key_space: 1_000,
+        keys: KeyDistribution::Zipf { exponent: 1.2 },
+        values: ValueSize::Uniform { min: 4, max: 8 },
+        seed: 7,
+    };
+
+    let first: Vec<_> = workload.pairs().take(100).collect();
+    let second: Vec<_> = workload.pairs().take(100).collect();
+    assert_eq!(first, second);
+    assert!(first.iter().all(|(_, v)| (4..=8).contains(&v.len())));
+
+    let hottest = Workload::key(0);
+    let hits = workload
+        .keys()
+        .take(10_000)
+        .filter(|k| *k == hottest)
+        .count();
+    assert!(hits > 1_000);
+}